@@ -1,12 +1,15 @@
 use std::{
-    path::PathBuf,
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
     time::{SystemTime, UNIX_EPOCH},
 };
 
 use clap::{Parser, Subcommand};
-use oxvg_ast::serialize::Node;
+use oxvg_ast::{implementations::markup5ever::Element5Ever, serialize::Node};
+use oxvg_optimiser::Jobs;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-use crate::config::Config;
+use crate::{config::Config, fs::load_files};
 
 pub trait RunCommand {
     /// # Errors
@@ -36,6 +39,8 @@ pub enum Command {
     /// Optimise SVG documents
     #[clap(alias = "optimize")]
     Optimise(Optimise),
+    /// Compare two SVG documents for potentially visible changes
+    Diff(Diff),
 }
 
 #[derive(clap::Args)]
@@ -47,6 +52,33 @@ pub struct Optimise {
     /// Defaults to stdout
     #[clap(long = "output", short = 'o')]
     pub output: Option<PathBuf>,
+    /// Overwrite each of the target files with its optimised result
+    #[clap(long = "in-place", short = 'i', conflicts_with = "output")]
+    pub in_place: bool,
+    /// Print the target files which aren't already optimised, without writing any changes, and
+    /// exit with an error if there are any
+    #[clap(long = "check", conflicts_with_all = ["output", "in_place", "stats"])]
+    pub check: bool,
+    /// Print the original and optimised size of each file, including when gzip compressed
+    #[clap(long = "stats")]
+    pub stats: bool,
+    /// The maximum number of files to optimise at once.
+    /// Defaults to the number of CPUs
+    #[clap(long = "jobs", short = 'j')]
+    pub jobs: Option<usize>,
+}
+
+#[derive(clap::Args)]
+pub struct Diff {
+    /// The original document
+    #[clap(value_parser)]
+    pub a: PathBuf,
+    /// The document to compare against the original
+    #[clap(value_parser)]
+    pub b: PathBuf,
+    /// The distance a path's bounds may move before it's considered a visible change
+    #[clap(long = "threshold", short = 't', default_value_t = 0.01)]
+    pub threshold: f64,
 }
 
 struct StdoutCounter {
@@ -77,39 +109,592 @@ impl std::io::Write for StdoutCounter {
     }
 }
 
+#[derive(Default, Clone, Copy)]
+struct SizeStats {
+    original: usize,
+    optimised: usize,
+    original_gzip: usize,
+    optimised_gzip: usize,
+}
+
+impl SizeStats {
+    /// # Errors
+    /// If gzip compression fails
+    fn new(original: &[u8], optimised: &[u8]) -> std::io::Result<Self> {
+        Ok(Self {
+            original: original.len(),
+            optimised: optimised.len(),
+            original_gzip: Self::gzip_len(original)?,
+            optimised_gzip: Self::gzip_len(optimised)?,
+        })
+    }
+
+    fn gzip_len(bytes: &[u8]) -> std::io::Result<usize> {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes)?;
+        Ok(encoder.finish()?.len())
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.original += other.original;
+        self.optimised += other.optimised;
+        self.original_gzip += other.original_gzip;
+        self.optimised_gzip += other.optimised_gzip;
+    }
+}
+
+impl std::fmt::Display for SizeStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        #[allow(clippy::cast_precision_loss)]
+        let saving = |before: usize, after: usize| {
+            if before == 0 {
+                0.0
+            } else {
+                100.0 - after as f64 / before as f64 * 100.0
+            }
+        };
+        write!(
+            f,
+            "{} B -> {} B ({:.1}% smaller), gzip {} B -> {} B ({:.1}% smaller)",
+            self.original,
+            self.optimised,
+            saving(self.original, self.optimised),
+            self.original_gzip,
+            self.optimised_gzip,
+            saving(self.original_gzip, self.optimised_gzip),
+        )
+    }
+}
+
+impl Optimise {
+    /// Runs `f` against each of the target files in parallel, returning the results in the
+    /// order the files were loaded
+    fn map_files<T: Send>(
+        &self,
+        config: &config::Config,
+        f: impl Fn(&Jobs<Element5Ever>, &PathBuf, &[u8]) -> anyhow::Result<T> + Sync,
+    ) -> anyhow::Result<Vec<(PathBuf, anyhow::Result<T>)>> {
+        let files = load_files(&self.paths);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs.unwrap_or_default())
+            .build()?;
+        Ok(pool.install(|| {
+            files
+                .into_par_iter()
+                .map_init(
+                    || Config::new_jobs(config),
+                    |jobs, (path, source)| {
+                        let result = match jobs {
+                            Ok(jobs) => f(jobs, &path, &source),
+                            Err(err) => Err(anyhow::anyhow!("{err}")),
+                        };
+                        (path, result)
+                    },
+                )
+                .collect()
+        }))
+    }
+
+    fn run_check(&self, config: &config::Config) -> anyhow::Result<()> {
+        let mut changed = 0;
+        let mut failed = 0;
+        let results = self.map_files(config, |jobs, _path, source| {
+            let optimised = jobs.run_str(std::str::from_utf8(source)?)?;
+            Ok(optimised.as_bytes() != source)
+        })?;
+        for (path, result) in results {
+            match result {
+                Ok(true) => {
+                    println!("{}", path.display());
+                    changed += 1;
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    log::error!("{}: {err}", path.display());
+                    failed += 1;
+                }
+            }
+        }
+
+        if failed > 0 {
+            anyhow::bail!("Failed to optimise {failed} file(s)");
+        }
+        if changed > 0 {
+            anyhow::bail!("{changed} file(s) would change");
+        }
+        Ok(())
+    }
+
+    fn run_in_place(&self, config: &config::Config) -> anyhow::Result<()> {
+        let mut failed = 0;
+        let mut total = SizeStats::default();
+        let results = self.map_files(config, |jobs, path, source| {
+            self.optimise_in_place(jobs, path, source)
+        })?;
+        for (path, result) in results {
+            match result {
+                Ok(Some(stats)) => {
+                    eprintln!("{}: {stats}", path.display());
+                    total.add(&stats);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    log::error!("{}: {err}", path.display());
+                    failed += 1;
+                }
+            }
+        }
+
+        if self.stats {
+            eprintln!("Total: {total}");
+        }
+        if failed > 0 {
+            anyhow::bail!("Failed to optimise {failed} file(s)");
+        }
+        Ok(())
+    }
+
+    fn run_many(&self, config: &config::Config) -> anyhow::Result<()> {
+        use std::io::Write;
+
+        let mut failed = 0;
+        let mut total = SizeStats::default();
+        let results = self.map_files(config, |jobs, _path, source| {
+            let optimised = jobs.run_str(std::str::from_utf8(source)?)?.into_bytes();
+            let stats = if self.stats {
+                Some(SizeStats::new(source, &optimised)?)
+            } else {
+                None
+            };
+            Ok((optimised, stats))
+        })?;
+        let mut stdout = std::io::stdout();
+        for (path, result) in results {
+            let written = result.and_then(|(optimised, stats)| {
+                match &self.output {
+                    Some(output) => {
+                        let output = Self::output_path(output, &path)?;
+                        std::fs::write(&output, &optimised)?;
+                        log::info!("Optimised {} to {}", path.display(), output.display());
+                    }
+                    None => {
+                        stdout.write_all(&optimised)?;
+                        writeln!(stdout)?;
+                    }
+                }
+                Ok(stats)
+            });
+            match written {
+                Ok(Some(stats)) => {
+                    eprintln!("{}: {stats}", path.display());
+                    total.add(&stats);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    log::error!("{}: {err}", path.display());
+                    failed += 1;
+                }
+            }
+        }
+
+        if self.stats {
+            eprintln!("Total: {total}");
+        }
+        if failed > 0 {
+            anyhow::bail!("Failed to optimise {failed} file(s)");
+        }
+        Ok(())
+    }
+
+    /// Returns the path within the output directory to write the optimised `source` to
+    fn output_path(output: &Path, source: &Path) -> anyhow::Result<PathBuf> {
+        let file_name = source
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("{} is not a file", source.display()))?;
+        std::fs::create_dir_all(output)?;
+        Ok(output.join(file_name))
+    }
+
+    fn optimise_in_place(
+        &self,
+        jobs: &Jobs<Element5Ever>,
+        path: &PathBuf,
+        source: &[u8],
+    ) -> anyhow::Result<Option<SizeStats>> {
+        let optimised = jobs.run_str(std::str::from_utf8(source)?)?.into_bytes();
+        std::fs::write(path, &optimised)?;
+        log::info!("Optimised {}", path.display());
+
+        if self.stats {
+            Ok(Some(SizeStats::new(source, &optimised)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 impl RunCommand for Optimise {
     fn run(&self, config: Config) -> anyhow::Result<()> {
         use oxvg_ast::{implementations::markup5ever::Node5Ever, parse::Node};
 
-        if self.paths.len() == 1 {
-            let file = std::fs::File::open(self.paths.first().unwrap())?;
-            let dom = Node5Ever::parse_file(&file)?;
-            let jobs = config.optimisation.unwrap_or_default();
+        if self.check {
+            return self.run_check(&config.source);
+        }
+        if self.in_place {
+            return self.run_in_place(&config.source);
+        }
+        if self.paths.len() != 1 || !self.paths[0].is_file() {
+            return self.run_many(&config.source);
+        }
 
-            let start_time = SystemTime::now().duration_since(UNIX_EPOCH)?;
-            let prev_file_size = file.metadata()?.len();
+        let path = self.paths.first().unwrap();
+        let file = std::fs::File::open(path)?;
+        let dom = Node5Ever::parse_file(&file)?;
+        let jobs = config.optimisation.unwrap_or_default();
 
-            jobs.run(&dom)?;
-            let mut stdout = StdoutCounter::new();
+        let start_time = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let prev_file_size = file.metadata()?.len();
+
+        jobs.run(&dom)?;
+        let mut stdout = StdoutCounter::new();
+        if self.stats {
+            use std::io::Write;
+
+            let mut optimised = Vec::new();
+            dom.serialize_into(&mut optimised)?;
+            stdout.write_all(&optimised)?;
+            let stats = SizeStats::new(&std::fs::read(path)?, &optimised)?;
+            eprintln!("{}: {stats}", path.display());
+        } else {
             dom.serialize_into(&mut stdout)?;
+        }
+
+        let result_file_size = stdout.count;
+        let end_time = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let duration = end_time - start_time;
+
+        log::info!("Done in {duration:?}!");
+        log::info!(
+            "{}.{:#1} KiB -> {}.{:#1} KiB",
+            prev_file_size / 1000,
+            prev_file_size % 1000,
+            result_file_size / 1000,
+            result_file_size % 1000
+        );
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct DiffReport {
+    visible: Vec<String>,
+    cosmetic: Vec<String>,
+}
+
+impl Diff {
+    /// Attributes that have no effect on how a document is rendered
+    const COSMETIC_ATTRIBUTES: [&'static str; 3] = ["id", "class", "version"];
+
+    fn compare_elements(
+        &self,
+        a: &Element5Ever,
+        b: &Element5Ever,
+        index: usize,
+        report: &mut DiffReport,
+    ) {
+        use oxvg_ast::{
+            attribute::{Attr, Attributes},
+            element::Element,
+            name::Name,
+        };
+
+        let label = format!("<{}> #{index}", a.local_name());
+        if a.qual_name() != b.qual_name() {
+            report
+                .visible
+                .push(format!("{label}: element changed to <{}>", b.local_name()));
+            return;
+        }
 
-            let result_file_size = stdout.count;
-            let end_time = SystemTime::now().duration_since(UNIX_EPOCH)?;
-            let duration = end_time - start_time;
-
-            log::info!("Done in {duration:?}!");
-            log::info!(
-                "{}.{:#1} KiB -> {}.{:#1} KiB",
-                prev_file_size / 1000,
-                prev_file_size % 1000,
-                result_file_size / 1000,
-                result_file_size % 1000
+        let attrs = |element: &Element5Ever| -> BTreeMap<String, String> {
+            element
+                .attributes()
+                .into_iter()
+                .map(|attr| {
+                    (
+                        attr.name().formatter().to_string(),
+                        attr.value().to_string(),
+                    )
+                })
+                .collect()
+        };
+        let a_attrs = attrs(a);
+        let b_attrs = attrs(b);
+        if a_attrs == b_attrs {
+            return;
+        }
+
+        let names: BTreeSet<_> = a_attrs.keys().chain(b_attrs.keys()).collect();
+        for name in names {
+            let a_value = a_attrs.get(name);
+            let b_value = b_attrs.get(name);
+            if a_value == b_value {
+                continue;
+            }
+
+            let message = format!(
+                "{label}: `{name}` changed from {} to {}",
+                a_value
+                    .as_deref()
+                    .map_or("(none)".into(), |v| format!("{v:?}")),
+                b_value
+                    .as_deref()
+                    .map_or("(none)".into(), |v| format!("{v:?}")),
             );
-        } else {
-            for _path in &self.paths {
-                todo!();
+            let is_cosmetic = match (a_value.as_deref(), b_value.as_deref()) {
+                _ if Self::COSMETIC_ATTRIBUTES.contains(&&**name)
+                    || name.starts_with("xmlns")
+                    || name.starts_with("data-") =>
+                {
+                    true
+                }
+                (Some(a_value), Some(b_value)) if name == "d" && &**a.local_name() == "path" => {
+                    self.is_path_cosmetic(a_value, b_value)
+                }
+                (Some(a_value), Some(b_value)) => a_value
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .zip(b_value.trim().parse::<f64>().ok())
+                    .is_some_and(|(a, b)| a == b),
+                _ => false,
+            };
+            if is_cosmetic {
+                report.cosmetic.push(message);
+            } else {
+                report.visible.push(message);
+            }
+        }
+    }
+
+    /// Whether two path definitions overlap with bounds differing by no more than the threshold
+    fn is_path_cosmetic(&self, a: &str, b: &str) -> bool {
+        use oxvg_path::{points::Points, Path};
+
+        let (Ok(a), Ok(b)) = (Path::parse(a), Path::parse(b)) else {
+            return false;
+        };
+        if a.0.is_empty() || b.0.is_empty() {
+            return a.0.is_empty() && b.0.is_empty();
+        }
+
+        let a_points = Points::from_path(&a);
+        let b_points = Points::from_path(&b);
+        let bounds_delta = [
+            a_points.min_x - b_points.min_x,
+            a_points.min_y - b_points.min_y,
+            a_points.max_x - b_points.max_x,
+            a_points.max_y - b_points.max_y,
+        ]
+        .into_iter()
+        .fold(0.0, |acc: f64, delta| acc.max(delta.abs()));
+        if bounds_delta > self.threshold {
+            return false;
+        }
+        if a == b {
+            return true;
+        }
+        // NOTE: Hulls of lines and points have no area, so they're compared by their extents instead
+        match (self.line_extent(&a_points), self.line_extent(&b_points)) {
+            (Some([a_start, a_end]), Some([b_start, b_end])) => {
+                let is_near =
+                    |a: [f64; 2], b: [f64; 2]| (a[0] - b[0]).hypot(a[1] - b[1]) <= self.threshold;
+                (is_near(a_start, b_start) && is_near(a_end, b_end))
+                    || (is_near(a_start, b_end) && is_near(a_end, b_start))
             }
+            (None, None) => a.intersects(&b),
+            _ => false,
         }
+    }
+
+    /// The endpoints of the line the points lie on, or `None` when they cover an area
+    fn line_extent(&self, points: &oxvg_path::points::Points) -> Option<[[f64; 2]; 2]> {
+        let list: Vec<_> = points
+            .list
+            .iter()
+            .flat_map(|point| point.list.iter().map(|point| point.0))
+            .collect();
+        let first = *list.first()?;
+        let distance = |a: [f64; 2], b: [f64; 2]| (a[0] - b[0]).hypot(a[1] - b[1]);
+        let farthest_from = |from: [f64; 2]| {
+            list.iter()
+                .copied()
+                .max_by(|a, b| distance(from, *a).total_cmp(&distance(from, *b)))
+                .unwrap_or(from)
+        };
+        let start = farthest_from(first);
+        let end = farthest_from(start);
+        let length = distance(start, end);
+        if length <= self.threshold {
+            return Some([start, end]);
+        }
+        let is_on_line = list.iter().all(|point| {
+            let cross = (end[0] - start[0]) * (point[1] - start[1])
+                - (end[1] - start[1]) * (point[0] - start[0]);
+            (cross / length).abs() <= self.threshold
+        });
+        is_on_line.then_some([start, end])
+    }
+}
+
+impl RunCommand for Diff {
+    fn run(&self, _config: Config) -> anyhow::Result<()> {
+        use oxvg_ast::{element::Element, implementations::markup5ever::Node5Ever, parse::Node};
+
+        let parse = |path: &PathBuf| -> anyhow::Result<(Node5Ever, Vec<Element5Ever>)> {
+            let file = std::fs::File::open(path)?;
+            let dom = Node5Ever::parse_file(&file)?;
+            let root = Element5Ever::find_element(dom.clone())
+                .ok_or_else(|| anyhow::anyhow!("No root element found in {}", path.display()))?;
+            let elements = std::iter::once(root.clone())
+                .chain(root.breadth_first())
+                .collect();
+            // NOTE: The document is returned so that its children aren't dropped
+            Ok((dom, elements))
+        };
+        let (_a_dom, a_elements) = parse(&self.a)?;
+        let (_b_dom, b_elements) = parse(&self.b)?;
+
+        let mut report = DiffReport::default();
+        for (index, (a, b)) in a_elements.iter().zip(&b_elements).enumerate() {
+            self.compare_elements(a, b, index, &mut report);
+        }
+        if a_elements.len() != b_elements.len() {
+            report.visible.push(format!(
+                "element count changed from {} to {}",
+                a_elements.len(),
+                b_elements.len()
+            ));
+        }
+
+        for change in &report.visible {
+            println!("visible: {change}");
+        }
+        for change in &report.cosmetic {
+            println!("cosmetic: {change}");
+        }
+        println!(
+            "{} potentially visible change(s), {} cosmetic change(s)",
+            report.visible.len(),
+            report.cosmetic.len()
+        );
         Ok(())
     }
 }
+
+#[test]
+fn optimise_many() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join(format!("oxvg-optimise-many-{}", std::process::id()));
+    let input = dir.join("input");
+    let output = dir.join("output");
+    std::fs::create_dir_all(&input)?;
+    let a = input.join("a.svg");
+    let b = input.join("b.svg");
+    std::fs::write(
+        &a,
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><!-- a --><g/></svg>"#,
+    )?;
+    std::fs::write(
+        &b,
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><!-- b --><g/></svg>"#,
+    )?;
+
+    let optimise = Optimise {
+        paths: vec![a, b],
+        output: Some(output.clone()),
+        in_place: false,
+        check: false,
+        stats: true,
+        jobs: Some(2),
+    };
+    let result = optimise.run(Config::default());
+
+    let a = std::fs::read_to_string(output.join("a.svg"));
+    let b = std::fs::read_to_string(output.join("b.svg"));
+    std::fs::remove_dir_all(&dir)?;
+    result?;
+    let (a, b) = (a?, b?);
+    assert!(!a.contains("<!--"), "{a}");
+    assert!(!b.contains("<!--"), "{b}");
+    Ok(())
+}
+
+#[test]
+fn diff_degenerate_paths() {
+    let diff = Diff {
+        a: PathBuf::new(),
+        b: PathBuf::new(),
+        threshold: 0.01,
+    };
+    assert!(diff.is_path_cosmetic("M0 0H10", "M0 0h10"));
+    assert!(diff.is_path_cosmetic("M0 0L10 10", "M10 10L0 0"));
+    assert!(diff.is_path_cosmetic("M0 0V10", "M0 0V10.001"));
+    assert!(!diff.is_path_cosmetic("M0 10L10 0", "M0 0L10 10"));
+    assert!(!diff.is_path_cosmetic("M0 0H10", "M0 0H20"));
+    assert!(!diff.is_path_cosmetic("M0 0L10 10", "M0 0L10 10L0 10z"));
+}
+
+#[test]
+fn diff_path_data_on_other_elements() -> anyhow::Result<()> {
+    use oxvg_ast::{element::Element, implementations::markup5ever::Node5Ever, parse::Node};
+
+    let diff = Diff {
+        a: PathBuf::new(),
+        b: PathBuf::new(),
+        threshold: 0.01,
+    };
+    let parse = |source: &str| -> anyhow::Result<(Node5Ever, Element5Ever)> {
+        let dom = Node5Ever::parse(source)?;
+        let root = Element5Ever::find_element(dom.clone())
+            .ok_or_else(|| anyhow::anyhow!("No root element found"))?;
+        Ok((dom, root))
+    };
+
+    let (_a_dom, a) = parse(r#"<path d="M0 0H10"/>"#)?;
+    let (_b_dom, b) = parse(r#"<path d="M0 0h10"/>"#)?;
+    let mut report = DiffReport::default();
+    diff.compare_elements(&a, &b, 0, &mut report);
+    assert!(report.visible.is_empty(), "{:?}", report.visible);
+    assert_eq!(report.cosmetic.len(), 1);
+
+    let (_a_dom, a) = parse(r#"<glyph d="M0 0H10"/>"#)?;
+    let (_b_dom, b) = parse(r#"<glyph d="M0 0h10"/>"#)?;
+    let mut report = DiffReport::default();
+    diff.compare_elements(&a, &b, 0, &mut report);
+    assert_eq!(report.visible.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn diff_reordered_attributes() -> anyhow::Result<()> {
+    use oxvg_ast::{element::Element, implementations::markup5ever::Node5Ever, parse::Node};
+
+    let diff = Diff {
+        a: PathBuf::new(),
+        b: PathBuf::new(),
+        threshold: 0.01,
+    };
+    let a_dom = Node5Ever::parse(r#"<rect x="1" y="2" fill="red"/>"#)?;
+    let b_dom = Node5Ever::parse(r#"<rect fill="red" y="2" x="1"/>"#)?;
+    let a = Element5Ever::find_element(a_dom.clone())
+        .ok_or_else(|| anyhow::anyhow!("No root element"))?;
+    let b = Element5Ever::find_element(b_dom.clone())
+        .ok_or_else(|| anyhow::anyhow!("No root element"))?;
+    let mut report = DiffReport::default();
+    diff.compare_elements(&a, &b, 0, &mut report);
+    assert!(report.visible.is_empty(), "{:?}", report.visible);
+    assert!(report.cosmetic.is_empty(), "{:?}", report.cosmetic);
+    Ok(())
+}
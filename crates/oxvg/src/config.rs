@@ -5,4 +5,28 @@ use serde::Deserialize;
 #[serde(rename_all = "camelCase")]
 pub struct Config {
     pub optimisation: Option<oxvg_optimiser::Jobs<Element5Ever>>,
+    /// The source the config was deserialized from
+    #[serde(skip)]
+    pub source: config::Config,
+}
+
+impl Config {
+    /// # Errors
+    /// If the source fails to deserialize
+    pub fn from_source(source: config::Config) -> Result<Self, config::ConfigError> {
+        let config: Self = source.clone().try_deserialize()?;
+        Ok(Self { source, ..config })
+    }
+
+    /// Deserializes a new instance of the optimisation jobs, since jobs can't be shared between
+    /// threads
+    ///
+    /// # Errors
+    /// If the source fails to deserialize
+    pub fn new_jobs(
+        source: &config::Config,
+    ) -> Result<oxvg_optimiser::Jobs<Element5Ever>, config::ConfigError> {
+        let config: Self = source.clone().try_deserialize()?;
+        Ok(config.optimisation.unwrap_or_default())
+    }
 }
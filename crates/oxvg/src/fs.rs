@@ -18,7 +18,7 @@ fn load_file(path: &PathBuf) -> Box<dyn Iterator<Item = (PathBuf, Vec<u8>)>> {
         std::fs::read_dir(path)
             .unwrap()
             .map(|dir| dir.unwrap().path())
-            .filter(|path| path.ends_with(".svg"))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "svg"))
             .map(|path| (path.clone(), std::fs::read(path.clone()).unwrap())),
     )
 }
@@ -7,13 +7,15 @@ use oxvg::{
 
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
-    let config: Config = config::Config::builder()
-        .add_source(File::with_name("oxvgrc").required(false))
-        .build()?
-        .try_deserialize()?;
+    let config = Config::from_source(
+        config::Config::builder()
+            .add_source(File::with_name("oxvgrc").required(false))
+            .build()?,
+    )?;
 
     match args.command {
         Command::Optimise(args) => args.run(config)?,
+        Command::Diff(args) => args.run(config)?,
     }
     Ok(())
 }
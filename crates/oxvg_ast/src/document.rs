@@ -13,6 +13,12 @@ pub trait Document {
     /// [MDN | documentElement](https://developer.mozilla.org/en-US/docs/Web/API/Document/documentElement)
     fn document_element(&self) -> &Self::Root;
 
+    /// Returns a copy of the document which shares no nodes with the original, so that changes
+    /// to the copy can be made, compared, and discarded without re-parsing the original.
+    fn clone_deep(&self) -> Self
+    where
+        Self: Sized;
+
     /// Creates a new attribute node and returns it
     ///
     /// [MDN | createAttribute](https://developer.mozilla.org/en-US/docs/Web/API/Document/createAttribute)
@@ -48,3 +54,43 @@ pub trait Document {
     /// [MDN | createTextNode](https://developer.mozilla.org/en-US/docs/Web/API/Document/createTextNode)
     fn create_text_node(&self, data: <Self::Root as Node>::Atom) -> <Self::Root as Node>::Child;
 }
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn clone_deep() -> anyhow::Result<()> {
+    use crate::{
+        implementations::markup5ever::{Element5Ever, Node5Ever},
+        parse::Node as _,
+    };
+
+    let dom = Node5Ever::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><g id="a"><path d="M0 0h10"/></g></svg>"#,
+    )?;
+    let root = <Element5Ever as Element>::find_element(dom.clone())
+        .ok_or(crate::parse::Error::NoElementInDocument)?;
+    let document_node = root.document().unwrap();
+    let document = document_node.as_document();
+    let original = document.document_element();
+
+    let clone = document.clone_deep();
+    let clone_root =
+        <Element5Ever as Element>::find_element(clone.document_element().as_parent_child())
+            .ok_or(crate::parse::Error::NoElementInDocument)?;
+    let clone_group = clone_root.first_element_child().unwrap();
+    clone_group.set_attribute_local("id".into(), "b".into());
+    clone_group.first_element_child().unwrap().remove();
+
+    // The clone's parents point to nodes within the clone
+    assert!(clone_group
+        .parent_node()
+        .is_some_and(|parent| parent.ptr_eq(&clone_root)));
+
+    let group = root.first_element_child().unwrap();
+    assert_eq!(
+        group.get_attribute_local(&"id".into()).unwrap().as_ref(),
+        "a"
+    );
+    assert!(group.has_child_elements());
+    assert!(!original.ptr_eq(clone.document_element()));
+    Ok(())
+}
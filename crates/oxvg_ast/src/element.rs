@@ -1,5 +1,5 @@
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, HashSet, VecDeque},
     fmt::Debug,
     ops::{Deref, DerefMut},
 };
@@ -208,10 +208,13 @@ pub trait Element: Node + Features + Debug + std::hash::Hash + Eq + PartialEq {
         parent.insert_after(node, &self.as_parent_child());
     }
 
-    /// Inserts a node after the last child of the element.
+    /// Inserts a node after the last child of the element, removing it from its current parent.
     ///
     /// [MDN | append](https://developer.mozilla.org/en-US/docs/Web/API/Element/append)
-    fn append(&self, node: Self::Child);
+    fn append(&self, node: Self::Child) {
+        node.remove();
+        self.clone().append_child(node);
+    }
 
     /// Inserts a node in the children list of the [Element]'s parent, just before this [Element]
     ///
@@ -329,16 +332,13 @@ pub trait Element: Node + Features + Debug + std::hash::Hash + Eq + PartialEq {
         !self.attributes().is_empty()
     }
 
-    /// Inserts the node before the first child of the element.
+    /// Inserts the node before the first child of the element, removing it from its current
+    /// parent.
     ///
     /// [MDN | prepend](https://developer.mozilla.org/en-US/docs/Web/API/Element/prepend)
-    fn prepend(&self, other: Self::ParentChild) {
-        let Some(mut parent) = self.parent_node() else {
-            return;
-        };
-        other.remove();
-        other.set_parent_node(&parent);
-        parent.insert_before(other, &self.as_parent_child());
+    fn prepend(&self, node: Self::Child) {
+        node.remove();
+        self.clone().insert(0, node);
     }
 
     /// Removes the attribute with the specified name from the element.
@@ -408,6 +408,215 @@ pub trait Element: Node + Features + Debug + std::hash::Hash + Eq + PartialEq {
         Iterator::new(self)
     }
 
+    /// Returns a detached duplicate of the element and each of its descendants, like
+    /// [`Node::clone_deep`], with each `id` found in `ids` replaced with its new value.
+    ///
+    /// Any other `id` is copied verbatim, so rename each one which would collide with the
+    /// original when the duplicate is inserted into the same document. References to the
+    /// renamed ids aren't updated.
+    fn clone_deep_with_ids(&self, ids: &HashMap<String, String>) -> Self {
+        let clone = self.clone_deep();
+        for element in std::iter::once(clone.clone()).chain(clone.breadth_first()) {
+            let Some(id) = element.get_attribute_local(&"id".into()) else {
+                continue;
+            };
+            let Some(new_id) = ids.get(id.as_ref()) else {
+                continue;
+            };
+            drop(id);
+            element.set_attribute_local("id".into(), new_id.as_str().into());
+        }
+        clone
+    }
+
+    /// Returns each id the element references in its attributes, in the order they're found.
+    ///
+    /// This includes ids from `href="#id"`, `url(#id)` in presentation attributes and styles, and
+    /// event-based timings such as `begin="id.click"`.
+    fn find_references(&self) -> Vec<String> {
+        use oxvg_collections::{
+            collections::REFERENCES_PROPS,
+            regex::{REFERENCES_BEGIN, REFERENCES_HREF, REFERENCES_URL},
+        };
+
+        let mut ids: Vec<String> = vec![];
+        for attr in self.attributes().into_iter() {
+            let value = attr.value();
+            let matches = match attr.name().local_name().as_ref() {
+                "href" => REFERENCES_HREF.captures_iter(value.as_ref()),
+                "begin" => REFERENCES_BEGIN.captures_iter(value.as_ref()),
+                name if REFERENCES_PROPS.contains(name) => {
+                    REFERENCES_URL.captures_iter(value.as_ref())
+                }
+                _ => continue,
+            };
+            for id in matches.filter_map(|captures| captures.get(1)) {
+                let id = id.as_str();
+                if !ids.iter().any(|existing| existing == id) {
+                    ids.push(id.to_string());
+                }
+            }
+        }
+        ids
+    }
+
+    /// Replaces each reference to an id in `ids` with its new value, for the same kinds of
+    /// references found by [`Element::find_references`].
+    fn rename_references(&self, ids: &HashMap<String, String>) {
+        use oxvg_collections::{
+            collections::REFERENCES_PROPS,
+            regex::{REFERENCES_BEGIN, REFERENCES_HREF, REFERENCES_URL},
+        };
+
+        for mut attr in self.attributes().into_iter_mut() {
+            let regex = match attr.name().local_name().as_ref() {
+                "href" => &*REFERENCES_HREF,
+                "begin" => &*REFERENCES_BEGIN,
+                name if REFERENCES_PROPS.contains(name) => &*REFERENCES_URL,
+                _ => continue,
+            };
+            let value = attr.value().as_ref();
+            let mut renamed = String::with_capacity(value.len());
+            let mut end = 0;
+            for id in regex
+                .captures_iter(value)
+                .filter_map(|captures| captures.get(1))
+            {
+                let Some(new_id) = ids.get(id.as_str()) else {
+                    continue;
+                };
+                renamed.push_str(&value[end..id.start()]);
+                renamed.push_str(new_id);
+                end = id.end();
+            }
+            if end == 0 {
+                continue;
+            }
+            renamed.push_str(&value[end..]);
+            attr.set_value(renamed.into());
+        }
+    }
+
+    /// Renames each id in `ids` declared by the element or any of its descendants, along with any
+    /// references to it within the subtree.
+    ///
+    /// References are renamed as by [`Element::rename_references`], along with id selectors and
+    /// `url(#id)` references within `<style>` elements when the `style` feature is enabled.
+    fn rename_ids(&self, ids: &HashMap<String, String>) {
+        for element in std::iter::once(self.clone()).chain(self.breadth_first()) {
+            if let Some(new_id) = element
+                .get_attribute_local(&"id".into())
+                .and_then(|id| ids.get(id.as_ref()))
+            {
+                element.set_attribute_local("id".into(), new_id.as_str().into());
+            }
+            element.rename_references(ids);
+
+            #[cfg(feature = "style")]
+            if element.prefix().is_none() && element.local_name().as_ref() == "style" {
+                if let Some(css) = element
+                    .text_content()
+                    .and_then(|css| crate::style::rename_id_references(&css, ids))
+                {
+                    element.clone().set_text_content(css.into());
+                }
+            }
+        }
+    }
+
+    /// Returns whether whitespace within the element is preserved, as specified by
+    /// `xml:space="preserve"` on the element or its closest ancestor with `xml:space`.
+    fn is_whitespace_preserved(&self) -> bool {
+        let name = <Self::Attr as Attr>::Name::new(Some("xml".into()), "space".into());
+        let mut element = Some(self.clone());
+        while let Some(current) = element.filter(|element| element.node_type() == Type::Element) {
+            if let Some(value) = current.get_attribute(&name) {
+                return value.as_ref() == "preserve";
+            }
+            element = Element::parent_element(&current);
+        }
+        false
+    }
+
+    /// Returns whether the element has no child elements, and no text other than whitespace
+    /// which isn't preserved by `xml:space`.
+    ///
+    /// Unlike the `:empty` selector, text made of whitespace alone is disregarded.
+    fn is_blank(&self) -> bool {
+        let is_whitespace_preserved = self.is_whitespace_preserved();
+        self.all_children(|child| match child.node_type() {
+            Type::Element => false,
+            Type::Text => child.node_value().is_none_or(|value| {
+                if is_whitespace_preserved {
+                    value.as_ref().is_empty()
+                } else {
+                    value.as_ref().trim().is_empty()
+                }
+            }),
+            _ => true,
+        })
+    }
+
+    /// Returns the element and each of its descendants with an `id`, keyed by their `id`.
+    ///
+    /// When an `id` is duplicated, the first element in document order is kept, as it's the one
+    /// references resolve to.
+    fn collect_ids(&self) -> HashMap<Self::Atom, Self> {
+        fn collect<E: Element>(element: &E, ids: &mut HashMap<E::Atom, E>) {
+            if let Some(id) = element.get_attribute_local(&"id".into()) {
+                ids.entry(id.clone()).or_insert_with(|| element.clone());
+            }
+            element.for_each_element_child(|child| collect(&child, ids));
+        }
+
+        let mut ids = HashMap::new();
+        collect(self, &mut ids);
+        ids
+    }
+
+    /// Returns each id referenced by the element or any of its descendants.
+    ///
+    /// See [`Element::find_references`] for the kinds of references found.
+    fn collect_id_references(&self) -> HashSet<String> {
+        std::iter::once(self.clone())
+            .chain(self.breadth_first())
+            .flat_map(|element| element.find_references())
+            .collect()
+    }
+
+    #[cfg(feature = "style")]
+    /// Returns the matrix `[a, b, c, d, e, f]` of the element's `transform` attribute, composed
+    /// with the `transform` attributes of each of its ancestors.
+    ///
+    /// Transforms that fail to parse are ignored, as they would be when rendered.
+    fn accumulated_transform(&self) -> [f64; 6] {
+        use lightningcss::traits::Parse as _;
+
+        let name = "transform".into();
+        let mut matrix = IDENTITY_MATRIX;
+        let mut element = Some(self.clone());
+        while let Some(current) = element.filter(|e| e.node_type() == Type::Element) {
+            let transform = current.get_attribute_local(&name).and_then(|value| {
+                crate::style::SVGTransformList::parse_string(value.as_ref())
+                    .ok()?
+                    .to_matrix_2d()
+            });
+            if let Some(transform) = transform {
+                let transform = [
+                    f64::from(transform.a),
+                    f64::from(transform.b),
+                    f64::from(transform.c),
+                    f64::from(transform.d),
+                    f64::from(transform.e),
+                    f64::from(transform.f),
+                ];
+                matrix = multiply_matrices(&transform, &matrix);
+            }
+            element = Element::parent_element(&current);
+        }
+        matrix
+    }
+
     #[cfg(feature = "selectors")]
     /// # Errors
     /// If the selector is invalid
@@ -421,6 +630,26 @@ pub trait Element: Node + Features + Debug + std::hash::Hash + Eq + PartialEq {
         crate::selectors::Select::new(self, selector)
     }
 
+    #[cfg(feature = "selectors")]
+    /// Returns whether the element itself matches the selector, where `:scope` is the element.
+    ///
+    /// [MDN | matches](https://developer.mozilla.org/en-US/docs/Web/API/Element/matches)
+    ///
+    /// # Errors
+    /// If the selector is invalid
+    fn matches<'a>(
+        &self,
+        selector: &'a str,
+    ) -> Result<bool, cssparser::ParseError<'a, selectors::parser::SelectorParseErrorKind<'a>>>
+    {
+        let selector = crate::selectors::Selector::new(selector)?;
+        Ok(selector.matches_with_scope_and_cache(
+            self,
+            Some(self.clone()),
+            &mut selectors::context::SelectorCaches::default(),
+        ))
+    }
+
     #[cfg(feature = "selectors")]
     fn select_with_selector(
         &self,
@@ -430,6 +659,22 @@ pub trait Element: Node + Features + Debug + std::hash::Hash + Eq + PartialEq {
     }
 }
 
+#[cfg(feature = "style")]
+const IDENTITY_MATRIX: [f64; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+#[cfg(feature = "style")]
+/// Multiplies the 2d matrices `a × b`, such that `b` is applied before `a`
+fn multiply_matrices(a: &[f64; 6], b: &[f64; 6]) -> [f64; 6] {
+    [
+        a[0] * b[0] + a[2] * b[1],
+        a[1] * b[0] + a[3] * b[1],
+        a[0] * b[2] + a[2] * b[3],
+        a[1] * b[2] + a[3] * b[3],
+        a[0] * b[4] + a[2] * b[5] + a[4],
+        a[1] * b[4] + a[3] * b[5] + a[5],
+    ]
+}
+
 #[derive(Debug)]
 pub struct Iterator<E: crate::element::Element> {
     queue: VecDeque<E>,
@@ -458,3 +703,358 @@ impl<E: crate::element::Element> std::iter::Iterator for Iterator<E> {
         Some(current)
     }
 }
+
+#[cfg(all(test, feature = "style", feature = "parse", feature = "markup5ever"))]
+#[test]
+fn accumulated_transform() -> anyhow::Result<()> {
+    use crate::{implementations::markup5ever::Node5Ever, parse::Node as _};
+
+    let dom = Node5Ever::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" transform="translate(10 20)">
+    <g transform="scale(2)">
+        <g transform="rotate(90)">
+            <path id="a" transform="translate(1 0)"/>
+        </g>
+        <path id="b" transform="nonsense"/>
+    </g>
+</svg>"#,
+    )?;
+    let root =
+        <crate::implementations::markup5ever::Element5Ever as Element>::find_element(dom.clone())
+            .ok_or(crate::parse::Error::NoElementInDocument)?;
+    let find = |id: &str| {
+        root.breadth_first()
+            .find(|element| {
+                element
+                    .get_attribute_local(&"id".into())
+                    .is_some_and(|value| value.as_ref() == id)
+            })
+            .unwrap()
+    };
+
+    let round = |matrix: [f64; 6]| matrix.map(|n| (n * 1e4).round() / 1e4 + 0.0);
+    assert_eq!(
+        root.accumulated_transform(),
+        [1.0, 0.0, 0.0, 1.0, 10.0, 20.0]
+    );
+    // translate(10 20) scale(2) rotate(90) translate(1 0)
+    assert_eq!(
+        round(find("a").accumulated_transform()),
+        [0.0, 2.0, -2.0, 0.0, 10.0, 22.0]
+    );
+    // Invalid transforms are ignored
+    assert_eq!(
+        find("b").accumulated_transform(),
+        [2.0, 0.0, 0.0, 2.0, 10.0, 20.0]
+    );
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn find_references() -> anyhow::Result<()> {
+    use crate::{implementations::markup5ever::Node5Ever, parse::Node as _};
+
+    let dom = Node5Ever::parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+    <use fill="url(#g)" href="#t" style="stroke:url('#s')" begin="a.click;g.end" xlink:href="#t" x="url(#x)"/>
+</svg>"##,
+    )?;
+    let root =
+        <crate::implementations::markup5ever::Element5Ever as Element>::find_element(dom.clone())
+            .ok_or(crate::parse::Error::NoElementInDocument)?;
+    let element = root.first_element_child().unwrap();
+
+    assert_eq!(element.find_references(), vec!["g", "t", "s", "a"]);
+    assert!(root.find_references().is_empty());
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn collect_ids() -> anyhow::Result<()> {
+    use crate::{implementations::markup5ever::Node5Ever, parse::Node as _};
+
+    let dom = Node5Ever::parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" id="root">
+    <defs>
+        <linearGradient id="a"/>
+        <path id="b" d="M0 0h10"/>
+    </defs>
+    <rect fill="url(#a)"/>
+    <g>
+        <use href="#b"/>
+        <use href="#missing"/>
+        <rect id="duplicate" x="1"/>
+    </g>
+    <rect id="duplicate" x="2"/>
+</svg>"##,
+    )?;
+    let root =
+        <crate::implementations::markup5ever::Element5Ever as Element>::find_element(dom.clone())
+            .ok_or(crate::parse::Error::NoElementInDocument)?;
+
+    let ids = root.collect_ids();
+    let mut id_names: Vec<_> = ids.keys().map(AsRef::as_ref).collect();
+    id_names.sort_unstable();
+    assert_eq!(id_names, vec!["a", "b", "duplicate", "root"]);
+    let get = |id: &str| ids.iter().find(|(key, _)| key.as_ref() == id).unwrap().1;
+    assert_eq!(get("b").local_name().as_ref(), "path");
+    assert_eq!(get("root"), &root);
+    // The first duplicate in document order is kept, even when it's nested deeper
+    assert_eq!(
+        get("duplicate")
+            .get_attribute_local(&"x".into())
+            .map(|x| x.to_string())
+            .as_deref(),
+        Some("1")
+    );
+
+    assert_eq!(
+        root.collect_id_references(),
+        HashSet::from(["a".to_string(), "b".to_string(), "missing".to_string()])
+    );
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn clone_deep_with_ids() -> anyhow::Result<()> {
+    use crate::{implementations::markup5ever::Node5Ever, parse::Node as _};
+
+    let dom = Node5Ever::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <g id="a"><g id="b"><path id="c" d="M0 0h10"/></g><rect/></g>
+</svg>"#,
+    )?;
+    let root =
+        <crate::implementations::markup5ever::Element5Ever as Element>::find_element(dom.clone())
+            .ok_or(crate::parse::Error::NoElementInDocument)?;
+    let group = root.first_element_child().unwrap();
+
+    let ids = HashMap::from([
+        ("a".to_string(), "a-1".to_string()),
+        ("c".to_string(), "c-1".to_string()),
+    ]);
+    let clone = group.clone_deep_with_ids(&ids);
+    assert!(clone.parent_node().is_none());
+    let ids_of = |element: &crate::implementations::markup5ever::Element5Ever| {
+        std::iter::once(element.clone())
+            .chain(element.breadth_first())
+            .map(|element| {
+                element
+                    .get_attribute_local(&"id".into())
+                    .map(|id| id.to_string())
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        ids_of(&clone),
+        vec![
+            Some("a-1".to_string()),
+            Some("b".to_string()),
+            None,
+            Some("c-1".to_string())
+        ]
+    );
+
+    // The original is unaffected by changes to the clone
+    clone.first_element_child().unwrap().remove();
+    assert_eq!(clone.child_element_count(), 1);
+    assert_eq!(group.child_element_count(), 2);
+    assert_eq!(
+        ids_of(&group),
+        vec![
+            Some("a".to_string()),
+            Some("b".to_string()),
+            None,
+            Some("c".to_string())
+        ]
+    );
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn rename_ids() -> anyhow::Result<()> {
+    use crate::{
+        implementations::markup5ever::{Element5Ever, Node5Ever},
+        parse::Node as _,
+    };
+
+    let dom = Node5Ever::parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg">
+    <linearGradient id="a"/>
+    <rect id="b" fill="url(#a)" stroke="url('#a')"/>
+    <use href="#a"/>
+    <use href="#b"/>
+</svg>"##,
+    )?;
+    let root = <Element5Ever as Element>::find_element(dom.clone())
+        .ok_or(crate::parse::Error::NoElementInDocument)?;
+
+    root.rename_ids(&HashMap::from([("a".to_string(), "a1".to_string())]));
+    let get = |element: &Element5Ever, name: &str| {
+        element
+            .get_attribute_local(&name.into())
+            .map(|value| value.to_string())
+    };
+    let children: Vec<_> = root.children();
+    assert_eq!(get(&children[0], "id").as_deref(), Some("a1"));
+    assert_eq!(get(&children[1], "id").as_deref(), Some("b"));
+    assert_eq!(get(&children[1], "fill").as_deref(), Some("url(#a1)"));
+    assert_eq!(get(&children[1], "stroke").as_deref(), Some("url('#a1')"));
+    assert_eq!(get(&children[2], "href").as_deref(), Some("#a1"));
+    assert_eq!(get(&children[3], "href").as_deref(), Some("#b"));
+    Ok(())
+}
+
+#[cfg(all(test, feature = "style", feature = "parse", feature = "markup5ever"))]
+#[test]
+fn rename_ids_in_styles() -> anyhow::Result<()> {
+    use crate::{
+        implementations::markup5ever::{Element5Ever, Node5Ever},
+        parse::Node as _,
+    };
+
+    let dom = Node5Ever::parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg">
+    <style>#a, :not(#a) { fill: #a; stroke: url(#a) } @media screen { #a { fill: url("#a") } }</style>
+    <linearGradient id="a"/>
+    <rect style="fill:url(#a)"/>
+</svg>"##,
+    )?;
+    let root = <Element5Ever as Element>::find_element(dom.clone())
+        .ok_or(crate::parse::Error::NoElementInDocument)?;
+
+    root.rename_ids(&HashMap::from([("a".to_string(), "a1".to_string())]));
+    let children: Vec<_> = root.children();
+    assert_eq!(
+        children[0].text_content().as_deref(),
+        Some(r#"#a1, :not(#a1) { fill: #a; stroke: url(#a1) } @media screen { #a1 { fill: url("#a1") } }"#)
+    );
+    assert_eq!(
+        children[2]
+            .get_attribute_local(&"style".into())
+            .map(|value| value.to_string())
+            .as_deref(),
+        Some("fill:url(#a1)")
+    );
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn insertion() -> anyhow::Result<()> {
+    use crate::{
+        implementations::markup5ever::{Element5Ever, Node5Ever},
+        parse::Node as _,
+    };
+
+    let dom = Node5Ever::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><g id="a"><path id="b"/><path id="c"/></g><g id="d"/></svg>"#,
+    )?;
+    let root = <Element5Ever as Element>::find_element(dom.clone())
+        .ok_or(crate::parse::Error::NoElementInDocument)?;
+    let find = |id: &str| {
+        root.breadth_first()
+            .find(|element| {
+                element
+                    .get_attribute_local(&"id".into())
+                    .is_some_and(|value| value.as_ref() == id)
+            })
+            .unwrap()
+    };
+    let ids = |element: &Element5Ever| {
+        element
+            .children()
+            .iter()
+            .map(|child| child.get_attribute_local(&"id".into()).unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    let is_child_of = |child: &Element5Ever, parent: &Element5Ever| {
+        child.parent_element().is_some_and(|p| &p == parent)
+    };
+    let (a, b, c, d) = (find("a"), find("b"), find("c"), find("d"));
+
+    // Moves `c` before `b` within the same parent
+    b.before(c.as_parent_child());
+    assert_eq!(ids(&a), vec!["c", "b"]);
+    assert!(is_child_of(&c, &a));
+
+    // Moves `c` after `b` within the same parent
+    b.after(c.as_parent_child());
+    assert_eq!(ids(&a), vec!["b", "c"]);
+    assert!(is_child_of(&c, &a));
+
+    // Moves `b` from `a` to the end of `d`
+    d.append(b.as_child());
+    assert_eq!(ids(&a), vec!["c"]);
+    assert_eq!(ids(&d), vec!["b"]);
+    assert!(is_child_of(&b, &d));
+
+    // Moves `c` from `a` to the start of `d`
+    d.prepend(c.as_child());
+    assert!(a.children().is_empty());
+    assert_eq!(ids(&d), vec!["c", "b"]);
+    assert!(is_child_of(&c, &d));
+
+    // Inserts a new element before the first child
+    let e = root
+        .as_document()
+        .create_element(<Element5Ever as Element>::Name::new_ns(
+            None,
+            "path".into(),
+            root.qual_name().ns().clone(),
+        ));
+    e.set_attribute_local("id".into(), "e".into());
+    c.before(e.as_parent_child());
+    assert_eq!(ids(&d), vec!["e", "c", "b"]);
+    assert!(is_child_of(&e, &d));
+
+    // Replaces `e`, leaving it detached
+    let f = e.clone_node();
+    f.set_attribute_local("id".into(), "f".into());
+    e.replace_with(f.as_parent_child());
+    assert_eq!(ids(&d), vec!["f", "c", "b"]);
+    assert!(is_child_of(&f, &d));
+    assert!(e.parent_node().is_none());
+    Ok(())
+}
+
+#[cfg(all(
+    test,
+    feature = "selectors",
+    feature = "parse",
+    feature = "markup5ever"
+))]
+#[test]
+fn matches() -> anyhow::Result<()> {
+    use crate::{implementations::markup5ever::Node5Ever, parse::Node as _};
+
+    let dom = Node5Ever::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <g class="foo">
+        <rect class="foo bar" fill="red"/>
+        <rect class="foo"/>
+    </g>
+</svg>"#,
+    )?;
+    let root =
+        <crate::implementations::markup5ever::Element5Ever as Element>::find_element(dom.clone())
+            .ok_or(crate::parse::Error::NoElementInDocument)?;
+    let g = root.first_element_child().unwrap();
+    let rect = g.first_element_child().unwrap();
+    let other_rect = rect.next_element_sibling().unwrap();
+
+    assert!(rect.matches("rect.foo[fill]").unwrap());
+    assert!(!other_rect.matches("rect.foo[fill]").unwrap());
+    assert!(!g.matches("rect.foo[fill]").unwrap());
+    assert!(rect.matches("g > rect.bar, circle").unwrap());
+    // Only the element itself is matched, not its descendants
+    assert!(!g.matches("rect").unwrap());
+    assert!(g.matches(":scope").unwrap());
+    assert!(!rect.matches(":scope > rect").unwrap());
+    assert!(rect.matches("[").is_err());
+    Ok(())
+}
@@ -74,6 +74,10 @@ impl Name for QualName {
         }
     }
 
+    fn new_ns(prefix: Option<Self::Prefix>, local: Self::LocalName, ns: Self::Namespace) -> Self {
+        QualName { prefix, local, ns }
+    }
+
     fn local_name(&self) -> &Self::LocalName {
         &self.local
     }
@@ -800,7 +804,9 @@ impl Node for Node5Ever {
         if children.len() <= index {
             None
         } else {
-            Some(Node5Ever(children.remove(index)))
+            let child = children.remove(index);
+            child.parent.set(None);
+            Some(Node5Ever(child))
         }
     }
 
@@ -813,16 +819,28 @@ impl Node for Node5Ever {
         }))
     }
 
+    fn clone_deep(&self) -> Self {
+        let mut clone = Self(Rc::new(rcdom::Node {
+            parent: Cell::new(None),
+            data: self.clone_node_data(),
+            children: RefCell::new(vec![]),
+        }));
+        for child in self.0.children.borrow().iter() {
+            clone.append_child(Self(child.clone()).clone_deep());
+        }
+        clone
+    }
+
     fn replace_child(
         &mut self,
         new_child: Self::Child,
         old_child: &Self::Child,
     ) -> Option<Self::Child> {
         let index = self.child_index(old_child)?;
-        Some(Node5Ever(std::mem::replace(
-            &mut self.0.children.borrow_mut()[index],
-            new_child.0,
-        )))
+        new_child.set_parent_node(self);
+        let old_child = std::mem::replace(&mut self.0.children.borrow_mut()[index], new_child.0);
+        old_child.parent.set(None);
+        Some(Node5Ever(old_child))
     }
 
     fn to_owned(&self) -> Self {
@@ -895,23 +913,383 @@ impl parse::Node for Node5Ever {
 
 #[cfg(feature = "serialize")]
 impl serialize::Node for Node5Ever {
-    fn serialize(&self) -> anyhow::Result<String> {
+    fn serialize_with_options(&self, options: serialize::Options) -> anyhow::Result<String> {
         let mut sink: std::io::BufWriter<_> = std::io::BufWriter::new(Vec::new());
-        self.serialize_into(&mut sink)?;
+        self.serialize_into_with_options(&mut sink, options)?;
 
         let sink: Vec<_> = sink.into_inner()?;
         Ok(String::from_utf8_lossy(&sink).to_string())
     }
 
-    fn serialize_into<Wr: std::io::Write>(&self, sink: Wr) -> anyhow::Result<()> {
+    fn serialize_into_with_options<Wr: std::io::Write>(
+        &self,
+        sink: Wr,
+        options: serialize::Options,
+    ) -> anyhow::Result<()> {
+        use markup5ever::serialize::{Serialize, Serializer as _, TraversalScope};
         use rcdom::SerializableHandle;
-        use xml5ever::serialize::{serialize, SerializeOpts};
 
-        Ok(serialize(
-            sink,
-            &std::convert::Into::<SerializableHandle>::into(self.0.clone()),
-            SerializeOpts::default(),
-        )?)
+        let handle = std::convert::Into::<SerializableHandle>::into(self.0.clone());
+        let is_declaration_missing = options.xml_declaration == serialize::XmlDeclaration::Add
+            && matches!(self.0.data, NodeData::Document)
+            && !self.0.children.borrow().iter().any(|child| {
+                matches!(&child.data, NodeData::ProcessingInstruction { target, .. } if target.as_ref() == "xml")
+            });
+        let mut writer = XmlWriter::new(sink, options, self);
+        if is_declaration_missing {
+            writer.write_processing_instruction("xml", serialize::XmlDeclaration::DEFAULT_DATA)?;
+        }
+        handle.serialize(&mut writer, TraversalScope::ChildrenOnly(None))?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serialize")]
+/// A namespace declared on an element, with `None` being the default namespace
+type XmlWriterNamespace = (Option<Prefix>, Namespace);
+
+#[cfg(feature = "serialize")]
+/// An XML serializer which writes the formatting and namespace declarations specified by
+/// [`serialize::Options`].
+struct XmlWriter<Wr: std::io::Write> {
+    writer: Wr,
+    options: serialize::Options,
+    /// The elements currently being written
+    stack: Vec<XmlWriterFrame>,
+    /// Whether anything has been written before the current node
+    is_started: bool,
+    /// The index of the next element to be written, in document order
+    element_index: usize,
+    /// The prefixed namespaces to declare on each element, in document order
+    planned_namespaces: Vec<Vec<XmlWriterNamespace>>,
+}
+
+#[cfg(feature = "serialize")]
+#[derive(Default)]
+struct XmlWriterFrame {
+    /// Whether whitespace is significant within the element, so it mustn't be indented
+    is_inline: bool,
+    /// Whether the element has any children that have been indented
+    has_indented_children: bool,
+    /// The namespaces declared by the element
+    namespaces: Vec<XmlWriterNamespace>,
+}
+
+#[cfg(feature = "serialize")]
+impl<Wr: std::io::Write> XmlWriter<Wr> {
+    fn new(sink: Wr, options: serialize::Options, node: &Node5Ever) -> Self {
+        let planned_namespaces = Self::plan_namespaces(node, options.namespace_declarations);
+        Self {
+            writer: sink,
+            options,
+            stack: vec![],
+            is_started: false,
+            element_index: 0,
+            planned_namespaces,
+        }
+    }
+
+    /// Finds which element each prefixed namespace should be declared on, based on the elements
+    /// and attributes that use it.
+    ///
+    /// Prefixes bound to more than one namespace aren't planned, and are instead declared on
+    /// each element that uses them.
+    fn plan_namespaces(
+        node: &Node5Ever,
+        mode: serialize::NamespaceDeclarations,
+    ) -> Vec<Vec<XmlWriterNamespace>> {
+        fn visit(
+            node: &rcdom::Handle,
+            path: &mut Vec<usize>,
+            element_count: &mut usize,
+            uses: &mut Vec<(XmlWriterNamespace, Option<Vec<usize>>)>,
+        ) {
+            if let NodeData::Element { name, attrs, .. } = &node.data {
+                path.push(*element_count);
+                *element_count += 1;
+                let attrs = attrs.borrow();
+                let names = std::iter::once(name).chain(attrs.iter().map(|attr| &attr.name));
+                for name in names {
+                    let Some(prefix) = &name.prefix else {
+                        continue;
+                    };
+                    if prefix.as_ref() == "xml" || prefix.as_ref() == "xmlns" {
+                        continue;
+                    }
+                    match uses
+                        .iter_mut()
+                        .find(|((p, _), _)| p.as_ref() == Some(prefix))
+                    {
+                        Some(((_, ns), common_path)) if *ns != name.ns => *common_path = None,
+                        Some((_, Some(common_path))) => {
+                            let common_len = common_path
+                                .iter()
+                                .zip(path.iter())
+                                .take_while(|(a, b)| a == b)
+                                .count();
+                            common_path.truncate(common_len);
+                        }
+                        Some((_, None)) => {}
+                        None => {
+                            uses.push((
+                                (Some(prefix.clone()), name.ns.clone()),
+                                Some(path.clone()),
+                            ));
+                        }
+                    }
+                }
+            }
+            for child in node.children.borrow().iter() {
+                visit(child, path, element_count, uses);
+            }
+            if matches!(node.data, NodeData::Element { .. }) {
+                path.pop();
+            }
+        }
+
+        if mode == serialize::NamespaceDeclarations::ElementNames {
+            return vec![];
+        }
+        let mut element_count = 0;
+        let mut uses = vec![];
+        for child in node.0.children.borrow().iter() {
+            visit(child, &mut vec![], &mut element_count, &mut uses);
+        }
+
+        let mut planned = vec![vec![]; element_count];
+        for (namespace, common_path) in uses {
+            let Some(common_path) = common_path else {
+                continue;
+            };
+            let index = match mode {
+                serialize::NamespaceDeclarations::ElementNames => None,
+                serialize::NamespaceDeclarations::Root => common_path.first(),
+                serialize::NamespaceDeclarations::Lazy => common_path.last(),
+            };
+            if let Some(index) = index {
+                planned[*index].push(namespace);
+            }
+        }
+        planned
+    }
+
+    fn is_inline(&self) -> bool {
+        self.options.indent == serialize::Indent::None
+            || self.stack.last().is_some_and(|frame| frame.is_inline)
+    }
+
+    /// Returns the namespace bound to the prefix by the current element or its ancestors
+    fn namespace_in_scope(&self, prefix: Option<&Prefix>) -> Option<&Namespace> {
+        self.stack.iter().rev().find_map(|frame| {
+            frame
+                .namespaces
+                .iter()
+                .find(|(p, _)| p.as_ref() == prefix)
+                .map(|(_, ns)| ns)
+        })
+    }
+
+    /// Writes a newline and indentation before a node, when not within an inline context
+    fn indent(&mut self) -> std::io::Result<()> {
+        if self.is_inline() {
+            return Ok(());
+        }
+        if let Some(frame) = self.stack.last_mut() {
+            frame.has_indented_children = true;
+        }
+        if !std::mem::replace(&mut self.is_started, true) {
+            return Ok(());
+        }
+        self.write_indent(self.stack.len())
+    }
+
+    fn write_indent(&mut self, depth: usize) -> std::io::Result<()> {
+        self.writer
+            .write_all(self.options.newline.as_str().as_bytes())?;
+        match self.options.indent {
+            serialize::Indent::None => {}
+            serialize::Indent::Spaces(width) => {
+                for _ in 0..depth * usize::from(width) {
+                    self.writer.write_all(b" ")?;
+                }
+            }
+            serialize::Indent::Tabs => {
+                for _ in 0..depth {
+                    self.writer.write_all(b"\t")?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_qual_name(&mut self, name: &QualName) -> std::io::Result<()> {
+        if let Some(prefix) = &name.prefix {
+            self.writer.write_all(prefix.as_bytes())?;
+            self.writer.write_all(b":")?;
+        }
+        self.writer.write_all(name.local.as_bytes())
+    }
+
+    fn write_attr_value(&mut self, value: &str) -> std::io::Result<()> {
+        let quote = self.options.quote.as_char();
+        write!(self.writer, "={quote}")?;
+        self.write_escaped(value, Some(self.options.quote))?;
+        write!(self.writer, "{quote}")
+    }
+
+    fn write_escaped(
+        &mut self,
+        text: &str,
+        quote: Option<serialize::QuoteStyle>,
+    ) -> std::io::Result<()> {
+        let entities = self.options.entities;
+        let mut start = 0;
+        for (i, c) in text.char_indices() {
+            if let Some(entity) = entities.escape(c, quote, &text[..i]) {
+                self.writer.write_all(&text.as_bytes()[start..i])?;
+                self.writer.write_all(entity.as_bytes())?;
+                start = i + c.len_utf8();
+            }
+        }
+        self.writer.write_all(&text.as_bytes()[start..])
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<Wr: std::io::Write> markup5ever::serialize::Serializer for XmlWriter<Wr> {
+    fn start_elem<'a, AttrIter>(&mut self, name: QualName, attrs: AttrIter) -> std::io::Result<()>
+    where
+        AttrIter: Iterator<Item = markup5ever::serialize::AttrRef<'a>>,
+    {
+        self.indent()?;
+        let is_declaring_usage =
+            self.options.namespace_declarations != serialize::NamespaceDeclarations::ElementNames;
+        let attrs: Vec<_> = attrs
+            .filter(|(name, _)| {
+                // Namespaces are declared based on their usage instead
+                !is_declaring_usage
+                    || (name.prefix.as_ref().is_none_or(|p| p.as_ref() != "xmlns")
+                        && !(name.prefix.is_none() && name.local.as_ref() == "xmlns"))
+            })
+            .collect();
+        let is_inline = self.is_inline()
+            || matches!(
+                name.local.as_ref(),
+                "text" | "tspan" | "textPath" | "title" | "desc" | "pre"
+            )
+            || attrs.iter().any(|(name, value)| {
+                name.local.as_ref() == "space" && name.prefix.is_some() && *value == "preserve"
+            });
+
+        let mut namespaces = self
+            .planned_namespaces
+            .get(self.element_index)
+            .cloned()
+            .unwrap_or_default();
+        self.element_index += 1;
+        let default_namespace = self.namespace_in_scope(None).cloned().unwrap_or_default();
+        if name.prefix.is_none()
+            && name.ns != default_namespace
+            && (is_declaring_usage || !name.ns.is_empty())
+        {
+            namespaces.insert(0, (None, name.ns.clone()));
+        }
+        let names = std::iter::once(&name).chain(
+            attrs
+                .iter()
+                .filter(|_| is_declaring_usage)
+                .map(|(name, _)| *name),
+        );
+        for name in names {
+            let Some(prefix) = &name.prefix else {
+                continue;
+            };
+            if prefix.as_ref() == "xml"
+                || namespaces.iter().any(|(p, _)| p.as_ref() == Some(prefix))
+            {
+                continue;
+            }
+            if self.namespace_in_scope(Some(prefix)) != Some(&name.ns) {
+                namespaces.push((Some(prefix.clone()), name.ns.clone()));
+            }
+        }
+
+        self.writer.write_all(b"<")?;
+        self.write_qual_name(&name)?;
+        for (prefix, ns) in &namespaces {
+            self.writer.write_all(b" xmlns")?;
+            if let Some(prefix) = prefix {
+                self.writer.write_all(b":")?;
+                self.writer.write_all(prefix.as_bytes())?;
+            }
+            self.write_attr_value(ns)?;
+        }
+        for (name, value) in attrs {
+            self.writer.write_all(b" ")?;
+            self.write_qual_name(name)?;
+            self.write_attr_value(value)?;
+        }
+        self.writer.write_all(b">")?;
+
+        self.stack.push(XmlWriterFrame {
+            is_inline,
+            has_indented_children: false,
+            namespaces,
+        });
+        Ok(())
+    }
+
+    fn end_elem(&mut self, name: QualName) -> std::io::Result<()> {
+        let frame = self.stack.pop().unwrap_or_default();
+        if frame.has_indented_children {
+            self.write_indent(self.stack.len())?;
+        }
+        self.writer.write_all(b"</")?;
+        self.write_qual_name(&name)?;
+        self.writer.write_all(b">")
+    }
+
+    fn write_text(&mut self, text: &str) -> std::io::Result<()> {
+        if !self.is_inline() {
+            if text.trim().is_empty() {
+                return Ok(());
+            }
+            self.indent()?;
+        }
+        self.is_started = true;
+        self.write_escaped(text, None)
+    }
+
+    fn write_comment(&mut self, text: &str) -> std::io::Result<()> {
+        self.indent()?;
+        self.is_started = true;
+        self.writer.write_all(b"<!--")?;
+        self.writer.write_all(text.as_bytes())?;
+        self.writer.write_all(b"-->")
+    }
+
+    fn write_doctype(&mut self, name: &str) -> std::io::Result<()> {
+        self.indent()?;
+        self.is_started = true;
+        self.writer.write_all(b"<!DOCTYPE ")?;
+        self.writer.write_all(name.as_bytes())?;
+        self.writer.write_all(b">")
+    }
+
+    fn write_processing_instruction(&mut self, target: &str, data: &str) -> std::io::Result<()> {
+        if target == "xml"
+            && self.stack.is_empty()
+            && self.options.xml_declaration == serialize::XmlDeclaration::Remove
+        {
+            return Ok(());
+        }
+        self.indent()?;
+        self.is_started = true;
+        self.writer.write_all(b"<?")?;
+        self.writer.write_all(target.as_bytes())?;
+        self.writer.write_all(b" ")?;
+        self.writer.write_all(data.as_bytes())?;
+        self.writer.write_all(b"?>")
     }
 }
 
@@ -974,10 +1352,6 @@ impl Element for Element5Ever {
         self.replace_with(clone);
     }
 
-    fn append(&self, node: Self::Child) {
-        self.node.0.children.borrow_mut().push(node.0);
-    }
-
     fn attributes(&self) -> Self::Attributes<'_> {
         Attributes5Ever(self.data().attrs)
     }
@@ -1311,6 +1685,10 @@ impl Node for Element5Ever {
         Self::new(self.node.clone_node()).unwrap()
     }
 
+    fn clone_deep(&self) -> Self {
+        Self::new(self.node.clone_deep()).unwrap()
+    }
+
     fn replace_child(
         &mut self,
         new_child: Self::Child,
@@ -1423,12 +1801,16 @@ impl node::Ref for Node5EverRef {
 
 #[cfg(feature = "serialize")]
 impl serialize::Node for Element5Ever {
-    fn serialize(&self) -> anyhow::Result<String> {
-        self.node.serialize()
+    fn serialize_with_options(&self, options: serialize::Options) -> anyhow::Result<String> {
+        self.node.serialize_with_options(options)
     }
 
-    fn serialize_into<Wr: std::io::Write>(&self, sink: Wr) -> anyhow::Result<()> {
-        self.node.serialize_into(sink)
+    fn serialize_into_with_options<Wr: std::io::Write>(
+        &self,
+        sink: Wr,
+        options: serialize::Options,
+    ) -> anyhow::Result<()> {
+        self.node.serialize_into_with_options(sink, options)
     }
 }
 
@@ -1443,7 +1825,8 @@ impl selectors::Element for Element5Ever {
     >;
 
     fn opaque(&self) -> selectors::OpaqueElement {
-        selectors::OpaqueElement::new(self)
+        // Use the shared node, so that clones of an element are the same opaque element
+        selectors::OpaqueElement::new(&*self.node.0)
     }
 
     fn parent_element(&self) -> Option<Self> {
@@ -1682,6 +2065,10 @@ impl Document for Document5Ever {
         &self.0
     }
 
+    fn clone_deep(&self) -> Self {
+        Self(self.0.clone_deep())
+    }
+
     fn create_attribute<'a>(
         &self,
         name: <<<Self::Root as Element>::Attributes<'a> as Attributes<'a>>::Attribute as Attr>::Name,
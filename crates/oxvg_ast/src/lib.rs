@@ -13,6 +13,7 @@ pub mod element;
 pub mod implementations;
 pub mod name;
 pub mod node;
+pub mod sprite;
 
 #[cfg(feature = "visitor")]
 pub mod visitor;
@@ -11,6 +11,10 @@ pub trait Name:
 
     fn new(prefix: Option<Self::Prefix>, local: Self::LocalName) -> Self;
 
+    /// Creates a qualified name within the given namespace, such as the namespace of an
+    /// element it will be inserted into.
+    fn new_ns(prefix: Option<Self::Prefix>, local: Self::LocalName, ns: Self::Namespace) -> Self;
+
     /// Returns the local part of the qualified name.
     fn local_name(&self) -> &Self::LocalName;
 
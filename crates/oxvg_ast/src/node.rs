@@ -226,6 +226,11 @@ pub trait Node: Clone + Debug + 'static + Features {
     /// [MDN | cloneNode](https://developer.mozilla.org/en-US/docs/Web/API/Node/cloneNode)
     fn clone_node(&self) -> Self;
 
+    /// Returns a duplicate of the node and each of its descendants. Unlike [`Node::clone_node`],
+    /// the duplicate shares no nodes with the original, so either can be mutated without
+    /// affecting the other.
+    fn clone_deep(&self) -> Self;
+
     /// Returns whether some node is a descendant if the current node.
     ///
     /// [MDN | contains](https://developer.mozilla.org/en-US/docs/Web/API/Node/contains)
@@ -242,22 +247,26 @@ pub trait Node: Clone + Debug + 'static + Features {
     /// parent.
     fn insert(&mut self, index: usize, new_node: Self::Child);
 
-    /// Inserts a node before the reference node as a child of the current node.
+    /// Inserts a node before the reference node as a child of the current node, or as the last
+    /// child if the reference node isn't a child.
     ///
     /// [MDN | insertBefore](https://developer.mozilla.org/en-US/docs/Web/API/Node/insertBefore)
     fn insert_before(&mut self, new_node: Self::Child, reference_node: &Self::Child) {
-        let len = self.child_nodes().len();
-        let reference_index = self.child_index(reference_node).unwrap_or(len);
-        self.insert(reference_index - 1, new_node);
+        let reference_index = self
+            .child_index(reference_node)
+            .unwrap_or_else(|| self.child_nodes().len());
+        self.insert(reference_index, new_node);
     }
 
-    /// Inserts a node after the reference node as a child of the current node.
+    /// Inserts a node after the reference node as a child of the current node, or as the last
+    /// child if the reference node isn't a child.
     ///
     /// [MDN | insertAfter](https://developer.mozilla.org/en-US/docs/Web/API/Node/insertAfter)
     fn insert_after(&mut self, new_node: Self::Child, reference_node: &Self::Child) {
-        let len = self.child_nodes().len();
-        let reference_index = self.child_index(reference_node).unwrap_or(len - 2);
-        self.insert(reference_index + 1, new_node);
+        let index = self
+            .child_index(reference_node)
+            .map_or_else(|| self.child_nodes().len(), |index| index + 1);
+        self.insert(index, new_node);
     }
 
     /// Removes the current node from it's parent and removes the reference to the parent
@@ -268,7 +277,8 @@ pub trait Node: Clone + Debug + 'static + Features {
     /// [MDN | remove](https://developer.mozilla.org/en-US/docs/Web/API/Element/remove)
     fn remove(&self);
 
-    /// Remove the nth child from this node's child list
+    /// Remove the nth child from this node's child list, removing the child's reference to its
+    /// parent
     fn remove_child_at(&mut self, index: usize) -> Option<Self::Child>;
 
     /// Removes a child node from this node's child list
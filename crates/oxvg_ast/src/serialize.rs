@@ -1,9 +1,415 @@
+#[derive(Debug, Clone, Default)]
+/// Options for controlling how a document is written
+pub struct Options {
+    /// The indentation of each level of nested elements
+    pub indent: Indent,
+    /// The line ending written between indented elements
+    pub newline: Newline,
+    /// Where to declare the prefixed namespaces used in the document
+    pub namespace_declarations: NamespaceDeclarations,
+    /// Whether to write the XML declaration, such as `<?xml version="1.0"?>`
+    pub xml_declaration: XmlDeclaration,
+    /// The quote to wrap attribute values with
+    pub quote: QuoteStyle,
+    /// How to escape special characters in text and attribute values
+    pub entities: EntityMode,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// How to indent nested elements
+pub enum Indent {
+    /// Write the document without adding any whitespace
+    #[default]
+    None,
+    /// Write each element on a new line, indented by a number of spaces for each level of nesting
+    Spaces(u8),
+    /// Write each element on a new line, indented by a tab for each level of nesting
+    Tabs,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// The line ending to use when indenting
+pub enum Newline {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    Crlf,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Where to declare the prefixed namespaces used by elements and attributes
+pub enum NamespaceDeclarations {
+    /// Declare the namespace of each element's name where it isn't already in scope, and write
+    /// any other `xmlns` attributes as-is.
+    ///
+    /// Declarations parsed from a document aren't kept as attributes, so a prefix used only by
+    /// attributes, such as `xlink:href`, isn't declared.
+    #[default]
+    ElementNames,
+    /// Declare each namespace on the root element, removing any unused declarations
+    Root,
+    /// Declare each namespace on the nearest common ancestor of the elements using it, which may
+    /// be smaller for documents which use a namespace in only part of the document.
+    Lazy,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// How to write the XML declaration of a document
+pub enum XmlDeclaration {
+    /// Write the declaration only if the document has one, as it was parsed
+    #[default]
+    Preserve,
+    /// Write the document's declaration, or `<?xml version="1.0" encoding="UTF-8"?>` if it
+    /// doesn't have one
+    Add,
+    /// Don't write the declaration, even if the document has one
+    Remove,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// The quote character used around attribute values
+pub enum QuoteStyle {
+    /// `"`
+    #[default]
+    Double,
+    /// `'`, which may avoid escaping `"` with an [`EntityMode`] other than
+    /// [`EntityMode::Standard`]
+    Single,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// How to escape `&`, `<`, `>`, and quotes in text and attribute values
+pub enum EntityMode {
+    /// Escape `&` and both quotes in attribute values, and `&`, `<`, and `>` in text, with their
+    /// named entities
+    #[default]
+    Standard,
+    /// Escape only the characters which would otherwise be invalid in their context, using the
+    /// shortest entity for each
+    Minimal,
+    /// Escape each special character with its named entity, such as `&lt;`
+    Named,
+    /// Escape each special character with its numeric entity, such as `&#60;`
+    Numeric,
+}
+
+impl XmlDeclaration {
+    /// The declaration written by [`XmlDeclaration::Add`] when the document doesn't have one
+    pub const DEFAULT_DATA: &'static str = r#"version="1.0" encoding="UTF-8""#;
+}
+
+impl Options {
+    /// Returns options for writing the document in a readable format, with each element indented
+    /// by two spaces.
+    pub fn pretty() -> Self {
+        Self {
+            indent: Indent::Spaces(2),
+            ..Self::default()
+        }
+    }
+}
+
+impl Newline {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::Crlf => "\r\n",
+        }
+    }
+}
+
+impl EntityMode {
+    /// Returns the entity to write in place of a special character, or `None` if it's written
+    /// as-is.
+    ///
+    /// The `quote` is the quote wrapping an attribute value, or `None` for text. The `preceding`
+    /// text is checked for whether a `>` would end a `]]>` sequence.
+    pub fn escape(
+        self,
+        c: char,
+        quote: Option<QuoteStyle>,
+        preceding: &str,
+    ) -> Option<&'static str> {
+        let is_quote = quote.is_some_and(|quote| quote.as_char() == c);
+        match self {
+            Self::Standard => match c {
+                '&' => Some("&amp;"),
+                '<' if quote.is_none() => Some("&lt;"),
+                '>' if quote.is_none() => Some("&gt;"),
+                '"' if quote.is_some() => Some("&quot;"),
+                '\'' if quote.is_some() => Some("&apos;"),
+                _ => None,
+            },
+            Self::Minimal => match c {
+                '&' => Some("&amp;"),
+                '<' => Some("&lt;"),
+                '>' if quote.is_none() && preceding.ends_with("]]") => Some("&gt;"),
+                '"' if is_quote => Some("&#34;"),
+                '\'' if is_quote => Some("&#39;"),
+                _ => None,
+            },
+            Self::Named => match c {
+                '&' => Some("&amp;"),
+                '<' => Some("&lt;"),
+                '>' => Some("&gt;"),
+                '"' if is_quote => Some("&quot;"),
+                '\'' if is_quote => Some("&apos;"),
+                _ => None,
+            },
+            Self::Numeric => match c {
+                '&' => Some("&#38;"),
+                '<' => Some("&#60;"),
+                '>' => Some("&#62;"),
+                '"' if is_quote => Some("&#34;"),
+                '\'' if is_quote => Some("&#39;"),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl QuoteStyle {
+    pub fn as_char(self) -> char {
+        match self {
+            Self::Double => '"',
+            Self::Single => '\'',
+        }
+    }
+}
+
 pub trait Node {
     /// # Errors
     /// If the underlying serialization fails
-    fn serialize(&self) -> anyhow::Result<String>;
+    fn serialize(&self) -> anyhow::Result<String> {
+        self.serialize_with_options(Options::default())
+    }
 
     /// # Errors
     /// If the underlying serialization fails
-    fn serialize_into<Wr: std::io::Write>(&self, sink: Wr) -> anyhow::Result<()>;
+    fn serialize_into<Wr: std::io::Write>(&self, sink: Wr) -> anyhow::Result<()> {
+        self.serialize_into_with_options(sink, Options::default())
+    }
+
+    /// # Errors
+    /// If the underlying serialization fails
+    fn serialize_with_options(&self, options: Options) -> anyhow::Result<String>;
+
+    /// # Errors
+    /// If the underlying serialization fails
+    fn serialize_into_with_options<Wr: std::io::Write>(
+        &self,
+        sink: Wr,
+        options: Options,
+    ) -> anyhow::Result<()>;
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn serialize_with_options() -> anyhow::Result<()> {
+    use crate::{implementations::markup5ever::Node5Ever, parse::Node as _};
+
+    let dom = Node5Ever::parse(r#"<svg xmlns="http://www.w3.org/2000/svg"><g/></svg>"#)?;
+    assert_eq!(
+        dom.serialize_with_options(Options::pretty())?,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <g></g>\n</svg>"
+    );
+    assert_eq!(
+        dom.serialize()?,
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><g></g></svg>"#
+    );
+
+    let dom = Node5Ever::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- comment -->
+    <g>
+        <text>Hello <tspan>world</tspan></text>
+    </g>
+</svg>"#,
+    )?;
+    assert_eq!(
+        dom.serialize_with_options(Options {
+            indent: Indent::Tabs,
+            newline: Newline::Crlf,
+            ..Options::default()
+        })?,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\">\r\n\t<!-- comment -->\r\n\t<g>\r\n\t\t<text>Hello <tspan>world</tspan></text>\r\n\t</g>\r\n</svg>"
+    );
+
+    // Whitespace is only added or removed outside of `xml:space="preserve"`
+    let dom = Node5Ever::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <g xml:space="preserve">  <rect/>  <g> <rect/> </g></g>
+    <g>  <rect/>  </g>
+</svg>"#,
+    )?;
+    assert_eq!(
+        dom.serialize_with_options(Options::pretty())?,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <g xml:space=\"preserve\">  <rect></rect>  <g> <rect></rect> </g></g>\n  <g>\n    <rect></rect>\n  </g>\n</svg>"
+    );
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn serialize_namespace_declarations() -> anyhow::Result<()> {
+    use crate::{implementations::markup5ever::Node5Ever, parse::Node as _};
+
+    let dom = Node5Ever::parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><g><use xlink:href="#a"/><use xlink:href="#b"/></g><rect/></svg>"##,
+    )?;
+    assert_eq!(
+        dom.serialize_with_options(Options {
+            namespace_declarations: NamespaceDeclarations::Root,
+            ..Options::default()
+        })?,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><g><use xlink:href="#a"></use><use xlink:href="#b"></use></g><rect></rect></svg>"##
+    );
+    assert_eq!(
+        dom.serialize_with_options(Options {
+            namespace_declarations: NamespaceDeclarations::Lazy,
+            ..Options::default()
+        })?,
+        r##"<svg xmlns="http://www.w3.org/2000/svg"><g xmlns:xlink="http://www.w3.org/1999/xlink"><use xlink:href="#a"></use><use xlink:href="#b"></use></g><rect></rect></svg>"##
+    );
+
+    // When declaring by usage, should keep a namespace used only by an attribute deep in the
+    // tree, and drop unused ones
+    let dom = Node5Ever::parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:foo="http://example.com/foo"><g><g><g><g><use xlink:href="#a"/></g></g></g></g></svg>"##,
+    )?;
+    // Only element names are declared by default, as parsed declarations aren't kept
+    assert_eq!(
+        dom.serialize()?,
+        r##"<svg xmlns="http://www.w3.org/2000/svg"><g><g><g><g><use xlink:href="#a"></use></g></g></g></g></svg>"##
+    );
+    assert_eq!(
+        dom.serialize_with_options(Options {
+            namespace_declarations: NamespaceDeclarations::Root,
+            ..Options::default()
+        })?,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><g><g><g><g><use xlink:href="#a"></use></g></g></g></g></svg>"##
+    );
+    assert_eq!(
+        dom.serialize_with_options(Options {
+            namespace_declarations: NamespaceDeclarations::Lazy,
+            ..Options::default()
+        })?,
+        r##"<svg xmlns="http://www.w3.org/2000/svg"><g><g><g><g><use xmlns:xlink="http://www.w3.org/1999/xlink" xlink:href="#a"></use></g></g></g></g></svg>"##
+    );
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn serialize_xml_declaration() -> anyhow::Result<()> {
+    use crate::{implementations::markup5ever::Node5Ever, parse::Node as _};
+
+    let with_options = |dom: &Node5Ever, xml_declaration| {
+        dom.serialize_with_options(Options {
+            xml_declaration,
+            ..Options::default()
+        })
+    };
+
+    let dom = Node5Ever::parse(r#"<?xml version="1.0" encoding="UTF-8"?><svg></svg>"#)?;
+    assert_eq!(
+        with_options(&dom, XmlDeclaration::Preserve)?,
+        r#"<?xml version="1.0" encoding="UTF-8"?><svg></svg>"#
+    );
+    assert_eq!(
+        with_options(&dom, XmlDeclaration::Add)?,
+        r#"<?xml version="1.0" encoding="UTF-8"?><svg></svg>"#
+    );
+    assert_eq!(with_options(&dom, XmlDeclaration::Remove)?, "<svg></svg>");
+
+    let dom = Node5Ever::parse(r#"<?xml version="1.1" standalone="no"?><svg></svg>"#)?;
+    assert_eq!(
+        with_options(&dom, XmlDeclaration::Add)?,
+        r#"<?xml version="1.1" standalone="no"?><svg></svg>"#
+    );
+
+    let dom = Node5Ever::parse("<svg></svg>")?;
+    assert_eq!(with_options(&dom, XmlDeclaration::Preserve)?, "<svg></svg>");
+    assert_eq!(
+        with_options(&dom, XmlDeclaration::Add)?,
+        r#"<?xml version="1.0" encoding="UTF-8"?><svg></svg>"#
+    );
+    assert_eq!(
+        dom.serialize_with_options(Options {
+            xml_declaration: XmlDeclaration::Add,
+            ..Options::pretty()
+        })?,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg></svg>"
+    );
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn serialize_quote() -> anyhow::Result<()> {
+    use crate::{implementations::markup5ever::Node5Ever, parse::Node as _};
+
+    let source = r#"<svg font-family="&quot;Times New Roman&quot;, 'Arial'"></svg>"#;
+    let dom = Node5Ever::parse(source)?;
+    assert_eq!(
+        dom.serialize()?,
+        r#"<svg font-family="&quot;Times New Roman&quot;, &apos;Arial&apos;"></svg>"#
+    );
+
+    let single = dom.serialize_with_options(Options {
+        quote: QuoteStyle::Single,
+        entities: EntityMode::Minimal,
+        ..Options::default()
+    })?;
+    assert_eq!(
+        single,
+        r#"<svg font-family='"Times New Roman", &#39;Arial&#39;'></svg>"#
+    );
+    assert_eq!(Node5Ever::parse(&single)?.serialize()?, dom.serialize()?);
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn serialize_entities() -> anyhow::Result<()> {
+    use crate::{implementations::markup5ever::Node5Ever, parse::Node as _};
+
+    let source = r#"<svg aria-label="a &lt; b &amp;&amp; &quot;c&quot; > d"><text>a &lt; b &amp; ]]&gt; c > d</text></svg>"#;
+    let dom = Node5Ever::parse(source)?;
+    assert_eq!(
+        dom.serialize()?,
+        r#"<svg aria-label="a < b &amp;&amp; &quot;c&quot; > d"><text>a &lt; b &amp; ]]&gt; c &gt; d</text></svg>"#
+    );
+
+    let named = dom.serialize_with_options(Options {
+        entities: EntityMode::Named,
+        ..Options::default()
+    })?;
+    assert_eq!(
+        named,
+        r#"<svg aria-label="a &lt; b &amp;&amp; &quot;c&quot; &gt; d"><text>a &lt; b &amp; ]]&gt; c &gt; d</text></svg>"#
+    );
+    assert_eq!(Node5Ever::parse(&named)?.serialize()?, dom.serialize()?);
+
+    let minimal = dom.serialize_with_options(Options {
+        entities: EntityMode::Minimal,
+        ..Options::default()
+    })?;
+    assert_eq!(
+        minimal,
+        r#"<svg aria-label="a &lt; b &amp;&amp; &#34;c&#34; > d"><text>a &lt; b &amp; ]]&gt; c > d</text></svg>"#
+    );
+    assert_eq!(Node5Ever::parse(&minimal)?.serialize()?, dom.serialize()?);
+
+    let numeric = dom.serialize_with_options(Options {
+        entities: EntityMode::Numeric,
+        ..Options::default()
+    })?;
+    assert_eq!(
+        numeric,
+        r#"<svg aria-label="a &#60; b &#38;&#38; &#34;c&#34; &#62; d"><text>a &#60; b &#38; ]]&#62; c &#62; d</text></svg>"#
+    );
+    assert_eq!(Node5Ever::parse(&numeric)?.serialize()?, dom.serialize()?);
+    Ok(())
 }
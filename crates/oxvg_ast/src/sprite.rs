@@ -0,0 +1,186 @@
+//! Combines the root `<svg>` of many documents into a single document, such as a set of icons
+//! into an icon sprite.
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    attribute::{Attr, Attributes},
+    document::Document,
+    element::Element,
+    name::Name,
+    node::Node,
+};
+
+/// Attributes of an `<svg>` which don't apply to a `<symbol>`
+const SVG_ONLY_ATTRIBUTES: [&str; 7] =
+    ["id", "x", "y", "width", "height", "version", "baseProfile"];
+
+/// Returns a copy of the first input's document, where the root `<svg>` contains a `<symbol>` for
+/// each input.
+///
+/// Each input is a name, such as the file name of an icon, and the root `<svg>` of its document.
+/// Each `<symbol>` is given the name as its `id`, suffixed with a number when the name has
+/// already been used, along with the `viewBox` and presentation attributes of the `<svg>`.
+///
+/// So that ids of different inputs don't collide, each id within an input is prefixed with the
+/// `id` of its `<symbol>`, along with any references to it, as by [`Element::rename_ids`]. When
+/// a prefixed id is already used, such as by the `<symbol>` of any input, it's suffixed with
+/// a number too.
+///
+/// Returns `None` when there are no inputs, or when the first input isn't within a document.
+pub fn build_sprite<E: Element>(inputs: &[(String, E)]) -> Option<E> {
+    let (_, first) = inputs.first()?;
+    let document = first.document()?.clone_deep();
+    let sprite = document.as_document().create_element(E::Name::new_ns(
+        None,
+        "svg".into(),
+        first.qual_name().ns().clone(),
+    ));
+    document.replace_children(vec![sprite.as_child()]);
+
+    let mut used_ids = HashSet::new();
+    let mut unique_id = |id: String| {
+        let mut unique_id = id.clone();
+        let mut suffix = 1;
+        while !used_ids.insert(unique_id.clone()) {
+            suffix += 1;
+            unique_id = format!("{id}-{suffix}");
+        }
+        unique_id
+    };
+    // reserved first, so that a prefixed id can't take the name of a later input
+    let symbol_ids: Vec<_> = inputs
+        .iter()
+        .map(|(name, _)| unique_id(name.clone()))
+        .collect();
+    for ((_, svg), symbol_id) in inputs.iter().zip(symbol_ids) {
+        let symbol = document.as_document().create_element(E::Name::new_ns(
+            None,
+            "symbol".into(),
+            svg.qual_name().ns().clone(),
+        ));
+        for attr in svg.attributes().into_iter() {
+            if attr.prefix().is_none() && SVG_ONLY_ATTRIBUTES.contains(&attr.local_name().as_ref())
+            {
+                continue;
+            }
+            symbol.attributes().set_named_item(attr.clone());
+        }
+        symbol.set_attribute_local("id".into(), symbol_id.as_str().into());
+
+        // sorted, so that suffixes are given in a consistent order
+        let mut ids: Vec<_> = svg
+            .collect_ids()
+            .into_keys()
+            .map(|id| id.as_ref().to_string())
+            .collect();
+        ids.sort_unstable();
+        let ids: HashMap<String, String> = ids
+            .into_iter()
+            .map(|id| {
+                let new_id = unique_id(format!("{symbol_id}-{id}"));
+                (id, new_id)
+            })
+            .collect();
+        for child in svg.child_nodes() {
+            let Some(child) = E::new(child.clone()) else {
+                symbol.append(child.clone_deep());
+                continue;
+            };
+            let child = child.clone_deep();
+            child.rename_ids(&ids);
+            symbol.append(child.as_child());
+        }
+        sprite.append(symbol.as_child());
+    }
+    Some(document)
+}
+
+#[cfg(all(
+    test,
+    feature = "parse",
+    feature = "serialize",
+    feature = "markup5ever"
+))]
+#[test]
+fn build_sprite_symbols() -> anyhow::Result<()> {
+    use crate::{
+        implementations::markup5ever::{Element5Ever, Node5Ever},
+        parse::Node as _,
+        serialize::{NamespaceDeclarations, Node as _, Options},
+    };
+
+    // Elements only hold a weak reference to their document, so keep each document alive
+    let mut documents = vec![];
+    let mut parse = |svg: &str| -> anyhow::Result<Element5Ever> {
+        let dom = Node5Ever::parse(svg)?;
+        documents.push(dom.clone());
+        <Element5Ever as Element>::find_element(dom).ok_or_else(|| anyhow::anyhow!("no element"))
+    };
+    let inputs = vec![
+        (
+            String::from("circle"),
+            parse(
+                r##"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24" viewBox="0 0 24 24" fill="none"><defs><linearGradient id="a"/></defs><circle r="10" fill="url(#a)"/></svg>"##,
+            )?,
+        ),
+        (
+            String::from("square"),
+            parse(
+                r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 16 16"><path id="a" d="M0 0h10v10H0z"/><use xlink:href="#a"/><use href="#external"/></svg>"##,
+            )?,
+        ),
+        (
+            String::from("circle"),
+            parse(r#"<svg xmlns="http://www.w3.org/2000/svg"><circle r="5"/></svg>"#)?,
+        ),
+        (
+            String::from("circle-a"),
+            parse(
+                r##"<svg xmlns="http://www.w3.org/2000/svg"><circle id="a" r="5" style="fill:url(#a)"/></svg>"##,
+            )?,
+        ),
+    ];
+
+    let sprite = build_sprite(&inputs).unwrap();
+    assert_eq!(
+        sprite.serialize_with_options(Options {
+            namespace_declarations: NamespaceDeclarations::Root,
+            ..Options::default()
+        })?,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><symbol viewBox="0 0 24 24" fill="none" id="circle"><defs><linearGradient id="circle-a-2"></linearGradient></defs><circle r="10" fill="url(#circle-a-2)"></circle></symbol><symbol viewBox="0 0 16 16" id="square"><path id="square-a" d="M0 0h10v10H0z"></path><use xlink:href="#square-a"></use><use href="#external"></use></symbol><symbol id="circle-2"><circle r="5"></circle></symbol><symbol id="circle-a"><circle id="circle-a-a" r="5" style="fill:url(#circle-a-a)"></circle></symbol></svg>"##
+    );
+
+    // The inputs are unchanged
+    assert_eq!(inputs[0].1.collect_ids().len(), 1);
+    assert!(build_sprite::<Element5Ever>(&[]).is_none());
+    Ok(())
+}
+
+#[cfg(all(
+    test,
+    feature = "parse",
+    feature = "serialize",
+    feature = "style",
+    feature = "markup5ever"
+))]
+#[test]
+fn build_sprite_styles() -> anyhow::Result<()> {
+    use crate::{
+        implementations::markup5ever::{Element5Ever, Node5Ever},
+        parse::Node as _,
+        serialize::Node as _,
+    };
+
+    let dom = Node5Ever::parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg"><style>#a{fill:red}</style><circle id="a" r="5"/></svg>"##,
+    )?;
+    let svg = <Element5Ever as Element>::find_element(dom.clone())
+        .ok_or_else(|| anyhow::anyhow!("no element"))?;
+
+    let sprite = build_sprite(&[(String::from("icon"), svg)]).unwrap();
+    assert_eq!(
+        sprite.serialize()?,
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><symbol id="icon"><style>#icon-a{fill:red}</style><circle id="icon-a" r="5"></circle></symbol></svg>"#
+    );
+    Ok(())
+}
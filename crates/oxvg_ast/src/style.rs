@@ -1,4 +1,6 @@
-use cssparser_lightningcss::{match_ignore_ascii_case, ParseError, Parser, ParserInput, Token};
+use cssparser_lightningcss::{
+    match_ignore_ascii_case, BasicParseErrorKind, ParseError, Parser, ParserInput, Token,
+};
 use itertools::Itertools;
 use lightningcss::{
     declaration,
@@ -39,6 +41,7 @@ use lightningcss::{
 };
 use smallvec::SmallVec;
 use std::{
+    cell::RefCell,
     collections::{HashMap, HashSet},
     fmt::Write,
 };
@@ -1148,8 +1151,7 @@ impl<'i> Parse<'i> for SVGTransform {
                         }
                         "translate" => {
                             let x = f32::parse(input)?;
-                            input.skip_whitespace();
-                            if let Ok(y) = input.try_parse(f32::parse) {
+                            if let Ok(y) = input.try_parse(parse_comma_number) {
                                 Ok(SVGTransform::Translate(x, y))
                             } else {
                                 Ok(SVGTransform::Translate(x, 0.0))
@@ -1157,8 +1159,7 @@ impl<'i> Parse<'i> for SVGTransform {
                         }
                         "scale" => {
                             let x = f32::parse(input)?;
-                            input.skip_whitespace();
-                            if let Ok(y) = input.try_parse(f32::parse) {
+                            if let Ok(y) = input.try_parse(parse_comma_number) {
                                 Ok(SVGTransform::Scale(x, y))
                             } else {
                                 Ok(SVGTransform::Scale(x, x))
@@ -1166,10 +1167,8 @@ impl<'i> Parse<'i> for SVGTransform {
                         }
                         "rotate" => {
                             let angle = f32::parse(input)?;
-                            input.skip_whitespace();
-                            if let Ok(x) = input.try_parse(f32::parse) {
-                                input.skip_whitespace();
-                                let y = f32::parse(input)?;
+                            if let Ok(x) = input.try_parse(parse_comma_number) {
+                                let y = parse_comma_number(input)?;
                                 Ok(SVGTransform::Rotate(angle, x, y))
                             } else {
                                 Ok(SVGTransform::Rotate(angle, 0.0, 0.0))
@@ -1193,6 +1192,15 @@ impl<'i> Parse<'i> for SVGTransform {
     }
 }
 
+/// Parses a number, optionally preceded by the comma separating it from the previous argument
+fn parse_comma_number<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<f32, ParseError<'i, ParserError<'i>>> {
+    input.skip_whitespace();
+    input.try_parse(Parser::expect_comma).ok();
+    f32::parse(input)
+}
+
 impl ToCss for SVGTransform {
     fn to_css<W>(&self, dest: &mut Printer<W>) -> Result<(), PrinterError>
     where
@@ -1345,7 +1353,12 @@ impl<'i> Parse<'i> for SVGTransformList {
             input.skip_whitespace();
             input.try_parse(Parser::expect_comma).ok();
             input.skip_whitespace();
+            let start = input.position();
             if let Ok(item) = input.try_parse(SVGTransform::parse) {
+                // NOTE: The end of input closes any unclosed function, which isn't valid here
+                if !input.slice_from(start).ends_with(')') {
+                    return Err(input.new_error(BasicParseErrorKind::EndOfInput));
+                }
                 results.push(item);
             } else {
                 return Ok(SVGTransformList(results));
@@ -1497,6 +1510,110 @@ pub fn root<E: Element>(root: &E) -> String {
         .join("\n")
 }
 
+/// Returns the stylesheet with each id selector, and each `url(#id)`, of an id in `ids` replaced
+/// with its new value, or `None` when nothing is renamed.
+///
+/// Hashes within declarations, such as colors, are left as-is.
+pub fn rename_id_references(css: &str, ids: &HashMap<String, String>) -> Option<String> {
+    let mut input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut input);
+    let mut replacements = vec![];
+    collect_id_replacements(&mut parser, ids, false, &mut replacements);
+    if replacements.is_empty() {
+        return None;
+    }
+
+    let mut renamed = String::with_capacity(css.len());
+    let mut end = 0;
+    for (start, stop, replacement) in replacements {
+        renamed.push_str(&css[end..start]);
+        renamed.push_str(&replacement);
+        end = stop;
+    }
+    renamed.push_str(&css[end..]);
+    Some(renamed)
+}
+
+/// Collects the byte range and replacement of each reference to an id in `ids`, where
+/// `is_declarations` is whether the parser is within a block of declarations.
+fn collect_id_replacements(
+    parser: &mut Parser,
+    ids: &HashMap<String, String>,
+    is_declarations: bool,
+    replacements: &mut Vec<(usize, usize, String)>,
+) {
+    // Whether the next block belongs to an at-rule containing rules, such as `@media`
+    let mut is_rule_list_prelude = false;
+    loop {
+        let start = parser.position().byte_index();
+        let Ok(token) = parser.next_including_whitespace_and_comments() else {
+            break;
+        };
+        let token = token.clone();
+        let end = parser.position().byte_index();
+        match token {
+            Token::IDHash(id) if !is_declarations => {
+                let Some(new_id) = ids.get(&*id) else {
+                    continue;
+                };
+                let mut replacement = String::from("#");
+                if cssparser_lightningcss::serialize_identifier(new_id, &mut replacement).is_ok() {
+                    replacements.push((start, end, replacement));
+                }
+            }
+            Token::UnquotedUrl(url) => {
+                if let Some(new_id) = url.strip_prefix('#').and_then(|id| ids.get(id)) {
+                    replacements.push((start, end, format!("url(#{new_id})")));
+                }
+            }
+            Token::Function(name) if name.eq_ignore_ascii_case("url") => {
+                let _ = parser.parse_nested_block(|parser| {
+                    parser.skip_whitespace();
+                    let start = parser.position();
+                    let url = parser.expect_string()?.clone();
+                    if let Some(new_id) = url.strip_prefix('#').and_then(|id| ids.get(id)) {
+                        let quote = &parser.slice_from(start)[..1];
+                        replacements.push((
+                            start.byte_index(),
+                            parser.position().byte_index(),
+                            format!("{quote}#{new_id}{quote}"),
+                        ));
+                    }
+                    Ok::<_, ParseError<()>>(())
+                });
+            }
+            Token::AtKeyword(name) => {
+                is_rule_list_prelude = match_ignore_ascii_case! { &*name,
+                    "media" | "supports" | "document" | "-moz-document" | "layer" | "container"
+                        | "scope" | "starting-style" => true,
+                    _ => false,
+                };
+            }
+            Token::Semicolon => is_rule_list_prelude = false,
+            Token::CurlyBracketBlock => {
+                collect_nested_id_replacements(parser, ids, !is_rule_list_prelude, replacements);
+                is_rule_list_prelude = false;
+            }
+            Token::Function(_) | Token::ParenthesisBlock | Token::SquareBracketBlock => {
+                collect_nested_id_replacements(parser, ids, is_declarations, replacements);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_nested_id_replacements(
+    parser: &mut Parser,
+    ids: &HashMap<String, String>,
+    is_declarations: bool,
+    replacements: &mut Vec<(usize, usize, String)>,
+) {
+    let _ = parser.parse_nested_block(|parser| {
+        collect_id_replacements(parser, ids, is_declarations, replacements);
+        Ok::<_, ParseError<()>>(())
+    });
+}
+
 #[derive(Debug)]
 pub struct ElementData<E: Element> {
     inline_style: Option<E::Atom>,
@@ -1537,6 +1654,39 @@ impl<E: Element> ElementData<E> {
     }
 }
 
+#[derive(Debug)]
+/// Selectors compiled while computing styles, keyed by their source, so that each selector
+/// of a stylesheet is only parsed once across all the elements it's matched against
+pub struct SelectorCache<E: Element>(RefCell<HashMap<String, Option<Selector<E>>>>);
+
+impl<E: Element> Default for SelectorCache<E> {
+    fn default() -> Self {
+        Self(RefCell::new(HashMap::new()))
+    }
+}
+
+impl<E: Element> SelectorCache<E> {
+    /// Returns whether the selector matches the element, or `false` if the selector is invalid
+    pub fn matches(&self, selector: &str, element: &E) -> bool {
+        let mut selectors = self.0.borrow_mut();
+        if !selectors.contains_key(selector) {
+            selectors.insert(selector.to_string(), Selector::new(selector).ok());
+        }
+        selectors[selector]
+            .as_ref()
+            .is_some_and(|select| select.matches_naive(element))
+    }
+
+    /// Returns the number of distinct selectors parsed
+    pub fn len(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().is_empty()
+    }
+}
+
 impl<'i> ComputedStyles<'i> {
     /// Include all sources of styles
     pub fn with_all<E: Element>(
@@ -1544,11 +1694,12 @@ impl<'i> ComputedStyles<'i> {
         element: &E,
         styles: &Option<StyleSheet<'i, '_>>,
         element_styles: &'i HashMap<E, ElementData<E>>,
+        selectors: &SelectorCache<E>,
     ) -> ComputedStyles<'i> {
         self.with_inline_style(element, element_styles)
             .with_attribute(element, element_styles)
-            .with_style(element, styles)
-            .with_inherited(element, styles, element_styles)
+            .with_style(element, styles, selectors)
+            .with_inherited(element, styles, element_styles, selectors)
     }
 
     /// Include the computed styles of a parent element
@@ -1557,11 +1708,15 @@ impl<'i> ComputedStyles<'i> {
         element: &E,
         styles: &Option<StyleSheet<'i, '_>>,
         element_styles: &'i HashMap<E, ElementData<E>>,
+        selectors: &SelectorCache<E>,
     ) -> ComputedStyles<'i> {
         let Some(parent) = Element::parent_element(element) else {
             return self;
         };
-        let parent_styles = ComputedStyles::default().with_all(&parent, styles, element_styles);
+        let parent_styles =
+            ComputedStyles::default().with_all(&parent, styles, element_styles, selectors);
+        // Values the parent inherits are passed down, unless the parent overrides them
+        self.inherited.extend(parent_styles.inherited);
         self.inherited.extend(
             parent_styles
                 .attr
@@ -1600,6 +1755,7 @@ impl<'i> ComputedStyles<'i> {
         mut self,
         element: &E,
         styles: &Option<StyleSheet<'i, '_>>,
+        selectors: &SelectorCache<E>,
     ) -> ComputedStyles<'i> {
         let Some(styles) = styles.as_ref() else {
             return self;
@@ -1608,7 +1764,7 @@ impl<'i> ComputedStyles<'i> {
             .rules
             .0
             .iter()
-            .for_each(|s| self.with_nested_style(element, s, "", 0, &Mode::Static));
+            .for_each(|s| self.with_nested_style(element, s, "", 0, &Mode::Static, selectors));
         self
     }
 
@@ -1620,6 +1776,7 @@ impl<'i> ComputedStyles<'i> {
         selector: &str,
         specificity: u32,
         mode: &Mode,
+        selectors: &SelectorCache<E>,
     ) {
         match style {
             rules::CssRule::Style(r) => r.selectors.0.iter().for_each(|s| {
@@ -1627,10 +1784,7 @@ impl<'i> ComputedStyles<'i> {
                     return;
                 };
                 let selector = format!("{selector}{this_selector}");
-                let Ok(select) = Selector::new(selector.as_str()) else {
-                    return;
-                };
-                if !select.matches_naive(element) {
+                if !selectors.matches(&selector, element) {
                     return;
                 };
                 let specificity = specificity + s.specificity();
@@ -1639,7 +1793,14 @@ impl<'i> ComputedStyles<'i> {
             rules::CssRule::Container(rules::container::ContainerRule { rules, .. })
             | rules::CssRule::Media(rules::media::MediaRule { rules, .. }) => {
                 rules.0.iter().for_each(|r| {
-                    self.with_nested_style(element, r, selector, specificity, &Mode::Dynamic);
+                    self.with_nested_style(
+                        element,
+                        r,
+                        selector,
+                        specificity,
+                        &Mode::Dynamic,
+                        selectors,
+                    );
                 });
             }
             _ => {}
@@ -2052,3 +2213,109 @@ impl Mode {
         !self.is_static()
     }
 }
+
+#[cfg(test)]
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn transform_round_trip() {
+    use lightningcss::values::{length::LengthValue, percentage::Percentage};
+
+    // A fixed xorshift sequence, so that failing cases can be reproduced
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut number = || {
+        let n = next();
+        let magnitude = [1.0, 1e-3, 1e3, 1e5][(n % 4) as usize];
+        let value = ((n >> 8) % 20_001) as f32 / 1e4 - 1.0;
+        value * magnitude
+    };
+    let close = |a: f32, b: f32| (a - b).abs() <= f32::max(a.abs(), 1.0) * 1e-5;
+
+    for i in 0..500 {
+        let transform = match i % 6 {
+            0 => SVGTransform::Matrix(Matrix {
+                a: number(),
+                b: number(),
+                c: number(),
+                d: number(),
+                e: number(),
+                f: number(),
+            }),
+            1 => SVGTransform::Translate(number(), number()),
+            2 => SVGTransform::Scale(number(), number()),
+            3 => SVGTransform::Rotate(number(), number(), number()),
+            4 => SVGTransform::SkewX(number()),
+            _ => SVGTransform::SkewY(number()),
+        };
+        let css = transform.to_css_string(PrinterOptions::default()).unwrap();
+        let comma_separated = css.replace(' ', ", ");
+
+        for input in [&css, &comma_separated] {
+            let SVGTransformList(parsed) = SVGTransformList::parse_string(input).unwrap();
+            let [parsed] = parsed.as_slice() else {
+                panic!("expected one transform from `{input}`, found {parsed:?}");
+            };
+            let values = |transform: &SVGTransform| match transform {
+                SVGTransform::Matrix(Matrix { a, b, c, d, e, f }) => vec![*a, *b, *c, *d, *e, *f],
+                SVGTransform::Translate(x, y) | SVGTransform::Scale(x, y) => vec![*x, *y],
+                SVGTransform::Rotate(a, x, y) => vec![*a, *x, *y],
+                SVGTransform::SkewX(a) | SVGTransform::SkewY(a) => vec![*a],
+                SVGTransform::CssTransform(_) => vec![],
+            };
+            assert_eq!(
+                std::mem::discriminant(parsed),
+                std::mem::discriminant(&transform),
+                "`{input}` parsed as {parsed:?}"
+            );
+            assert!(
+                values(parsed)
+                    .into_iter()
+                    .zip(values(&transform))
+                    .all(|(a, b)| close(a, b)),
+                "`{input}` parsed as {parsed:?}, expected {transform:?}"
+            );
+        }
+    }
+
+    // Unclosed functions should be left unparsed, rather than closed by the end of input
+    assert!(SVGTransformList::parse_string("rotate(45, 34, 34").is_err());
+
+    for _ in 0..500 {
+        let value = number();
+        let css = value.to_css_string(PrinterOptions::default()).unwrap();
+        let parsed = CSSNumber::parse_string(&css).unwrap();
+        assert!(
+            close(parsed, value),
+            "`{css}` parsed as {parsed}, expected {value}"
+        );
+    }
+
+    for i in 0..500 {
+        let value = if i % 2 == 0 {
+            LengthPercentage::Dimension(LengthValue::Px(number()))
+        } else {
+            LengthPercentage::Percentage(Percentage(number()))
+        };
+        let css = value.to_css_string(PrinterOptions::default()).unwrap();
+        let parsed = LengthPercentage::parse_string(&css).unwrap();
+        assert!(
+            match (&parsed, &value) {
+                (
+                    LengthPercentage::Dimension(LengthValue::Px(a)),
+                    LengthPercentage::Dimension(LengthValue::Px(b)),
+                )
+                | (
+                    LengthPercentage::Percentage(Percentage(a)),
+                    LengthPercentage::Percentage(Percentage(b)),
+                ) => close(*a, *b),
+                _ => false,
+            },
+            "`{css}` parsed as {parsed:?}, expected {value:?}"
+        );
+    }
+}
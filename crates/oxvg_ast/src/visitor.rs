@@ -4,9 +4,10 @@ use lightningcss::stylesheet;
 
 use crate::{
     element::Element,
+    name::Name,
     node::{self, Node},
     selectors::Selector,
-    style::{self, ComputedStyles, ElementData},
+    style::{self, ComputedStyles, ElementData, SelectorCache},
 };
 
 #[derive(Debug)]
@@ -16,6 +17,7 @@ pub struct Context<'i, 'o, E: Element> {
     pub element_styles: &'i HashMap<E, ElementData<E>>,
     pub root: E,
     pub flags: ContextFlags,
+    pub selectors: SelectorCache<E>,
 }
 
 impl<'i, 'o, E: Element> Context<'i, 'o, E> {
@@ -30,8 +32,24 @@ impl<'i, 'o, E: Element> Context<'i, 'o, E> {
             element_styles,
             root,
             flags,
+            selectors: SelectorCache::default(),
         }
     }
+
+    /// Computes the styles of any element in the document, including those inherited
+    /// from its ancestors.
+    ///
+    /// The stylesheet is matched against the element and then against each of its ancestors.
+    /// Only the compiled selectors are cached, so prefer [`Context::computed_styles`] for the
+    /// element being visited.
+    pub fn computed_styles_of(&self, element: &E) -> ComputedStyles<'i> {
+        ComputedStyles::default().with_all(
+            element,
+            &self.stylesheet,
+            self.element_styles,
+            &self.selectors,
+        )
+    }
 }
 
 impl PrepareOutcome {
@@ -61,6 +79,10 @@ bitflags! {
         const within_foreign_object = 0b1000;
         /// Whether to skip over the element's children or not
         const skip_children = 0b1_0000;
+        /// Whether this element is a `clipPath` or `mask`, or a child of one
+        const within_clip_or_mask = 0b10_0000;
+        /// Whether this element is a `defs` or a child of one
+        const within_defs = 0b100_0000;
     }
 }
 
@@ -156,6 +178,12 @@ pub trait Visitor<E: Element> {
         false
     }
 
+    /// Whether to visit elements outside the SVG namespace within a `<foreignObject>`, such as
+    /// XHTML, which are otherwise kept as-is
+    fn visit_foreign_content(&self) -> bool {
+        false
+    }
+
     fn prepare(&mut self, document: &E, context_flags: &mut ContextFlags) -> PrepareOutcome {
         PrepareOutcome::none
     }
@@ -194,10 +222,10 @@ pub trait Visitor<E: Element> {
     ///
     /// # Errors
     /// If any of the visitor's methods fail
-    fn visit<'i>(
+    fn visit(
         &mut self,
         element: &mut E,
-        context: &mut Context<'i, '_, E>,
+        context: &mut Context<'_, '_, E>,
     ) -> Result<(), Self::Error> {
         match element.node_type() {
             node::Type::Document => {
@@ -209,11 +237,7 @@ pub trait Visitor<E: Element> {
                 log::debug!("visiting {element:?}");
                 let use_style = context.flags.contains(ContextFlags::use_style);
                 if use_style && self.use_style(element) {
-                    context.computed_styles = ComputedStyles::<'i>::default().with_all(
-                        element,
-                        &context.stylesheet,
-                        context.element_styles,
-                    );
+                    context.computed_styles = context.computed_styles_of(element);
                 } else {
                     context.flags.set(ContextFlags::use_style, false);
                 }
@@ -224,7 +248,28 @@ pub trait Visitor<E: Element> {
                 if is_root_foreign_object {
                     context.flags.set(ContextFlags::within_foreign_object, true);
                 }
-                self.element(element, context)?;
+                let is_root_clip_or_mask =
+                    !context.flags.contains(ContextFlags::within_clip_or_mask)
+                        && element.prefix().is_none()
+                        && matches!(element.local_name().as_ref(), "clipPath" | "mask");
+                if is_root_clip_or_mask {
+                    context.flags.set(ContextFlags::within_clip_or_mask, true);
+                }
+                let is_root_defs = !context.flags.contains(ContextFlags::within_defs)
+                    && element.prefix().is_none()
+                    && element.local_name().as_ref() == "defs";
+                if is_root_defs {
+                    context.flags.set(ContextFlags::within_defs, true);
+                }
+                // Foreign content, such as XHTML, is kept as-is unless the visitor opts in
+                let is_foreign_content =
+                    context.flags.contains(ContextFlags::within_foreign_object)
+                        && !is_root_foreign_object
+                        && element.qual_name().ns().as_ref() != "http://www.w3.org/2000/svg"
+                        && !self.visit_foreign_content();
+                if !is_foreign_content {
+                    self.element(element, context)?;
+                }
                 context.flags.set(ContextFlags::use_style, use_style);
                 if context.flags.contains(ContextFlags::skip_children) {
                     context.flags.set(ContextFlags::skip_children, false);
@@ -232,12 +277,20 @@ pub trait Visitor<E: Element> {
                     self.visit_children(element, context)?;
                 }
                 log::debug!("left the {element:?}");
-                self.exit_element(element, context)?;
+                if !is_foreign_content {
+                    self.exit_element(element, context)?;
+                }
                 if is_root_foreign_object {
                     context
                         .flags
                         .set(ContextFlags::within_foreign_object, false);
                 }
+                if is_root_clip_or_mask {
+                    context.flags.set(ContextFlags::within_clip_or_mask, false);
+                }
+                if is_root_defs {
+                    context.flags.set(ContextFlags::within_defs, false);
+                }
                 Ok(())
             }
             _ => Ok(()),
@@ -286,3 +339,138 @@ pub fn has_scripts<E: Element>(root: &E) -> bool {
             .find_element().map(|e| e.select_with_selector(Selector::new( "script,a[href^='javascript:'],[onbegin],[onend],[onrepeat],[onload],[onabort],[onerror],[onresize],[onscroll],[onunload],[onzoom],[oncopy],[oncut],[onpaste],[oncancel],[oncanplay],[oncanplaythrough],[onchange],[onclick],[onclose],[oncuechange],[ondblclick],[ondrag],[ondragend],[ondragenter],[ondragleave],[ondragover],[ondragstart],[ondrop],[ondurationchange],[onemptied],[onended],[onfocus],[oninput],[oninvalid],[onkeydown],[onkeypress],[onkeyup],[onloadeddata],[onloadedmetadata],[onloadstart],[onmousedown],[onmouseenter],[onmouseleave],[onmousemove],[onmouseout],[onmouseup],[onmousewheel],[onpause],[onplay],[onplaying],[onprogress],[onratechange],[onreset],[onseeked],[onseeking],[onselect],[onshow],[onstalled],[onsubmit],[onsuspend],[ontimeupdate],[ontoggle],[onvolumechange],[onwaiting],[onactivate],[onfocusin],[onfocusout],[onmouseover]" ).unwrap()))
             .is_some_and(|mut e| e.next().is_some())
 }
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn context_flags_within() -> anyhow::Result<()> {
+    use crate::{
+        implementations::markup5ever::{Element5Ever, Node5Ever},
+        parse::Node as _,
+    };
+
+    struct Within(Vec<(String, usize)>);
+    impl Visitor<Element5Ever> for Within {
+        type Error = ();
+
+        fn element(
+            &mut self,
+            element: &mut Element5Ever,
+            context: &mut Context<Element5Ever>,
+        ) -> Result<(), Self::Error> {
+            let flags = context.flags.clone()
+                & (ContextFlags::within_clip_or_mask | ContextFlags::within_defs);
+            let id = element.get_attribute_local(&"id".into());
+            if let Some(id) = id {
+                self.0.push((id.to_string(), flags.bits()));
+            }
+            Ok(())
+        }
+    }
+
+    let dom = Node5Ever::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <defs id="a">
+        <clipPath id="b">
+            <path id="c"/>
+        </clipPath>
+    </defs>
+    <mask id="d"><path id="e"/></mask>
+    <path id="f"/>
+</svg>"#,
+    )?;
+    let mut root = <Element5Ever as Element>::find_element(dom.clone())
+        .ok_or(crate::parse::Error::NoElementInDocument)?;
+    let mut visitor = Within(vec![]);
+    visitor
+        .start(&mut root)
+        .map_err(|()| anyhow::anyhow!("visitor failed"))?;
+
+    let clip_or_mask = ContextFlags::within_clip_or_mask.bits();
+    let defs = ContextFlags::within_defs.bits();
+    assert_eq!(
+        visitor.0,
+        vec![
+            ("a".to_string(), defs),
+            ("b".to_string(), clip_or_mask | defs),
+            ("c".to_string(), clip_or_mask | defs),
+            ("d".to_string(), clip_or_mask),
+            ("e".to_string(), clip_or_mask),
+            ("f".to_string(), 0),
+        ]
+    );
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn context_computed_styles_of() -> anyhow::Result<()> {
+    use lightningcss::properties::PropertyId;
+
+    use crate::{
+        implementations::markup5ever::{Element5Ever, Node5Ever},
+        parse::Node as _,
+        style::Id,
+    };
+
+    let dom = Node5Ever::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <style>g { fill: red; }</style>
+    <g>
+        <path id="a"/>
+    </g>
+    <path id="b"/>
+</svg>"#,
+    )?;
+    let root = <Element5Ever as Element>::find_element(dom.clone())
+        .ok_or(crate::parse::Error::NoElementInDocument)?;
+    let style_source = style::root(&root);
+    let element_styles = ElementData::new(&root);
+    let mut context = Context::new(root.clone(), ContextFlags::use_style, &element_styles);
+    context.stylesheet =
+        stylesheet::StyleSheet::parse(&style_source, stylesheet::ParserOptions::default()).ok();
+
+    let a = root.select("#a").unwrap().next().expect("#a should exist");
+    let b = root.select("#b").unwrap().next().expect("#b should exist");
+    let fill = Id::CSS(PropertyId::Fill);
+    assert!(context.computed_styles_of(&a).inherited.contains_key(&fill));
+    assert!(!context.computed_styles_of(&b).inherited.contains_key(&fill));
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parse", feature = "markup5ever"))]
+#[test]
+fn context_selector_cache() -> anyhow::Result<()> {
+    use crate::{
+        implementations::markup5ever::{Element5Ever, Node5Ever},
+        parse::Node as _,
+    };
+
+    let dom = Node5Ever::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <style>
+        g { fill: red; }
+        .a { fill: blue; }
+        @media print { .a, .b { fill: green; } }
+    </style>
+    <g>
+        <path class="a"/>
+        <path class="b"/>
+    </g>
+    <path class="a"/>
+</svg>"#,
+    )?;
+    let root = <Element5Ever as Element>::find_element(dom.clone())
+        .ok_or(crate::parse::Error::NoElementInDocument)?;
+    let style_source = style::root(&root);
+    let element_styles = ElementData::new(&root);
+    let mut context = Context::new(root.clone(), ContextFlags::use_style, &element_styles);
+    context.stylesheet =
+        stylesheet::StyleSheet::parse(&style_source, stylesheet::ParserOptions::default()).ok();
+
+    // Each distinct selector is parsed once, regardless of how many elements are matched
+    for element in root.breadth_first() {
+        context.computed_styles_of(&element);
+    }
+    assert_eq!(context.selectors.len(), 3);
+    Ok(())
+}
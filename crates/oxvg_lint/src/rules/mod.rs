@@ -1,8 +1,19 @@
 pub mod attributes;
+pub mod no_empty_text;
+pub mod no_negative_dimensions;
+pub mod no_unknown_elements;
+pub mod no_xlink;
+pub mod require_view_box;
 
 use oxvg_diagnostics::SVGError;
 use rcdom::Node;
 
 pub trait Rule {
     fn execute(&self, element: &Node) -> Vec<SVGError>;
+
+    /// Mechanically fixes what the rule reports for the element, returning the number of fixes
+    /// applied. Rules which can't be fixed automatically leave the element as-is.
+    fn fix(&self, _element: &Node) -> usize {
+        0
+    }
 }
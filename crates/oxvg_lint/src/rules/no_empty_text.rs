@@ -0,0 +1,71 @@
+use super::Rule;
+use oxvg_diagnostics::SVGError;
+use rcdom::{Node, NodeData};
+use serde::{Deserialize, Serialize};
+
+/// Flags `<text>` and `<tspan>` elements without any content, as they render nothing
+#[derive(Serialize, Deserialize, Default)]
+pub struct NoEmptyText;
+
+impl Rule for NoEmptyText {
+    fn execute(&self, element: &Node) -> Vec<SVGError> {
+        if let Some(e) = self.empty_text(element) {
+            vec![e]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl NoEmptyText {
+    pub fn empty_text(&self, node: &Node) -> Option<SVGError> {
+        let NodeData::Element { name, .. } = &node.data else {
+            return None;
+        };
+        if name.prefix.is_some() || !matches!(name.local.as_ref(), "text" | "tspan") {
+            return None;
+        }
+
+        let is_empty = node
+            .children
+            .borrow()
+            .iter()
+            .all(|child| match &child.data {
+                NodeData::Text { contents } => contents.borrow().trim().is_empty(),
+                NodeData::Comment { .. } | NodeData::ProcessingInstruction { .. } => true,
+                _ => false,
+            });
+        if !is_empty {
+            return None;
+        }
+
+        Some(
+            SVGError::new(&format!("Found empty <{}> element", name.local), None)
+                .with_advice("Remove the element or give it some text"),
+        )
+    }
+}
+
+#[test]
+fn no_empty_text() {
+    use xml5ever::{
+        driver::{parse_document, XmlParseOpts},
+        tendril::TendrilSink,
+    };
+
+    let rule = NoEmptyText;
+    let is_empty = |source: &str| {
+        let dom: rcdom::RcDom =
+            parse_document(rcdom::RcDom::default(), XmlParseOpts::default()).one(source);
+        let root = &*dom.document.children.borrow()[0];
+        let text = &*root.children.borrow()[0];
+        rule.empty_text(text).is_some()
+    };
+
+    // Expect an error, as the text renders nothing
+    assert!(is_empty("<svg><text></text></svg>"));
+    assert!(is_empty("<svg><tspan> </tspan></svg>"));
+
+    assert!(!is_empty("<svg><text>Hi</text></svg>"));
+    assert!(!is_empty("<svg><text><tspan>Hi</tspan></text></svg>"));
+}
@@ -0,0 +1,83 @@
+use super::Rule;
+use oxvg_diagnostics::SVGError;
+use rcdom::{Node, NodeData};
+use serde::{Deserialize, Serialize};
+
+/// Attributes which are invalid when negative
+const DIMENSIONS: [&str; 5] = ["width", "height", "r", "rx", "ry"];
+
+/// Flags dimensions with negative values, which are an error and disable rendering of the
+/// element
+#[derive(Serialize, Deserialize, Default)]
+pub struct NoNegativeDimensions;
+
+impl Rule for NoNegativeDimensions {
+    fn execute(&self, element: &Node) -> Vec<SVGError> {
+        self.negative_dimensions(element)
+    }
+}
+
+impl NoNegativeDimensions {
+    pub fn negative_dimensions(&self, node: &Node) -> Vec<SVGError> {
+        let NodeData::Element { attrs, .. } = &node.data else {
+            return vec![];
+        };
+
+        attrs
+            .borrow()
+            .iter()
+            .filter(|attr| {
+                attr.name.prefix.is_none()
+                    && DIMENSIONS.contains(&attr.name.local.as_ref())
+                    && leading_number(&attr.value).is_some_and(|n| n < 0.0)
+            })
+            .map(|attr| {
+                SVGError::new(
+                    &format!(
+                        "Found negative {}, \"{}\"",
+                        attr.name.local,
+                        attr.value.trim()
+                    ),
+                    None,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Parses the number of a length or percentage, ignoring its unit
+fn leading_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let numeric = value
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E')))
+        .map_or(value, |end| &value[..end]);
+    // A unit such as `em` may begin like an exponent
+    (1..=numeric.len())
+        .rev()
+        .find_map(|end| numeric[..end].parse().ok())
+}
+
+#[test]
+fn no_negative_dimensions() {
+    use xml5ever::{
+        driver::{parse_document, XmlParseOpts},
+        tendril::TendrilSink,
+    };
+
+    let rule = NoNegativeDimensions;
+    let errors = |source: &str| {
+        let dom: rcdom::RcDom =
+            parse_document(rcdom::RcDom::default(), XmlParseOpts::default()).one(source);
+        let root = &*dom.document.children.borrow()[0];
+        rule.negative_dimensions(root).len()
+    };
+
+    // Expect an error for each negative dimension
+    assert_eq!(errors(r#"<rect width="-5"/>"#), 1);
+    assert_eq!(errors(r#"<rect width="-5%" height="-1e1px"/>"#), 2);
+    assert_eq!(errors(r#"<circle r="-5em"/>"#), 1);
+    assert_eq!(errors(r#"<ellipse rx="-.5" ry="2" x="-5"/>"#), 1);
+
+    assert_eq!(errors(r#"<rect width="5" height="0"/>"#), 0);
+    assert_eq!(errors(r#"<rect width="5em" height="1e-1"/>"#), 0);
+}
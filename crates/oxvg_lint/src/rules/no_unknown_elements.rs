@@ -0,0 +1,74 @@
+use super::Rule;
+use oxvg_collections::allowed_content::ELEMS;
+use oxvg_diagnostics::SVGError;
+use rcdom::{Node, NodeData};
+use serde::{Deserialize, Serialize};
+
+/// Flags SVG elements which aren't part of the known content model
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct NoUnknownElements {
+    /// Names of elements to ignore, such as custom elements
+    pub allow: Vec<String>,
+}
+
+impl Rule for NoUnknownElements {
+    fn execute(&self, element: &Node) -> Vec<SVGError> {
+        if let Some(e) = self.unknown_element(element) {
+            vec![e]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl NoUnknownElements {
+    pub fn unknown_element(&self, node: &Node) -> Option<SVGError> {
+        let NodeData::Element { name, .. } = &node.data else {
+            return None;
+        };
+        let local_name = name.local.as_ref();
+        if name.ns.as_ref() != "http://www.w3.org/2000/svg"
+            || ELEMS.contains_key(local_name)
+            || self.allow.iter().any(|allowed| allowed == local_name)
+        {
+            return None;
+        }
+
+        Some(
+            SVGError::new(&format!("Found unknown element <{local_name}>"), None)
+                .with_advice("Add the element to `allow` if it's intended"),
+        )
+    }
+}
+
+#[test]
+fn no_unknown_elements() {
+    use xml5ever::{
+        driver::{parse_document, XmlParseOpts},
+        tendril::TendrilSink,
+    };
+
+    let errors = |rule: &NoUnknownElements| {
+        let dom: rcdom::RcDom = parse_document(rcdom::RcDom::default(), XmlParseOpts::default())
+            .one(r#"<svg xmlns="http://www.w3.org/2000/svg"><rect/><my-icon/></svg>"#);
+        let root = &*dom.document.children.borrow()[0];
+        let mut errors = vec![rule.unknown_element(root).is_some()];
+        errors.extend(
+            root.children
+                .borrow()
+                .iter()
+                .map(|child| rule.unknown_element(child).is_some()),
+        );
+        errors
+    };
+
+    // Expect an error, as `my-icon` isn't an SVG element
+    let rule = NoUnknownElements::default();
+    assert_eq!(errors(&rule), vec![false, false, true]);
+
+    let rule = NoUnknownElements {
+        allow: vec!["my-icon".to_string()],
+    };
+    assert_eq!(errors(&rule), vec![false, false, false]);
+}
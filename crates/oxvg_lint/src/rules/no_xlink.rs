@@ -0,0 +1,95 @@
+use super::Rule;
+use markup5ever::{local_name, namespace_url, ns, Attribute, QualName};
+use oxvg_diagnostics::SVGError;
+use rcdom::{Node, NodeData};
+use serde::{Deserialize, Serialize};
+
+/// Flags `xlink:href`, which is deprecated in favour of `href`
+#[derive(Serialize, Deserialize, Default)]
+pub struct NoXlink;
+
+impl Rule for NoXlink {
+    fn execute(&self, element: &Node) -> Vec<SVGError> {
+        let NodeData::Element { attrs, .. } = &element.data else {
+            return vec![];
+        };
+        attrs
+            .borrow()
+            .iter()
+            .filter(|attr| is_xlink_href(attr))
+            .map(|_| {
+                SVGError::new("Found deprecated xlink:href attribute", None)
+                    .with_advice("Use href instead")
+            })
+            .collect()
+    }
+
+    fn fix(&self, element: &Node) -> usize {
+        let NodeData::Element { attrs, .. } = &element.data else {
+            return 0;
+        };
+        let attrs = &mut *attrs.borrow_mut();
+        let Some(index) = attrs.iter().position(is_xlink_href) else {
+            return 0;
+        };
+        let has_href = attrs
+            .iter()
+            .any(|attr| attr.name.ns == ns!() && attr.name.local == local_name!("href"));
+        if has_href {
+            // `href` takes precedence, so the legacy attribute has no effect
+            attrs.remove(index);
+        } else {
+            attrs[index].name = QualName::new(None, ns!(), local_name!("href"));
+        }
+        1
+    }
+}
+
+fn is_xlink_href(attr: &Attribute) -> bool {
+    attr.name.ns == ns!(xlink) && attr.name.local == local_name!("href")
+}
+
+#[test]
+fn no_xlink() {
+    use xml5ever::{
+        driver::{parse_document, XmlParseOpts},
+        tendril::TendrilSink,
+    };
+
+    let rule = NoXlink;
+    let dom: rcdom::RcDom = parse_document(rcdom::RcDom::default(), XmlParseOpts::default()).one(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+    <use xlink:href="#a"/>
+    <use href="#b" xlink:href="#a"/>
+</svg>"##,
+    );
+    let root = &*dom.document.children.borrow()[0];
+    let elements: Vec<_> = root
+        .children
+        .borrow()
+        .iter()
+        .filter(|child| matches!(child.data, NodeData::Element { .. }))
+        .cloned()
+        .collect();
+    let hrefs = |element: &Node| {
+        let NodeData::Element { attrs, .. } = &element.data else {
+            unreachable!();
+        };
+        attrs
+            .borrow()
+            .iter()
+            .map(|attr| format!("{:?}:{}={}", attr.name.prefix, attr.name.local, attr.value))
+            .collect::<Vec<_>>()
+    };
+
+    // Expect an error, then fix it by migrating to `href`
+    assert_eq!(rule.execute(&elements[0]).len(), 1);
+    assert_eq!(rule.fix(&elements[0]), 1);
+    assert_eq!(hrefs(&elements[0]), vec!["None:href=#a"]);
+    assert!(rule.execute(&elements[0]).is_empty());
+    assert_eq!(rule.fix(&elements[0]), 0);
+
+    // An existing `href` is kept
+    assert_eq!(rule.fix(&elements[1]), 1);
+    assert_eq!(hrefs(&elements[1]), vec!["None:href=#b"]);
+}
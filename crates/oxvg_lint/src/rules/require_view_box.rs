@@ -0,0 +1,92 @@
+use super::Rule;
+use oxvg_diagnostics::SVGError;
+use rcdom::{Node, NodeData};
+use serde::{Deserialize, Serialize};
+
+/// Flags a root `<svg>` without a `viewBox`, which prevents it from scaling responsively.
+///
+/// Nested `<svg>` elements are only flagged when they also lack a `width` and `height`.
+#[derive(Serialize, Deserialize, Default)]
+pub struct RequireViewBox;
+
+impl Rule for RequireViewBox {
+    fn execute(&self, element: &Node) -> Vec<SVGError> {
+        if let Some(e) = self.view_box(element) {
+            vec![e]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl RequireViewBox {
+    pub fn view_box(&self, node: &Node) -> Option<SVGError> {
+        let NodeData::Element { name, attrs, .. } = &node.data else {
+            return None;
+        };
+        if name.prefix.is_some() || name.local.as_ref() != "svg" {
+            return None;
+        }
+
+        let attrs = attrs.borrow();
+        let has_attr = |local_name: &str| {
+            attrs
+                .iter()
+                .any(|attr| attr.name.prefix.is_none() && attr.name.local.as_ref() == local_name)
+        };
+        if has_attr("viewBox") {
+            return None;
+        }
+        if !is_root(node) && (has_attr("width") || has_attr("height")) {
+            return None;
+        }
+
+        Some(
+            SVGError::new("Found <svg> element without a viewBox", None)
+                .with_advice("Add a viewBox so that the image can scale to its container"),
+        )
+    }
+}
+
+fn is_root(node: &Node) -> bool {
+    let parent = node.parent.take();
+    let is_root = parent
+        .as_ref()
+        .and_then(std::rc::Weak::upgrade)
+        .is_none_or(|parent| matches!(parent.data, NodeData::Document));
+    node.parent.set(parent);
+    is_root
+}
+
+#[test]
+fn require_view_box() {
+    use xml5ever::{
+        driver::{parse_document, XmlParseOpts},
+        tendril::TendrilSink,
+    };
+
+    let rule = RequireViewBox;
+    let errors = |source: &str| {
+        let dom: rcdom::RcDom =
+            parse_document(rcdom::RcDom::default(), XmlParseOpts::default()).one(source);
+        let root = &*dom.document.children.borrow()[0];
+        let mut errors = vec![rule.view_box(root).is_some()];
+        errors.extend(
+            root.children
+                .borrow()
+                .iter()
+                .map(|child| rule.view_box(child).is_some()),
+        );
+        errors
+    };
+
+    // Expect an error, as the root has no viewBox
+    assert_eq!(errors(r#"<svg width="10" height="10"></svg>"#), vec![true]);
+    assert_eq!(errors(r#"<svg viewBox="0 0 10 10"></svg>"#), vec![false]);
+
+    // Nested elements only need a viewBox without dimensions
+    assert_eq!(
+        errors(r#"<svg viewBox="0 0 10 10"><svg width="5" height="5"/><svg/></svg>"#),
+        vec![false, false, true]
+    );
+}
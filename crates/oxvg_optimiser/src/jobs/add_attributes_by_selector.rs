@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use oxvg_ast::{
+    attribute::{Attr, Attributes},
+    element::Element,
+    name::Name,
+    visitor::Visitor,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Adds attributes to each element matching a selector, such as `role="img"` to each `<svg>`.
+///
+/// Attributes an element already has are kept, unless `overwrite` is set.
+pub struct AddAttributesBySelector {
+    /// The attributes to add, keyed by the selector of elements to add them to
+    pub selectors: BTreeMap<String, BTreeMap<AttributeName, String>>,
+    /// Whether to replace the value of attributes which are already set
+    pub overwrite: Option<bool>,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// An attribute name, optionally with a prefix, such as `role` or `xlink:href`
+pub struct AttributeName(String);
+
+#[derive(Debug)]
+struct DeserializeAttributeNameError(String);
+
+impl std::fmt::Display for DeserializeAttributeNameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, r#"invalid attribute name "{}""#, self.0)
+    }
+}
+
+impl serde::de::StdError for DeserializeAttributeNameError {}
+
+impl<'de> Deserialize<'de> for AttributeName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        let mut parts = name.split(':');
+        let is_valid =
+            parts.by_ref().take(2).all(|part| !part.is_empty()) && parts.next().is_none();
+        if !is_valid {
+            return Err(serde::de::Error::custom(DeserializeAttributeNameError(
+                name,
+            )));
+        }
+        Ok(Self(name))
+    }
+}
+
+impl<E: Element> Visitor<E> for AddAttributesBySelector {
+    type Error = String;
+
+    fn document(&mut self, document: &mut E) -> Result<(), String> {
+        let overwrite = self.overwrite.unwrap_or(false);
+        for (selector, attributes) in &self.selectors {
+            let selected: Vec<_> = document
+                .select(selector)
+                .map_err(|e| format!(r#"invalid selector "{selector}": {e:?}"#))?
+                .collect();
+            for element in selected {
+                for (name, value) in attributes {
+                    let name =
+                        <<E::Attributes<'_> as Attributes<'_>>::Attribute as Attr>::Name::parse(
+                            &name.0,
+                        );
+                    if !overwrite && element.has_attribute(&name) {
+                        continue;
+                    }
+                    element.set_attribute(name, value.as_str().into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn add_attributes_by_selector() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "addAttributesBySelector": {
+            "selectors": {
+                "svg": { "role": "img" },
+                "[stroke]": { "vector-effect": "non-scaling-stroke" }
+            }
+        } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Add attributes to each matching element, including nested svgs -->
+    <path d="M0 0h10" stroke="red"/>
+    <path d="M0 0h10" fill="red"/>
+    <svg role="presentation">
+        <path d="M0 0h10" stroke="red" vector-effect="none"/>
+    </svg>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "addAttributesBySelector": {
+            "selectors": {
+                "svg": { "role": "img" },
+                "[stroke]": { "vector-effect": "non-scaling-stroke" }
+            },
+            "overwrite": true
+        } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Replace existing attributes when overwriting -->
+    <svg role="presentation">
+        <path d="M0 0h10" stroke="red" vector-effect="none"/>
+    </svg>
+</svg>"#
+        ),
+    )?);
+
+    assert!(test_config(
+        r#"{ "addAttributesBySelector": {
+            "selectors": { "svg >>> path": { "role": "img" } }
+        } }"#,
+        None,
+    )
+    .is_err());
+
+    for name in ["", ":role", "role:", "a:b:c"] {
+        assert!(test_config(
+            &format!(
+                r#"{{ "addAttributesBySelector": {{
+                    "selectors": {{ "svg": {{ "{name}": "img" }} }}
+                }} }}"#
+            ),
+            None,
+        )
+        .is_err());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use oxvg_ast::{
+    element::Element,
+    name::Name,
+    visitor::{Context, ContextFlags, PrepareOutcome, Visitor},
+};
+use serde::Deserialize;
+
+use super::remove_view_box::SEPARATOR;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+/// Adds `width` and `height` to the root `<svg>` from its `viewBox`, for consumers such as
+/// email clients which don't size an image without them.
+///
+/// Nothing is added when the `<svg>` already has either a `width` or `height`.
+pub struct AddDimensionsFromViewBox(bool);
+
+impl<E: Element> Visitor<E> for AddDimensionsFromViewBox {
+    type Error = String;
+
+    fn prepare(&mut self, _document: &E, _context_flags: &mut ContextFlags) -> PrepareOutcome {
+        if self.0 {
+            PrepareOutcome::none
+        } else {
+            PrepareOutcome::skip
+        }
+    }
+
+    fn element(&mut self, element: &mut E, _context: &mut Context<E>) -> Result<(), String> {
+        let name = element.qual_name();
+        if name.prefix().is_some() || name.local_name().as_ref() != "svg" {
+            return Ok(());
+        }
+        if element.closest_local(&"svg".into()).is_some() {
+            return Ok(());
+        }
+
+        let width_name = "width".into();
+        let height_name = "height".into();
+        if element.has_attribute_local(&width_name) || element.has_attribute_local(&height_name) {
+            log::debug!("not replacing existing dimensions");
+            return Ok(());
+        }
+        let Some(view_box) = element.get_attribute_local(&"viewBox".into()) else {
+            return Ok(());
+        };
+        let nums: Vec<_> = SEPARATOR.split(view_box.as_ref().trim()).collect();
+        let [_, _, width, height] = nums.as_slice() else {
+            log::debug!("invalid viewBox: {:?}", view_box.as_ref());
+            return Ok(());
+        };
+        let is_positive = |n: &str| n.parse::<f64>().is_ok_and(|n| n > 0.0);
+        if !is_positive(width) || !is_positive(height) {
+            log::debug!("viewBox has no area: {:?}", view_box.as_ref());
+            return Ok(());
+        }
+        let width = (*width).into();
+        let height = (*height).into();
+        drop(view_box);
+
+        log::debug!("adding dimensions from viewBox");
+        element.set_attribute_local(width_name, width);
+        element.set_attribute_local(height_name, height);
+        Ok(())
+    }
+}
+
+#[test]
+fn add_dimensions_from_view_box() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "addDimensionsFromViewBox": true }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24">
+    <!-- Should add width and height from the viewBox of the root svg -->
+    <svg viewBox="0 0 12 12"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "addDimensionsFromViewBox": true }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" width="48">
+    <!-- Should keep existing dimensions -->
+</svg>"#
+        ),
+    )?);
+
+    Ok(())
+}
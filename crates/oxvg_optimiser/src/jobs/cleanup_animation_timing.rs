@@ -0,0 +1,221 @@
+use oxvg_ast::{
+    element::Element,
+    name::Name,
+    visitor::{Context, Visitor},
+};
+use oxvg_collections::collections::{ElementGroup, Group};
+use serde::Deserialize;
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Minifies the `calcMode` and `keySplines` of animation elements.
+///
+/// - Control points of `keySplines` are rounded, and clamped to the valid range of `0` to `1`
+/// - `keySplines` which are all straight lines are replaced with `calcMode="linear"`
+/// - `keySplines` are removed when they're ignored by a `calcMode` other than `spline`
+/// - `calcMode` is removed when it's the default for the element
+pub struct CleanupAnimationTiming {
+    float_precision: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The interpolation of an animation, as specified by the `calcMode` attribute
+enum CalcMode {
+    Discrete,
+    Linear,
+    Paced,
+    Spline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The two control points of a cubic bezier easing, as listed in `keySplines`
+struct ControlPoint {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+}
+
+impl<E: Element> Visitor<E> for CleanupAnimationTiming {
+    type Error = String;
+
+    fn element(&mut self, element: &mut E, _context: &mut Context<E>) -> Result<(), Self::Error> {
+        let name = element.qual_name();
+        let local_name = name.local_name();
+        if name.prefix().is_some()
+            || !Group::set(&ElementGroup::Animation).contains(local_name.as_ref())
+        {
+            return Ok(());
+        }
+
+        let calc_mode_name = "calcMode".into();
+        let default_calc_mode = if local_name.as_ref() == "animateMotion" {
+            CalcMode::Paced
+        } else {
+            CalcMode::Linear
+        };
+        let calc_mode = match element.get_attribute_local(&calc_mode_name) {
+            Some(value) => match CalcMode::parse(value.as_ref()) {
+                Some(calc_mode) => calc_mode,
+                None => return Ok(()),
+            },
+            None => default_calc_mode,
+        };
+        let calc_mode = self.cleanup_key_splines(element, calc_mode);
+
+        if calc_mode == default_calc_mode {
+            element.remove_attribute_local(&calc_mode_name);
+        } else {
+            element.set_attribute_local(calc_mode_name, calc_mode.as_str().into());
+        }
+        Ok(())
+    }
+}
+
+impl CleanupAnimationTiming {
+    /// Updates the element's `keySplines`, returning the equivalent calc mode
+    fn cleanup_key_splines(&self, element: &impl Element, calc_mode: CalcMode) -> CalcMode {
+        let key_splines_name = "keySplines".into();
+        let Some(key_splines) = element.get_attribute_local(&key_splines_name) else {
+            return calc_mode;
+        };
+        if calc_mode != CalcMode::Spline {
+            log::debug!("removing keySplines ignored by calcMode");
+            drop(key_splines);
+            element.remove_attribute_local(&key_splines_name);
+            return calc_mode;
+        }
+
+        let control_points: Option<Vec<_>> = key_splines
+            .as_ref()
+            .split(';')
+            .map(str::trim)
+            .filter(|spline| !spline.is_empty())
+            .map(ControlPoint::parse)
+            .collect();
+        drop(key_splines);
+        let Some(mut control_points) = control_points else {
+            log::debug!("not updating invalid keySplines");
+            return calc_mode;
+        };
+
+        if control_points.iter().all(ControlPoint::is_linear) {
+            log::debug!("replacing linear keySplines with linear calcMode");
+            element.remove_attribute_local(&key_splines_name);
+            return CalcMode::Linear;
+        }
+
+        let precision = self.float_precision.unwrap_or(DEFAULT_FLOAT_PRECISION);
+        let value = control_points
+            .iter_mut()
+            .map(|control_point| {
+                control_point.clamp();
+                control_point.to_string(precision)
+            })
+            .collect::<Vec<_>>()
+            .join(";");
+        element.set_attribute_local(key_splines_name, value.into());
+        calc_mode
+    }
+}
+
+impl CalcMode {
+    fn parse(value: &str) -> Option<Self> {
+        Some(match value.trim() {
+            "discrete" => Self::Discrete,
+            "linear" => Self::Linear,
+            "paced" => Self::Paced,
+            "spline" => Self::Spline,
+            _ => return None,
+        })
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Discrete => "discrete",
+            Self::Linear => "linear",
+            Self::Paced => "paced",
+            Self::Spline => "spline",
+        }
+    }
+}
+
+impl ControlPoint {
+    /// Parses a control point from four numbers separated by commas and/or whitespace
+    fn parse(value: &str) -> Option<Self> {
+        let mut numbers = value
+            .split(|char: char| char == ',' || char.is_whitespace())
+            .filter(|number| !number.is_empty())
+            .map(str::parse::<f64>);
+        let mut next = || numbers.next()?.ok();
+        let control_point = Self {
+            x1: next()?,
+            y1: next()?,
+            x2: next()?,
+            y2: next()?,
+        };
+        if numbers.next().is_some() {
+            return None;
+        }
+        Some(control_point)
+    }
+
+    /// Whether the easing follows a straight line, the same as linear interpolation
+    fn is_linear(&self) -> bool {
+        self.x1 == self.y1 && self.x2 == self.y2
+    }
+
+    /// Moves each value into the range of `0` to `1`, as required for `keySplines`
+    fn clamp(&mut self) {
+        for value in [&mut self.x1, &mut self.y1, &mut self.x2, &mut self.y2] {
+            if !(0.0..=1.0).contains(value) {
+                log::warn!("clamping out of range keySplines control point {value}");
+                *value = value.clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    fn to_string(self, precision: usize) -> String {
+        [self.x1, self.y1, self.x2, self.y2]
+            .map(|value| {
+                let value = format!("{value:.precision$}");
+                let value = value.trim_end_matches('0').trim_end_matches('.');
+                match value.strip_prefix("0.") {
+                    Some(decimals) => format!(".{decimals}"),
+                    None => value.to_string(),
+                }
+            })
+            .join(" ")
+    }
+}
+
+static DEFAULT_FLOAT_PRECISION: usize = 3;
+
+#[test]
+fn cleanup_animation_timing() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "cleanupAnimationTiming": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- normalize keySplines -->
+    <animate attributeName="r" values="0;3;0" dur="1s" keyTimes="0;0.5;1" calcMode="spline" keySplines="0.41,0.3144,0.8,0.54; 0.2 0.0 0.4 1.0"/>
+    <!-- clamp out of range control points -->
+    <animate attributeName="r" values="0;3" dur="1s" keyTimes="0;1" calcMode="spline" keySplines="0.2 -0.5 1.5 1"/>
+    <!-- replace linear keySplines -->
+    <animate attributeName="r" values="0;3;0" dur="1s" keyTimes="0;0.5;1" calcMode="spline" keySplines="0 0 1 1;0.5 0.5 0.5 0.5"/>
+    <animateMotion path="M0 0h10" dur="1s" keyTimes="0;1" calcMode="spline" keySplines="0 0 1 1"/>
+    <!-- remove ignored keySplines and default calcMode -->
+    <animate attributeName="r" values="0;3" dur="1s" calcMode="discrete" keySplines="0 0 1 1"/>
+    <animate attributeName="r" values="0;3" dur="1s" calcMode="linear"/>
+    <animateMotion path="M0 0h10" dur="1s" calcMode="paced"/>
+    <!-- keep invalid values -->
+    <animate attributeName="r" values="0;3" dur="1s" calcMode="spline" keySplines="0 0 1"/>
+    <animate attributeName="r" values="0;3" dur="1s" calcMode="step-start"/>
+</svg>"#
+        ),
+    )?);
+
+    Ok(())
+}
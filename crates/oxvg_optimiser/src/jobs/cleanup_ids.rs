@@ -1,24 +1,18 @@
 use std::{
     cell::RefCell,
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
 };
 
 use derive_where::derive_where;
 use oxvg_ast::{
-    attribute::{Attr, Attributes},
+    attribute::Attr,
     element::Element,
-    name::Name,
     visitor::{Context, PrepareOutcome, Visitor},
 };
 use serde::Deserialize;
 
-use crate::utils::find_references;
-
 use super::ContextFlags;
 
-#[derive(Debug)]
-struct ReplaceCounter(String, usize);
-
 #[derive(Clone, Debug)]
 struct GeneratedId {
     pub current: String,
@@ -29,8 +23,7 @@ struct GeneratedId {
 #[derive_where(Clone)]
 struct RefRename<E: Element> {
     element_ref: E,
-    name: <E::Attr as Attr>::Name,
-    referenced_id: String,
+    referenced_ids: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, Clone, Default)]
@@ -77,30 +70,21 @@ impl<E: Element> Visitor<E> for CleanupIds<E> {
 
         let mut generated_id = self.generated_id.borrow_mut();
         // Find references in attributes
-        let mut ref_renames = self.ref_renames.borrow_mut();
-        for attr in element.attributes().into_iter() {
-            let name = attr.name();
-            let local_name = name.local_name();
-            let value = attr.value();
-            let Some(matches) = find_references(local_name.as_ref(), value.as_ref()) else {
-                continue;
-            };
-            matches
-                .filter_map(|item| item.get(1))
-                .map(|item| item.as_str())
-                .for_each(|item| {
-                    if self.replaceable_ids.contains(item) {
-                        log::debug!("CleanupIds::run: found potential reference: {item}");
-                        ref_renames.push(RefRename {
-                            element_ref: element.clone(),
-                            name: name.clone(),
-                            referenced_id: item.to_string(),
-                        });
-                    } else {
-                        log::debug!("CleanupIds::run: found unmatched reference: {item}");
-                        generated_id.insert_prevent_collision(item.to_string());
-                    }
-                });
+        let mut referenced_ids = vec![];
+        for item in element.find_references() {
+            if self.replaceable_ids.contains(&item) {
+                log::debug!("CleanupIds::run: found potential reference: {item}");
+                referenced_ids.push(item);
+            } else {
+                log::debug!("CleanupIds::run: found unmatched reference: {item}");
+                generated_id.insert_prevent_collision(item);
+            }
+        }
+        if !referenced_ids.is_empty() {
+            self.ref_renames.borrow_mut().push(RefRename {
+                element_ref: element.clone(),
+                referenced_ids,
+            });
         }
         Ok(())
     }
@@ -116,35 +100,28 @@ impl<E: Element> Visitor<E> for CleanupIds<E> {
         let mut generated_id = self.generated_id.borrow_mut();
         for RefRename {
             element_ref,
-            name,
-            referenced_id,
+            referenced_ids,
         } in self.ref_renames.borrow().iter()
         {
-            let element = element_ref;
-            let Some(ref mut attr) = element.get_attribute_node_mut(name) else {
-                log::debug!("CleanupIds::breakdown: {name:?} attribute missing");
-                continue;
-            };
-            let minified_id = used_ids
-                .get(referenced_id)
-                .unwrap_or(&generated_id.current)
-                .clone();
-            let replacements =
-                replace_id_in_attr(attr.value().to_string(), referenced_id, &minified_id);
-            if replacements.count() == &0 {
-                continue;
-            }
-            let is_new = used_ids
-                .insert(referenced_id.clone(), minified_id.clone())
-                .is_none();
-            if is_new {
-                generated_id.next();
+            let mut renames = HashMap::with_capacity(referenced_ids.len());
+            for referenced_id in referenced_ids {
+                let minified_id = used_ids
+                    .get(referenced_id)
+                    .unwrap_or(&generated_id.current)
+                    .clone();
+                let is_new = used_ids
+                    .insert(referenced_id.clone(), minified_id.clone())
+                    .is_none();
+                if is_new {
+                    generated_id.next();
+                }
+                log::debug!("CleanupIds::breakdown: updating reference: {referenced_id}");
+                renames.insert(referenced_id.clone(), minified_id);
             }
+            // Rename all of an element's references at once, so a new id can't be mistaken
+            // for another reference
             if self.options.minify.unwrap_or(MINIFY_DEFAULT) {
-                log::debug!(
-                    "CleanupIds::breakdown: updating reference: {name:?} <-> {referenced_id}"
-                );
-                attr.set_value(replacements.0.as_str().into());
+                element_ref.rename_references(&renames);
             }
         }
         log::debug!(
@@ -249,53 +226,6 @@ impl<'de, E: Element> Deserialize<'de> for CleanupIds<E> {
     }
 }
 
-fn replace_id_in_attr(attr: String, id: &str, new_id: &str) -> ReplaceCounter {
-    let has_hash = attr.contains('#');
-    let mut replacer = ReplaceCounter::new(attr);
-    if has_hash {
-        replacer = replacer
-            .replace(
-                &format!("#{}", urlencoding::encode(id)),
-                &format!("#{new_id}"),
-            )
-            .replace(&format!("#{id}"), &format!("#{new_id}"));
-    } else {
-        replacer = replacer.replace(&format!("{id}."), &format!("{new_id}."));
-    }
-    replacer
-}
-
-impl ReplaceCounter {
-    fn new(value: String) -> ReplaceCounter {
-        ReplaceCounter(value, 0)
-    }
-
-    /// An adaptation of `std::str::replace` with an additional counter
-    fn replace(&mut self, from: &str, to: &str) -> ReplaceCounter {
-        let string = &self.0;
-        let mut result = String::new();
-        let mut last_end = 0;
-        for (start, part) in string.match_indices(from) {
-            result.push_str(unsafe { string.get_unchecked(last_end..start) });
-            result.push_str(to);
-            last_end = start + part.len();
-            self.1 += 1;
-        }
-        result.push_str(unsafe { string.get_unchecked(last_end..string.len()) });
-        Self(result, self.1)
-    }
-
-    fn count(&self) -> &usize {
-        &self.1
-    }
-}
-
-impl From<&str> for ReplaceCounter {
-    fn from(value: &str) -> Self {
-        Self(value.to_string(), 0)
-    }
-}
-
 impl GeneratedId {
     fn set_prevent_collision(&mut self, ids: Vec<String>) {
         self.prevent_collision = ids.into_iter().collect();
@@ -84,5 +84,34 @@ fn cleanup_list_of_values() -> anyhow::Result<()> {
         )
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "cleanupListOfValues": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should pack points with the shortest separators, keeping pairs apart -->
+    <polyline points="0.0,0.0 10.00,10"/>
+    <polyline points="10-5-0.5,0.25 0.5.5 1e1,-2"/>
+    <polygon points="10 20, 30 -40.0004"/>
+    <!-- Should keep non-numeric points -->
+    <polygon points="10.0 20 30 none"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "cleanupListOfValues": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should cleanup dash arrays -->
+    <path d="M0 0h10" stroke-dasharray="4, 4"/>
+    <path d="M0 0h10" stroke-dasharray="0.50,1.250px 2.0"/>
+    <!-- Should keep repeated lengths, as an odd list is repeated to make an even one -->
+    <path d="M0 0h10" stroke-dasharray="4 4 4 4"/>
+    <path d="M0 0h10" stroke-dasharray="4"/>
+    <path d="M0 0h10" stroke-dasharray="none"/>
+</svg>"#
+        )
+    )?);
+
     Ok(())
 }
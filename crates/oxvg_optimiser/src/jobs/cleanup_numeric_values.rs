@@ -15,6 +15,16 @@ pub struct CleanupNumericValues {
     convert_to_px: Option<bool>,
 }
 
+impl CleanupNumericValues {
+    /// Returns lossy options which favour a smaller output over exact values
+    pub fn aggressive() -> Self {
+        Self {
+            float_precision: Some(2),
+            ..Self::default()
+        }
+    }
+}
+
 impl<E: Element> Visitor<E> for CleanupNumericValues {
     type Error = String;
 
@@ -76,5 +86,46 @@ fn cleanup_numeric_values() -> anyhow::Result<()> {
         )
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "cleanupNumericValues": { "leadingZero": true, "convertToPx": true } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should convert absolute units to px and remove leading zeros -->
+    <rect x="0.5" y="-0.5" width="1pc" height="1in" stroke-width="1pt"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "cleanupNumericValues": { "leadingZero": false, "convertToPx": false } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should keep units and leading zeros -->
+    <rect x="0.5" y="-0.5" width="1pc" height="1in" stroke-width="1pt"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "cleanupNumericValues": { "floatPrecision": 0 } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should apply exponents to the number -->
+    <rect x="1e2" y="1.5e-1" width="-0.2" height="100"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "cleanupNumericValues": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0.0 0.0 24.00 24">
+    <!-- Should round `viewBox`, keeping the spaces between numbers and leaving invalid units -->
+    <svg viewBox="-0.5, -0.5 0.5 0.5"/>
+    <svg viewBox="0 0 24px 24px"/>
+</svg>"#
+        )
+    )?);
+
     Ok(())
 }
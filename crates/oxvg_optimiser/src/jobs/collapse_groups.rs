@@ -9,9 +9,11 @@ use oxvg_ast::{
     name::Name,
     visitor::{Context, ContextFlags, PrepareOutcome, Visitor},
 };
-use oxvg_collections::collections::{ElementGroup, Group, INHERITABLE_ATTRS};
+use oxvg_collections::collections::{ElementGroup, INHERITABLE_ATTRS};
 use serde::Deserialize;
 
+use crate::utils::has_animated_attr;
+
 #[derive(Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CollapseGroups(bool);
@@ -91,7 +93,7 @@ fn move_attributes_to_child(element: &impl Element) {
         let value = attr.value();
 
         let child_attr = first_child_attrs.get_named_item_mut(&name);
-        if has_animated_attr(first_child, name) {
+        if has_animated_attr(first_child, local_name) {
             log::debug!("collapse_groups: canelled moves: has animated_attr");
             return;
         }
@@ -143,23 +145,6 @@ fn flatten_when_all_attributes_moved(element: &impl Element) {
     element.flatten();
 }
 
-fn has_animated_attr(element: &impl Element, name: &impl Name) -> bool {
-    let local_name = name.local_name();
-    let node_name: &str = local_name.as_ref();
-    for child in std::iter::once(element.clone()).chain(element.breadth_first()) {
-        let child_name_local = child.local_name();
-        let child_name: &str = child_name_local.as_ref();
-        if Group::set(&ElementGroup::Animation).contains(child_name)
-            && child
-                .get_attribute_local(&"attributeName".into())
-                .is_some_and(|attr| attr.as_ref() == node_name)
-        {
-            return true;
-        }
-    }
-    false
-}
-
 fn is_group_identifiable<E: Element>(node: &E, child: &E) -> bool {
     let class = &"class".into();
     child.has_attribute_local(&"id".into())
@@ -167,11 +152,12 @@ fn is_group_identifiable<E: Element>(node: &E, child: &E) -> bool {
 }
 
 fn is_position_visually_unstable<E: Element>(node: &E, child: &E) -> bool {
+    // A clip or mask moved onto a transformed child would be transformed along with it
     let is_node_clipping =
         node.has_attribute_local(&"clip-path".into()) || node.has_attribute_local(&"mask".into());
-    let is_child_transformed_group =
-        child.local_name().as_ref() == "g" && child.has_attribute_local(&"transform".into());
-    is_node_clipping || is_child_transformed_group
+    let is_child_transformed = child.has_attribute_local(&"transform".into());
+    let is_child_transformed_group = child.local_name().as_ref() == "g" && is_child_transformed;
+    (is_node_clipping && is_child_transformed) || is_child_transformed_group
 }
 
 fn is_node_with_filter(node: &impl Element) -> bool {
@@ -470,5 +456,27 @@ fn collapse_groups() -> anyhow::Result<()> {
         )
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "collapseGroups": true }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should move `clip-path` to a lone untransformed child, otherwise preserve the group -->
+    <clipPath id="c">
+        <path d="..."/>
+    </clipPath>
+    <g clip-path="url(#c)">
+        <path d="..."/>
+        <path d="..."/>
+    </g>
+    <g clip-path="url(#c)">
+        <path d="..."/>
+    </g>
+    <g mask="url(#c)" transform="scale(2)">
+        <path d="..."/>
+    </g>
+</svg>"#
+        )
+    )?);
+
     Ok(())
 }
@@ -7,17 +7,31 @@ use serde::Deserialize;
 use super::ContextFlags;
 
 #[derive(Deserialize, Clone)]
+#[serde(untagged)]
+/// Converts `<ellipse>` elements with equal radii to `<circle>` elements.
+///
+/// Either a `bool` to enable the job, or its options.
+pub enum ConvertEllipseToCircle {
+    Enabled(bool),
+    Options(Options),
+}
+
+#[derive(Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-pub struct ConvertEllipseToCircle(bool);
+pub struct Options {
+    /// The largest difference between `rx` and `ry` for an ellipse to be converted, using
+    /// the average of the two as `r`. By default they must be exactly equal.
+    tolerance: Option<f64>,
+}
 
 impl<E: Element> Visitor<E> for ConvertEllipseToCircle {
     type Error = String;
 
     fn prepare(&mut self, _document: &E, _context_flags: &mut ContextFlags) -> PrepareOutcome {
-        if self.0 {
-            PrepareOutcome::none
-        } else {
+        if matches!(self, Self::Enabled(false)) {
             PrepareOutcome::skip
+        } else {
+            PrepareOutcome::none
         }
     }
 
@@ -37,20 +51,44 @@ impl<E: Element> Visitor<E> for ConvertEllipseToCircle {
             .get_attribute_local(ry_name)
             .map_or(String::from("0"), |attr| attr.to_string());
 
-        if rx != ry && rx != "auto" && ry != "auto" {
+        let radius = if rx == ry || ry == "auto" {
+            rx
+        } else if rx == "auto" {
+            ry
+        } else if let Some(radius) = self.radius_within_tolerance(&rx, &ry) {
+            radius
+        } else {
             return Ok(());
-        }
-        let radius = if rx == "auto" { ry } else { rx };
+        };
         element.remove_attribute_local(rx_name);
         element.remove_attribute_local(ry_name);
         element.set_attribute_local("r".into(), radius.into());
+        element.set_local_name("circle".into());
         Ok(())
     }
 }
 
 impl Default for ConvertEllipseToCircle {
     fn default() -> Self {
-        Self(true)
+        Self::Enabled(true)
+    }
+}
+
+impl ConvertEllipseToCircle {
+    /// Returns the average of the radii when they're within the tolerance of each other
+    fn radius_within_tolerance(&self, rx: &str, ry: &str) -> Option<String> {
+        let Self::Options(Options {
+            tolerance: Some(tolerance),
+        }) = self
+        else {
+            return None;
+        };
+        let rx: f64 = rx.parse().ok()?;
+        let ry: f64 = ry.parse().ok()?;
+        if (rx - ry).abs() > *tolerance {
+            return None;
+        }
+        Some(f64::midpoint(rx, ry).to_string())
     }
 }
 
@@ -71,5 +109,28 @@ fn convert_ellipse_to_circle() -> anyhow::Result<()> {
         )
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertEllipseToCircle": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Don't convert near-circular ellipses by default -->
+    <ellipse rx="10" ry="10.0001"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertEllipseToCircle": { "tolerance": 0.001 } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Convert ellipses within the tolerance, using the average radius -->
+    <ellipse rx="10" ry="10.0001"/>
+    <ellipse rx="10" ry="10"/>
+    <ellipse rx="10" ry="10.01"/>
+    <ellipse rx="10px" ry="10.0001px"/>
+</svg>"#
+        )
+    )?);
+
     Ok(())
 }
@@ -0,0 +1,285 @@
+use std::collections::{HashMap, HashSet};
+
+use lightningcss::properties::PropertyId;
+use oxvg_ast::{
+    attribute::{Attr, Attributes},
+    element::Element,
+    style::{self, ComputedStyles, PresentationAttrId},
+    visitor::{Context, ContextFlags, PrepareOutcome, Visitor},
+};
+use oxvg_collections::regex::REFERENCES_URL;
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+/// Replaces `fill` and `stroke` references to a `<linearGradient>` or `<radialGradient>` with
+/// only one `<stop>` by the colour of that stop, removing the gradient when it's no longer
+/// referenced.
+///
+/// The stop's colour is resolved from its `stop-color` and `stop-opacity`, whether they're set
+/// by attributes, the `style` attribute, or the stylesheet. A gradient's shape and transform
+/// don't matter, since a single stop paints the same colour everywhere.
+pub struct ConvertOneStopGradients(bool);
+
+struct Replacement {
+    color: String,
+    opacity: Option<String>,
+}
+
+impl<E: Element> Visitor<E> for ConvertOneStopGradients {
+    type Error = String;
+
+    fn prepare(&mut self, _document: &E, _context_flags: &mut ContextFlags) -> PrepareOutcome {
+        if self.0 {
+            PrepareOutcome::use_style
+        } else {
+            PrepareOutcome::skip
+        }
+    }
+
+    fn exit_document(&mut self, document: &mut E, context: &Context<E>) -> Result<(), String> {
+        let gradients: HashMap<String, E> = document
+            .breadth_first()
+            .filter(|element| {
+                element.prefix().is_none()
+                    && matches!(
+                        element.local_name().as_ref(),
+                        "linearGradient" | "radialGradient"
+                    )
+            })
+            .filter_map(|element| {
+                let id = element.get_attribute_local(&"id".into())?.to_string();
+                Some((id, element))
+            })
+            .collect();
+
+        let replacements: HashMap<&str, Replacement> = gradients
+            .iter()
+            .filter_map(|(id, gradient)| {
+                let stops = get_stops(gradient, &gradients);
+                let [stop] = stops.as_slice() else {
+                    return None;
+                };
+                let replacement = get_replacement(stop, context)?;
+                Some((id.as_str(), replacement))
+            })
+            .collect();
+        if replacements.is_empty() {
+            return Ok(());
+        }
+
+        for element in document.breadth_first() {
+            for name in ["fill", "stroke"] {
+                let name = name.into();
+                let Some(value) = element.get_attribute_local(&name) else {
+                    continue;
+                };
+                let Some(replacement) = REFERENCES_URL
+                    .captures(value.as_ref().trim())
+                    .and_then(|captures| replacements.get(&captures[1]))
+                else {
+                    continue;
+                };
+                drop(value);
+                let opacity_name = format!("{name}-opacity").into();
+                if replacement.opacity.is_some() && element.has_attribute_local(&opacity_name) {
+                    log::debug!("not replacing {name} with existing opacity");
+                    continue;
+                }
+                log::debug!("replacing {name} with one stop gradient");
+                element.set_attribute_local(name, replacement.color.as_str().into());
+                if let Some(opacity) = &replacement.opacity {
+                    element.set_attribute_local(opacity_name, opacity.as_str().into());
+                }
+            }
+        }
+
+        let stylesheet = style::root(document);
+        let mut removable: HashSet<&str> = replacements
+            .keys()
+            .filter(|id| !stylesheet.contains(&format!("#{id}")))
+            .copied()
+            .collect();
+        loop {
+            let referenced = get_references(document, &gradients, &removable);
+            let count = removable.len();
+            removable.retain(|id| !referenced.contains(*id));
+            if removable.len() == count {
+                break;
+            }
+        }
+        for id in removable {
+            log::debug!("removing one stop gradient #{id}");
+            gradients[id].remove();
+        }
+        Ok(())
+    }
+}
+
+/// Returns the `<stop>` elements of the gradient, or of the gradient it inherits them from
+fn get_stops<E: Element>(gradient: &E, gradients: &HashMap<String, E>) -> Vec<E> {
+    let mut visited = HashSet::new();
+    let mut gradient = gradient.clone();
+    loop {
+        let stops: Vec<_> = gradient
+            .children()
+            .into_iter()
+            .filter(|child| child.prefix().is_none() && child.local_name().as_ref() == "stop")
+            .collect();
+        if !stops.is_empty() {
+            return stops;
+        }
+        let Some(href) = gradient
+            .attributes()
+            .into_iter()
+            .find(|attr| attr.local_name().as_ref() == "href")
+            .map(|attr| attr.value().to_string())
+        else {
+            return stops;
+        };
+        let Some(parent) = href.strip_prefix('#').and_then(|id| gradients.get(id)) else {
+            return stops;
+        };
+        if !visited.insert(parent.as_ptr_byte()) {
+            return stops;
+        }
+        gradient = parent.clone();
+    }
+}
+
+/// Returns the colour painted by the stop, or `None` if it may change
+fn get_replacement<E: Element>(stop: &E, context: &Context<E>) -> Option<Replacement> {
+    let computed_styles = context.computed_styles_of(stop);
+    let color = get_static_string(
+        &computed_styles,
+        &PropertyId::from("stop-color"),
+        &PresentationAttrId::StopColor,
+    )
+    .ok()?;
+    let opacity = get_static_string(
+        &computed_styles,
+        &PropertyId::from("stop-opacity"),
+        &PresentationAttrId::StopOpacity,
+    )
+    .ok()?;
+    Some(Replacement {
+        color: color.unwrap_or_else(|| String::from("black")),
+        opacity: opacity.filter(|opacity| opacity.parse::<f64>() != Ok(1.0)),
+    })
+}
+
+/// Returns the declared value of a property without a CSS equivalent, or `Err` if it's
+/// declared within an at-rule or pseudo-class
+fn get_static_string(
+    computed_styles: &ComputedStyles,
+    property_id: &PropertyId,
+    presentation_attr_id: &PresentationAttrId,
+) -> Result<Option<String>, ()> {
+    let style = computed_styles
+        .important_declarations
+        .get(property_id)
+        .map(|p| &p.1)
+        .or_else(|| computed_styles.inline_important.get(property_id))
+        .or_else(|| computed_styles.inline.get(property_id))
+        .or_else(|| computed_styles.declarations.get(property_id).map(|p| &p.1))
+        .or_else(|| computed_styles.attr.get(presentation_attr_id));
+    match style {
+        Some(style) if style.is_static() => Ok(style.to_css_string(false)),
+        Some(_) => Err(()),
+        None => Ok(None),
+    }
+}
+
+/// Returns the ids referenced by elements other than the gradients being removed
+fn get_references<E: Element>(
+    document: &E,
+    gradients: &HashMap<String, E>,
+    removable: &HashSet<&str>,
+) -> HashSet<String> {
+    let mut references = HashSet::new();
+    for element in document.breadth_first() {
+        let is_removable = element.get_attribute_local(&"id".into()).is_some_and(|id| {
+            removable.contains(id.as_ref()) && gradients.contains_key(id.as_ref())
+        });
+        if is_removable {
+            continue;
+        }
+        references.extend(element.find_references());
+    }
+    references
+}
+
+#[test]
+fn convert_one_stop_gradients() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertOneStopGradients": true }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should replace references to linear and radial gradients with one stop -->
+    <defs>
+        <linearGradient id="a">
+            <stop offset="0" stop-color="red"/>
+        </linearGradient>
+        <radialGradient id="b" gradientTransform="rotate(45)">
+            <stop offset="0" stop-color="blue" stop-opacity="0.5"/>
+        </radialGradient>
+        <linearGradient id="c">
+            <stop offset="0" stop-color="red"/>
+            <stop offset="1" stop-color="blue"/>
+        </linearGradient>
+    </defs>
+    <rect width="10" height="10" fill="url(#a)"/>
+    <rect width="10" height="10" fill="url(#b)" stroke="url(#a)"/>
+    <rect width="10" height="10" fill="url(#c)"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertOneStopGradients": true }"#,
+        Some(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+    <!-- Should resolve the stop colour from the stylesheet, and stops inherited by `href` -->
+    <style>
+        .stop { stop-color: green; }
+    </style>
+    <defs>
+        <radialGradient id="a" cx="0.5" cy="0.5" r="0.5" gradientTransform="scale(2)">
+            <stop class="stop" offset="0"/>
+        </radialGradient>
+        <radialGradient id="b" xlink:href="#a"/>
+    </defs>
+    <circle r="10" fill="url(#a)"/>
+    <circle r="10" fill="url(#b)"/>
+</svg>"##
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertOneStopGradients": true }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should keep gradients still referenced elsewhere -->
+    <style>
+        @media (prefers-color-scheme: dark) {
+            .stop { stop-color: white; }
+        }
+    </style>
+    <defs>
+        <linearGradient id="a">
+            <stop offset="0" stop-color="red"/>
+        </linearGradient>
+        <linearGradient id="b">
+            <stop class="stop" offset="0" stop-color="black"/>
+        </linearGradient>
+    </defs>
+    <rect width="10" height="10" fill="url(#a)" style="stroke: url(#a)"/>
+    <rect width="10" height="10" fill="url(#b)"/>
+</svg>"#
+        ),
+    )?);
+
+    Ok(())
+}
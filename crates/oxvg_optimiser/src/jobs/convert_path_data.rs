@@ -8,20 +8,58 @@ use serde_json::Value;
 
 #[derive(Deserialize, Default, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
+/// Optimises the `d` attribute of paths.
+///
+/// Each boolean option sets the [`convert::Flags`] of the same name, as documented there. Options
+/// which aren't specified use the value of [`convert::Flags::default`].
 pub struct ConvertPathData {
+    /// Sets [`convert::Flags::remove_useless_flag`]
     remove_useless: Option<bool>,
+    /// Sets [`convert::Flags::smart_arc_rounding_flag`]
     smart_arc_rounding: Option<bool>,
+    /// Sets [`convert::Flags::straight_curves_flag`]
     straight_curves: Option<bool>,
+    /// How far a curve may bend from a straight line for `straight_curves` to convert it to a
+    /// line. Defaults to the error of `float_precision`.
+    straight_curve_tolerance: Option<f64>,
+    /// Sets [`convert::Flags::convert_to_q_flag`]
     convert_to_q: Option<bool>,
+    /// Sets [`convert::Flags::line_shorthands_flag`]
     line_shorthands: Option<bool>,
+    /// Sets [`convert::Flags::collapse_repeated_flag`]
     collapse_repeated: Option<bool>,
+    /// Sets [`convert::Flags::curve_smooth_shorthands_flag`]
     curve_smooth_shorthands: Option<bool>,
+    /// Sets [`convert::Flags::convert_to_z_flag`]
     convert_to_z: Option<bool>,
+    /// Sets [`convert::Flags::force_absolute_path_flag`]
     force_absolute_path: Option<bool>,
+    /// Sets [`convert::Flags::negative_extra_space_flag`]
     negative_extra_space: Option<bool>,
     make_arcs: Option<MakeArcs>,
     float_precision: Option<Precision>,
+    /// Sets [`convert::Flags::utilize_absolute_flag`]
     utilize_absolute: Option<bool>,
+    /// Sets [`convert::Flags::implicit_lineto_flag`]
+    implicit_lineto: Option<bool>,
+    /// Whether to write commands with absolute or relative coordinates. Unless `auto`, this
+    /// overrides `force_absolute_path`, `utilize_absolute`, and `negative_extra_space`.
+    coordinate_style: Option<CoordinateStyle>,
+    /// Avoid optimisations which change the number of vertices in the path, for paths that are
+    /// morphed or have markers placed along them. This also disables `simplify_tolerance`.
+    ///
+    /// Sets [`convert::Flags::preserve_point_count_flag`]
+    preserve_point_count: Option<bool>,
+    /// Lossily drop points within this distance of the lines between their neighbours, using
+    /// [`Path::simplify`]. Disabled by default.
+    simplify_tolerance: Option<f64>,
+    /// Keep the original `d` attribute when the converted path is no shorter, so that the
+    /// author's formatting is retained.
+    preserve_if_equal_size: Option<bool>,
+    /// Set the `pathLength` of paths which have one to the length of the converted path, for
+    /// when the author's `pathLength` was the measured length of the original path. Otherwise
+    /// `pathLength` is kept as-is, since stroke dashing is scaled to it.
+    recalculate_path_length: Option<bool>,
     // TODO: Do we want to have apply_transforms as an option, or is it better to have this as a plugin
     // just *before* this one
     // apply_transforms: Option<bool>,
@@ -29,9 +67,54 @@ pub struct ConvertPathData {
     // transform_precision: Option<usize>,
 }
 
+#[derive(Deserialize, Clone, Default, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+/// Whether commands are written with absolute or relative coordinates
+pub enum CoordinateStyle {
+    /// Use whichever is shorter for each command
+    #[default]
+    Auto,
+    /// Use absolute coordinates for each command, such as `L` and `C`
+    Absolute,
+    /// Use relative coordinates for each command, such as `l` and `c`
+    Relative,
+}
+
+impl CoordinateStyle {
+    /// Sets the flags controlling whether absolute or relative coordinates are used
+    fn apply(self, flags: convert::Flags) -> convert::Flags {
+        match self {
+            Self::Auto => flags,
+            Self::Absolute => flags
+                .with_force_absolute_path(Some(true))
+                .with_utilize_absolute(Some(true))
+                .with_negative_extra_space(Some(false)),
+            Self::Relative => flags
+                .with_force_absolute_path(Some(false))
+                .with_utilize_absolute(Some(false))
+                .with_negative_extra_space(Some(false)),
+        }
+    }
+}
+
 #[derive(Clone, Default, Copy, Debug)]
 pub struct Precision(pub oxvg_path::convert::Precision);
 
+impl ConvertPathData {
+    /// Returns lossy options which favour a smaller output over an exact rendering
+    pub fn aggressive() -> Self {
+        Self {
+            make_arcs: Some(MakeArcs {
+                threshold: 4.0,
+                tolerance: 1.0,
+            }),
+            float_precision: Some(Precision(convert::Precision::Enabled(2))),
+            simplify_tolerance: Some(0.1),
+            ..Self::default()
+        }
+    }
+}
+
 impl<E: Element> Visitor<E> for ConvertPathData {
     type Error = String;
 
@@ -60,10 +143,23 @@ impl<E: Element> Visitor<E> for ConvertPathData {
                 return Ok(());
             }
         };
+        let original = self
+            .preserve_if_equal_size
+            .unwrap_or(false)
+            .then(|| d.as_ref().trim().to_string());
         drop(d);
         if path.0.is_empty() {
             return Ok(());
         }
+        let original_length = (self.recalculate_path_length.unwrap_or(false)
+            && element.has_attribute_local(&"pathLength".into()))
+        .then(|| self.path_length(&path));
+        let path = match self.simplify_tolerance {
+            Some(tolerance) if !self.preserve_point_count.unwrap_or(false) => {
+                path.simplify(tolerance)
+            }
+            _ => path,
+        };
 
         let path = convert::run(
             &path,
@@ -71,54 +167,72 @@ impl<E: Element> Visitor<E> for ConvertPathData {
                 flags: self.into(),
                 make_arcs: self.make_arcs.clone().unwrap_or_default(),
                 precision: self.float_precision.unwrap_or_default().0,
+                straight_curve_tolerance: self.straight_curve_tolerance,
+                ..convert::Options::default()
             },
             &style_info,
         );
 
-        element.set_attribute_local(d_localname, String::from(path).into());
+        let path = String::from(path);
+        if original.is_some_and(|original| original.len() <= path.len()) {
+            log::debug!("ConvertPathData::run: keeping original, conversion is no shorter");
+            return Ok(());
+        }
+        if let Some(original_length) = original_length {
+            self.set_path_length(element, original_length, &path);
+        }
+        element.set_attribute_local(d_localname, path.into());
         Ok(())
     }
 }
 
+impl ConvertPathData {
+    /// Replaces the element's `pathLength` with the length of `d`, when it differs from the
+    /// length of the original path.
+    fn set_path_length<E: Element>(&self, element: &E, original_length: f64, d: &str) {
+        let Ok(path) = Path::parse(d) else {
+            return;
+        };
+        let length = self.path_length(&path);
+        if (length - original_length).abs() < f64::EPSILON {
+            log::debug!("ConvertPathData::run: keeping pathLength, length is unchanged");
+            return;
+        }
+        log::debug!("ConvertPathData::run: updating pathLength to {length}");
+        element.set_attribute_local("pathLength".into(), length.to_string().into());
+    }
+
+    /// Returns the length of the path, rounded to the precision of the job.
+    fn path_length(&self, path: &Path) -> f64 {
+        let decimals = match self.float_precision.unwrap_or_default().0 {
+            convert::Precision::Enabled(decimals) => decimals,
+            convert::Precision::None | convert::Precision::Disabled => 3,
+        };
+        let scale = 10_f64.powi(decimals);
+        (path.length(0.01 / scale) * scale).round() / scale
+    }
+}
+
 impl From<&mut ConvertPathData> for convert::Flags {
+    /// Sets the flag of each boolean option which is specified, where each option maps to the
+    /// flag of the same name, such as `remove_useless` to `remove_useless_flag` using
+    /// [`convert::Flags::with_remove_useless`].
     fn from(val: &mut ConvertPathData) -> Self {
-        use convert::Flags;
-
-        let mut output = convert::Flags::default();
-        if let Some(f) = val.remove_useless {
-            output.set(Flags::remove_useless_flag, f);
-        }
-        if let Some(f) = val.smart_arc_rounding {
-            output.set(Flags::smart_arc_rounding_flag, f);
-        }
-        if let Some(f) = val.straight_curves {
-            output.set(Flags::straight_curves_flag, f);
-        }
-        if let Some(f) = val.convert_to_q {
-            output.set(Flags::convert_to_q_flag, f);
-        }
-        if let Some(f) = val.line_shorthands {
-            output.set(Flags::line_shorthands_flag, f);
-        }
-        if let Some(f) = val.collapse_repeated {
-            output.set(Flags::collapse_repeated_flag, f);
-        }
-        if let Some(f) = val.curve_smooth_shorthands {
-            output.set(Flags::curve_smooth_shorthands_flag, f);
-        }
-        if let Some(f) = val.convert_to_z {
-            output.set(Flags::convert_to_z_flag, f);
-        }
-        if let Some(f) = val.force_absolute_path {
-            output.set(Flags::force_absolute_path_flag, f);
-        }
-        if let Some(f) = val.negative_extra_space {
-            output.set(Flags::negative_extra_space_flag, f);
-        }
-        if let Some(f) = val.utilize_absolute {
-            output.set(Flags::utilize_absolute_flag, f);
-        }
-        output
+        let flags = convert::Flags::default()
+            .with_remove_useless(val.remove_useless)
+            .with_smart_arc_rounding(val.smart_arc_rounding)
+            .with_straight_curves(val.straight_curves)
+            .with_convert_to_q(val.convert_to_q)
+            .with_line_shorthands(val.line_shorthands)
+            .with_collapse_repeated(val.collapse_repeated)
+            .with_curve_smooth_shorthands(val.curve_smooth_shorthands)
+            .with_convert_to_z(val.convert_to_z)
+            .with_force_absolute_path(val.force_absolute_path)
+            .with_negative_extra_space(val.negative_extra_space)
+            .with_utilize_absolute(val.utilize_absolute)
+            .with_implicit_lineto(val.implicit_lineto)
+            .with_preserve_point_count(val.preserve_point_count);
+        val.coordinate_style.unwrap_or_default().apply(flags)
     }
 }
 
@@ -554,5 +668,211 @@ fn convert_path_data() -> anyhow::Result<()> {
         )
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": {} }"#,
+        Some(
+            r##"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Carry rounding error through `h`/`v` so small lines aren't all collapsed to `h0`/`v0` -->
+    <path d="M0 0h10.0004v10h.0004v10h.0004v10" stroke="#000" stroke-linecap="round"/>
+    <path d="M0 0v10.0004h10v.0004h10v.0004h10" stroke="#000" stroke-linecap="round"/>
+    <path d="M0 0h10.0004v10h.0004v10h.0004v10"/>
+</svg>"##
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Optimise evenodd fills like nonzero fills, as winding isn't affected -->
+    <path fill-rule="evenodd" d="M0 0Q10 -10 20 0Q10 10 0 0Z M5 0Q10 -3 15 0Q10 3 5 0Z"/>
+    <g fill-rule="evenodd">
+        <path d="M0 0Q10 -10 20 0Q10 10 0 0Z M5 0Q10 -3 15 0Q10 3 5 0Z"/>
+    </g>
+    <path d="M0 0Q10 -10 20 0Q10 10 0 0Z M5 0Q10 -3 15 0Q10 3 5 0Z"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Straighten near-collinear curves, but keep visible curves and overshoots -->
+    <path d="M0 0c3 .002 7-.002 10 0"/>
+    <path d="M0 0q5 .001 10 0"/>
+    <path d="M0 0c3 2 7 2 10 0"/>
+    <path d="M0 0c0 0 20 0 10 0"/>
+    <path d="M0 0q20 0 10 0"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": { "implicitLineto": false } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should use explicit lines after moves -->
+    <path d="M10 10L20 20L30 10"/>
+    <path d="M10 10L20 20M30 30L40 50"/>
+    <path d="m10 10l10 10l10-10"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": { "simplifyTolerance": 0.5 } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should lossily simplify dense paths -->
+    <path d="M0 0L10 .2L20 -.1L30 .3L40 0L40 10L40.2 20L40 30L39.9 40L0 40z"/>
+    <path d="M0 0C5 .1 15 -.1 20 0Q30 10 40 0"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": { "preservePointCount": true, "simplifyTolerance": 0.5 } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should keep the same number of vertices -->
+    <path d="M10 10h10h10v10v10l0 0z"/>
+    <path d="M7.234 19.474C6.562 19.811 5.803 20 5 20c-2.761 0-5-2.239-5-5 0-1.767.917-3.32 2.301-4.209"/>
+    <path d="M0 0L10 .2L20 -.1L30 .3L40 0"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": { "preserveIfEqualSize": true } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should keep the original when conversion offers no benefit -->
+    <path d="M10 10h5"/>
+    <path d="M10,10h5"/>
+    <!-- Should convert when smaller -->
+    <path d="M 10,10 L 15,10"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": { "straightCurveTolerance": 0.01 } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should keep slight curves with a tight tolerance -->
+    <path d="M0 0c5 .2 10 .2 15 0"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": { "straightCurveTolerance": 0.5 } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should flatten slight curves with a loose tolerance -->
+    <path d="M0 0c5 .2 10 .2 15 0"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should keep pathLength by default -->
+    <path d="M 0,0 L 10,0 L 10,10" pathLength="1"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": { "recalculatePathLength": true, "simplifyTolerance": 1 } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should update pathLength to the length of the converted path -->
+    <path d="M 0,0 L 10,0.5 L 20,0 L 20,10" pathLength="30.025"/>
+    <!-- Should keep pathLength when the length is unchanged -->
+    <path d="M 0,0 A 10,10 0 0 1 20,0" pathLength="100"/>
+    <!-- Should not add pathLength -->
+    <path d="M 0,0 L 10,0"/>
+</svg>"#
+        )
+    )?);
+
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <path d="M 10,10 L 20,20 C 20,30 30,40 40,40 L 100,100 Z M 100,100 l -90,-90"/>
+</svg>"#;
+    for coordinate_style in ["auto", "absolute", "relative"] {
+        insta::assert_snapshot!(test_config(
+            &format!(r#"{{ "convertPathData": {{ "coordinateStyle": "{coordinate_style}" }} }}"#),
+            Some(svg)
+        )?);
+    }
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertPathData": { "floatPrecision": 0 } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Carry rounding error of curves merged into arcs, so the path ends at its rounded end point -->
+    <path d="M.49 8.8c-.3-.75-.44-1.55-.44-2.35 0-3.54 2.88-6.43 6.43-6.43 3.53 0 6.42 2.88 6.42 6.43 0 .8-.15 1.6-.43 2.35"/>
+</svg>"#
+        )
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn convert_path_data_flags() -> anyhow::Result<()> {
+    use convert::Flags;
+
+    let options = [
+        ("removeUseless", Flags::remove_useless_flag),
+        ("smartArcRounding", Flags::smart_arc_rounding_flag),
+        ("straightCurves", Flags::straight_curves_flag),
+        ("convertToQ", Flags::convert_to_q_flag),
+        ("lineShorthands", Flags::line_shorthands_flag),
+        ("collapseRepeated", Flags::collapse_repeated_flag),
+        ("curveSmoothShorthands", Flags::curve_smooth_shorthands_flag),
+        ("convertToZ", Flags::convert_to_z_flag),
+        ("forceAbsolutePath", Flags::force_absolute_path_flag),
+        ("negativeExtraSpace", Flags::negative_extra_space_flag),
+        ("utilizeAbsolute", Flags::utilize_absolute_flag),
+        ("implicitLineto", Flags::implicit_lineto_flag),
+        ("preservePointCount", Flags::preserve_point_count_flag),
+    ];
+    let default = Flags::from(&mut ConvertPathData::default());
+    assert_eq!(default.bits(), Flags::default().bits());
+    for (option, flag) in options {
+        for enabled in [true, false] {
+            let mut job: ConvertPathData =
+                serde_json::from_str(&format!(r#"{{ "{option}": {enabled} }}"#))?;
+            let flags = Flags::from(&mut job);
+            let mut expected = Flags::default();
+            expected.set(Flags::from_bits_retain(flag.bits()), enabled);
+            assert_eq!(flags.bits(), expected.bits(), "{option}: {enabled}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn convert_path_data_idempotent() -> anyhow::Result<()> {
+    use crate::{jobs::Jobs, test_config};
+    use oxvg_ast::implementations::markup5ever::Element5Ever;
+
+    // Absolute coordinates should be based on where the rounded relative coordinates land,
+    // otherwise the next pass will choose differently
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <path d="M-30.69030 70.5039 l25.83118 -81.47482 v-19.80522"/>
+    <path d="M53 28 a10 5 30 1 1 -39.32845 -55.34339 H34.29569 h88.47124 l91.84611 -60.2791 T16.27270 -8.28442"/>
+    <path d="M21 49 S-3.53947 52.54925 48.9288 64.40859 C7.49904 19.18910 61.82027 -98.7310 -23.89955 57.85044 S-41.32029 -82.82109 -32.60314 -26.3966"/>
+</svg>"#;
+    let once = test_config(r#"{ "convertPathData": {} }"#, Some(svg))?;
+    let twice = test_config(r#"{ "convertPathData": {} }"#, Some(&once))?;
+    assert_eq!(once, twice);
+
+    assert!(Jobs::<Element5Ever>::default().is_idempotent(svg)?);
     Ok(())
 }
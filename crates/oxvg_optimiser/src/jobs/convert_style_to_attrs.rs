@@ -0,0 +1,160 @@
+use lightningcss::{
+    printer::PrinterOptions,
+    properties::Property,
+    stylesheet::{ParserOptions, StyleAttribute},
+    traits::ToCss,
+    vendor_prefix::VendorPrefix,
+};
+use oxvg_ast::{
+    element::Element,
+    visitor::{Context, ContextFlags, PrepareOutcome, Visitor},
+};
+use oxvg_collections::collections::PRESENTATION;
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+/// Splits declarations of `style` attributes into presentation attributes, keeping any
+/// properties which can't be written as an attribute in a shorter `style`.
+///
+/// Documents with a stylesheet are skipped, since presentation attributes have a lower
+/// precedence than its rules where the `style` attribute has a higher one.
+pub struct ConvertStyleToAttrs {
+    /// Whether to keep `!important` declarations in the `style` attribute, instead of converting
+    /// them to presentation attributes.
+    keep_important: Option<bool>,
+}
+
+impl<E: Element> Visitor<E> for ConvertStyleToAttrs {
+    type Error = String;
+
+    fn prepare(&mut self, document: &E, context_flags: &mut ContextFlags) -> PrepareOutcome {
+        context_flags.query_has_stylesheet(document);
+        if context_flags.contains(ContextFlags::has_stylesheet) {
+            PrepareOutcome::skip
+        } else {
+            PrepareOutcome::none
+        }
+    }
+
+    fn element(&mut self, element: &mut E, context: &mut Context<E>) -> Result<(), String> {
+        if context.flags.contains(ContextFlags::within_foreign_object) {
+            return Ok(());
+        }
+        let style_name = "style".into();
+        let Some(style) = element
+            .get_attribute_local(&style_name)
+            .map(|style| style.to_string())
+        else {
+            return Ok(());
+        };
+        let mut css = match StyleAttribute::parse(&style, ParserOptions::default()) {
+            Ok(css) => css,
+            Err(error) => {
+                log::debug!("failed to parse style: {error}");
+                return Ok(());
+            }
+        };
+
+        let mut attrs = vec![];
+        let mut take_attrs = |declarations: &mut Vec<Property>| {
+            declarations.retain(|property| match as_attr(property) {
+                Some(attr) => {
+                    attrs.push(attr);
+                    false
+                }
+                None => true,
+            });
+        };
+        take_attrs(&mut css.declarations.declarations);
+        if !self.keep_important.unwrap_or(DEFAULT_KEEP_IMPORTANT) {
+            take_attrs(&mut css.declarations.important_declarations);
+        }
+        if attrs.is_empty() {
+            return Ok(());
+        }
+
+        let style = css
+            .declarations
+            .to_css_string(PrinterOptions {
+                minify: true,
+                ..PrinterOptions::default()
+            })
+            .map_err(|error| error.to_string())?;
+        if style.is_empty() {
+            element.remove_attribute_local(&style_name);
+        } else {
+            element.set_attribute_local(style_name, style.into());
+        }
+        for (name, value) in attrs {
+            log::debug!("converted {name} to attribute");
+            element.set_attribute_local(name.into(), value.into());
+        }
+        Ok(())
+    }
+}
+
+/// Returns the name and value of the presentation attribute equivalent to the property
+fn as_attr(property: &Property) -> Option<(String, String)> {
+    if matches!(property, Property::Unparsed(_) | Property::Custom(_)) {
+        // Values such as `var(--color)` can't be used in attributes
+        return None;
+    }
+    let id = property.property_id();
+    let prefix = id.prefix();
+    if !prefix.is_empty() && prefix != VendorPrefix::None {
+        return None;
+    }
+    let name = id.name();
+    // The CSS syntax of transforms differs from the attribute, such as requiring units
+    if !PRESENTATION.contains(name) || name == "transform" || name == "transform-origin" {
+        return None;
+    }
+    let value = property
+        .value_to_css_string(PrinterOptions::default())
+        .ok()?;
+    Some((name.to_string(), value))
+}
+
+const DEFAULT_KEEP_IMPORTANT: bool = false;
+
+#[test]
+fn convert_style_to_attrs() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertStyleToAttrs": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- split presentation properties, keeping others in the style -->
+    <rect width="10" height="10" style="fill:red;mix-blend-mode:multiply"/>
+    <rect width="10" height="10" fill="blue" style="fill: red; stroke-width: 2px"/>
+    <rect width="10" height="10" style="stroke:red !important;transform:rotate(45deg);fill:var(--color)"/>
+    <rect width="10" height="10" style="mix-blend-mode:multiply"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertStyleToAttrs": { "keepImportant": true } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- keep important declarations -->
+    <rect width="10" height="10" style="stroke:red !important;fill:blue"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertStyleToAttrs": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- skip documents with stylesheets -->
+    <style>rect { fill: blue }</style>
+    <rect width="10" height="10" style="fill:red"/>
+</svg>"#
+        ),
+    )?);
+
+    Ok(())
+}
@@ -1,14 +1,18 @@
 use lightningcss::{
     printer::PrinterOptions,
-    properties::transform::{Matrix, TransformList},
+    properties::{
+        transform::{Matrix, TransformList},
+        PropertyId,
+    },
     traits::ToCss,
+    vendor_prefix::VendorPrefix,
 };
 use oxvg_ast::{
     attribute::{Attr, Attributes},
     element::Element,
     style::{
-        Id, Precision, PresentationAttr, PresentationAttrId, SVGTransform, SVGTransformList,
-        Static, Style,
+        ComputedStyles, Id, Precision, PresentationAttr, PresentationAttrId, SVGTransform,
+        SVGTransformList, Static, Style,
     },
     visitor::{Context, ContextFlags, PrepareOutcome, Visitor},
 };
@@ -62,7 +66,18 @@ impl<E: Element> Visitor<E> for ConvertTransform {
             .attr
             .get(&PresentationAttrId::Transform)
         {
-            self.transform_attr(transform, "transform", element);
+            let folded = if self.inner().collapse_into_one {
+                fold_transform_origin(transform, element, &context.computed_styles)
+            } else {
+                None
+            };
+            if let Some(folded) = folded {
+                log::debug!("folding transform-origin into transform");
+                element.remove_attribute_local(&"transform-origin".into());
+                self.transform_attr(&folded, "transform", element);
+            } else {
+                self.transform_attr(transform, "transform", element);
+            }
         }
 
         if let Some(gradient_transform) = context
@@ -94,14 +109,17 @@ impl ConvertTransform {
             matrix_to_transform: self.matrix_to_transform.unwrap_or(true),
             short_rotate: self.short_rotate.unwrap_or(true),
             remove_useless: self.remove_useless.unwrap_or(true),
-            collapse_into_one: self.collapse_into_one.unwrap_or(true),
+            // NOTE: A collapsed matrix can't be converted back to its original transforms
+            collapse_into_one: self
+                .collapse_into_one
+                .unwrap_or(self.matrix_to_transform != Some(false)),
         }
     }
 
     fn transform_attr(&self, value: &Style, name: &str, element: &impl Element) {
         log::debug!("transform_attr: found {name} to transform");
         if value.is_unparsed() {
-            element.remove_attribute_local(&name.into());
+            log::debug!("transform_attr: keeping unparsed {name}");
             return;
         }
         if let Some(transform) = self.transform(value) {
@@ -164,6 +182,43 @@ impl ConvertTransform {
     }
 }
 
+/// Returns the transform about the element's `transform-origin`, or `None` if the origin
+/// isn't an absolute position set by the attribute alone.
+///
+/// Percentages and keywords are relative to the element's bounding box, so they're left as is.
+fn fold_transform_origin<'i>(
+    transform: &Style<'i>,
+    element: &impl Element,
+    computed_styles: &ComputedStyles,
+) -> Option<Style<'i>> {
+    let Style::Static(Static::Attr(PresentationAttr::Transform(transform))) = transform else {
+        return None;
+    };
+    let id = PropertyId::TransformOrigin(VendorPrefix::None);
+    if computed_styles.declarations.contains_key(&id)
+        || computed_styles.important_declarations.contains_key(&id)
+        || computed_styles.inline.contains_key(&id)
+        || computed_styles.inline_important.contains_key(&id)
+    {
+        log::debug!("not folding transform-origin overridden by style");
+        return None;
+    }
+    let origin = element.get_attribute_local(&"transform-origin".into())?;
+    let parse = |n: &str| n.strip_suffix("px").unwrap_or(n).parse::<f32>().ok();
+    let origin: Option<Vec<_>> = origin.as_ref().split_whitespace().map(parse).collect();
+    let [x, y] = origin?[..] else {
+        return None;
+    };
+
+    let mut list = Vec::with_capacity(transform.0.len() + 2);
+    list.push(SVGTransform::Translate(x, y));
+    list.extend(transform.0.iter().cloned());
+    list.push(SVGTransform::Translate(-x, -y));
+    Some(Style::Static(Static::Attr(PresentationAttr::Transform(
+        SVGTransformList(list),
+    ))))
+}
+
 impl Inner {
     fn define_precision(mut self, data: &SVGTransformList, outer: &ConvertTransform) -> Self {
         let matrix_data = data
@@ -485,5 +540,48 @@ fn convert_transform() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertTransform": { "matrixToTransform": false } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+    <!-- keep transforms decomposed when matrices can't be converted back -->
+    <rect width="10" height="20" transform="translate(10 10) rotate(45)"/>
+    <rect width="10" height="20" transform="translate(10.00001 10) rotate(45.123456)"/>
+    <rect width="10" height="20" transform="matrix(1 0 0 1 10 10)"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertTransform": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+    <!-- decompose pure rotations and scales -->
+    <rect width="10" height="20" transform="matrix(0.7071068 0.7071068 -0.7071068 0.7071068 0 0)"/>
+    <rect width="10" height="20" transform="matrix(0 -1 1 0 0 0)"/>
+    <rect width="10" height="20" transform="matrix(2 0 0 3 0 0)"/>
+    <rect width="10" height="20" transform="matrix(-1 0 0 1 0 0)"/>
+    <!-- keep matrices which are shorter than their decomposition -->
+    <rect width="10" height="20" transform="matrix(1.5 0 0 1.5 10 20)"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "convertTransform": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+    <!-- fold absolute transform origins into the transform -->
+    <rect width="20" height="20" transform="rotate(45)" transform-origin="10 10"/>
+    <rect width="20" height="20" transform="scale(2)" transform-origin="10px 5px"/>
+    <!-- keep origins relative to the bounding box, invalid, or overridden by style -->
+    <rect width="20" height="20" transform="scale(2)" transform-origin="10px, 5px"/>
+    <rect width="20" height="20" transform="rotate(45)" transform-origin="50% 50%"/>
+    <rect width="20" height="20" transform="rotate(45)" transform-origin="center"/>
+    <rect width="20" height="20" transform="rotate(45)" transform-origin="10 10" style="transform-origin: 0 0"/>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
@@ -0,0 +1,165 @@
+use oxvg_ast::{
+    attribute::{Attr, Attributes},
+    element::Element,
+    visitor::{Context, Visitor},
+};
+use oxvg_collections::collections::{AttrsGroups, Group};
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+/// Replaces each `<switch>` with the child it would render in the given environment.
+///
+/// A `<switch>` renders its first direct child whose conditional processing attributes
+/// (`requiredExtensions`, `requiredFeatures`, and `systemLanguage`) all evaluate to true. The
+/// other children are removed, and the `<switch>` is unwrapped, or replaced with a `<g>` when it
+/// has attributes of its own. A `<switch>` where no child evaluates to true is removed.
+///
+/// # Correctness
+///
+/// The rendering is only preserved for viewers matching the environment, so the document will
+/// no longer adapt to the user's language or the viewer's capabilities.
+pub struct EvaluateSwitch {
+    /// The user's languages, such as `["en-US", "fr"]`. Defaults to `["en"]`
+    languages: Option<Vec<String>>,
+    /// The features supported by the viewer, such as
+    /// `["http://www.w3.org/TR/SVG11/feature#Shape"]`. When not specified, `requiredFeatures` is
+    /// ignored, as it is in SVG 2
+    features: Option<Vec<String>>,
+    /// The extensions supported by the viewer, such as `["http://www.w3.org/1999/xhtml"]`.
+    /// Defaults to no extensions
+    extensions: Option<Vec<String>>,
+}
+
+impl<E: Element> Visitor<E> for EvaluateSwitch {
+    type Error = String;
+
+    fn exit_element(&mut self, element: &mut E, _context: &Context<E>) -> Result<(), String> {
+        if element.prefix().is_some() || element.local_name().as_ref() != "switch" {
+            return Ok(());
+        }
+
+        let mut winner = None;
+        for child in element.children() {
+            if winner.is_none() && self.evaluate(&child) {
+                remove_conditional_processing(&child);
+                winner = Some(child);
+            } else {
+                child.remove();
+            }
+        }
+        if winner.is_none() {
+            log::debug!("removing switch with no matching child");
+            element.remove();
+        } else if element.attributes().len() == 0 {
+            log::debug!("unwrapping switch");
+            element.flatten();
+        } else {
+            log::debug!("replacing switch with group");
+            element.set_local_name("g".into());
+        }
+        Ok(())
+    }
+}
+
+impl EvaluateSwitch {
+    /// Returns whether each conditional processing attribute of the element evaluates to true
+    fn evaluate<E: Element>(&self, element: &E) -> bool {
+        element.attributes().into_iter().all(|attr| {
+            if attr.prefix().is_some() {
+                return true;
+            }
+            let value = attr.value().as_ref();
+            match attr.local_name().as_ref() {
+                "requiredExtensions" => self.has_extensions(value),
+                "requiredFeatures" => self.has_features(value),
+                "systemLanguage" => self.has_language(value),
+                _ => true,
+            }
+        })
+    }
+
+    fn has_extensions(&self, value: &str) -> bool {
+        let extensions = self.extensions.as_deref().unwrap_or_default();
+        let mut required = value.split_whitespace().peekable();
+        required.peek().is_some()
+            && required.all(|extension| extensions.iter().any(|e| e == extension))
+    }
+
+    fn has_features(&self, value: &str) -> bool {
+        let Some(features) = self.features.as_deref() else {
+            return true;
+        };
+        let mut required = value.split_whitespace().peekable();
+        required.peek().is_some() && required.all(|feature| features.iter().any(|f| f == feature))
+    }
+
+    /// Returns whether any of the user's languages equals one of the languages of the value, or
+    /// a prefix of it followed by `-`
+    fn has_language(&self, value: &str) -> bool {
+        let default_languages = [String::from("en")];
+        let languages = self.languages.as_deref().unwrap_or(&default_languages);
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .any(|tag| {
+                languages.iter().any(|language| {
+                    tag.get(..language.len())
+                        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(language))
+                        && matches!(tag.as_bytes().get(language.len()), None | Some(b'-'))
+                })
+            })
+    }
+}
+
+fn remove_conditional_processing<E: Element>(element: &E) {
+    element.attributes().retain(|attr| {
+        let name = attr.local_name();
+        attr.prefix().is_some() || !AttrsGroups::ConditionalProcessing.matches(name.as_ref())
+    });
+}
+
+#[test]
+fn evaluate_switch() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <switch>
+        <text systemLanguage="fr">Bonjour</text>
+        <text systemLanguage="de, en-US">Hello</text>
+        <text>Fallback</text>
+    </switch>
+</svg>"#;
+    insta::assert_snapshot!(test_config(r#"{ "evaluateSwitch": {} }"#, Some(svg))?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "evaluateSwitch": { "languages": ["fr"] } }"#,
+        Some(svg)
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "evaluateSwitch": { "languages": ["ja"] } }"#,
+        Some(svg)
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "evaluateSwitch": { "features": ["http://www.w3.org/TR/SVG11/feature#Shape"] } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- switch with attributes becomes a group -->
+    <switch fill="red">
+        <foreignObject requiredExtensions="http://www.w3.org/1999/xhtml" width="10" height="10"/>
+        <rect requiredFeatures="http://www.w3.org/TR/SVG11/feature#Text" width="10" height="10"/>
+        <rect requiredFeatures="http://www.w3.org/TR/SVG11/feature#Shape" width="20" height="20"/>
+    </switch>
+    <!-- switch without a matching child is removed -->
+    <switch>
+        <rect systemLanguage="" width="10" height="10"/>
+    </switch>
+</svg>"#
+        ),
+    )?);
+
+    Ok(())
+}
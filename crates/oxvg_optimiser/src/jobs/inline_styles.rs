@@ -26,6 +26,8 @@ struct RemovedToken<E: Element> {
     element: E,
     token: Vec<E::Atom>,
     specificity: u32,
+    /// The position of the rule in the stylesheets, for ordering rules of equal specificity
+    order: usize,
     declarations: String,
 }
 
@@ -35,6 +37,8 @@ struct RemovedTokens<E: Element> {
     classes: Vec<RemovedToken<E>>,
     ids: Vec<RemovedToken<E>>,
     other: Vec<RemovedToken<E>>,
+    /// The order of the next rule to be removed
+    next_order: usize,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -60,6 +64,11 @@ pub struct Options {
     /// classes and non-pseudo elements.
     /// Using `["*"]` will match all pseudo-elements
     pub use_pseudos: Option<Vec<String>>,
+    /// The environment to evaluate media queries against. Rules within media queries which match
+    /// it are inlined, and media queries which don't match it are removed.
+    ///
+    /// Media queries which can't be evaluated against it are handled by `use_mqs`.
+    pub media: Option<MediaContext>,
     #[serde(skip_deserializing)]
     /// After running, a record of matching tokens in a selector that are an ancestor of a matching
     /// element.
@@ -67,6 +76,20 @@ pub struct Options {
     pub parent_tokens: RefCell<ParentTokens>,
 }
 
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+/// The environment a document will be rendered in, for evaluating media queries
+pub struct MediaContext {
+    /// The media type, such as `screen` or `print`. Defaults to `screen`.
+    pub media_type: Option<String>,
+    /// The width of the viewport, in pixels
+    pub width: Option<f32>,
+    /// The height of the viewport, in pixels
+    pub height: Option<f32>,
+    /// The preferred color scheme, such as `light` or `dark`
+    pub prefers_color_scheme: Option<String>,
+}
+
 #[derive(Clone)]
 #[derive_where(Default)]
 pub struct InlineStyles<E: Element> {
@@ -150,8 +173,10 @@ impl<E: Element> Visitor<E> for InlineStyles<E> {
         };
         let removed_styles = flatten_media(removed_styles);
 
-        let new_removed_tokens = self.gather_removed_tokens(&removed_styles, context);
+        let next_order = self.removed_tokens.borrow().next_order;
+        let new_removed_tokens = self.gather_removed_tokens(&removed_styles, context, next_order);
         let mut removed_tokens = self.removed_tokens.borrow_mut();
+        removed_tokens.next_order = new_removed_tokens.next_order;
         removed_tokens.classes.extend(new_removed_tokens.classes);
         removed_tokens.ids.extend(new_removed_tokens.ids);
         removed_tokens.other.extend(new_removed_tokens.other);
@@ -205,7 +230,7 @@ impl<E: Element> Visitor<E> for InlineStyles<E> {
             .iter()
             .chain(removed_tokens.classes.iter())
             .chain(removed_tokens.other.iter())
-            .sorted_by(|a, b| a.specificity.cmp(&b.specificity))
+            .sorted_by(|a, b| (a.specificity, a.order).cmp(&(b.specificity, b.order)))
             .sorted_by(|a, b| a.element.as_ptr_byte().cmp(&b.element.as_ptr_byte()))
             .chunk_by(|r| r.element.as_ptr_byte());
 
@@ -281,56 +306,66 @@ impl<E: Element> InlineStyles<E> {
         &self,
         styles: &rules::CssRuleList,
         context: &Context<E>,
+        first_order: usize,
     ) -> RemovedTokens<E> {
         let mut removed_classes = vec![];
         let mut removed_ids = vec![];
         let mut removed_others = vec![];
 
-        styles.0.iter().for_each(|rule| match rule {
-            rules::CssRule::Media(media_rule) => {
-                self.gather_removed_tokens(&media_rule.rules, context);
-            }
-            rules::CssRule::Style(style_rule) => {
-                let mut selector = format!("{}", style_rule.selectors);
-                selector = self.options.strip_allowed_pseudos(selector);
-                let declarations = match style_rule
-                    .declarations
-                    .to_css_string(printer::PrinterOptions::default())
-                {
-                    Ok(d) => d,
-                    Err(e) => {
-                        log::debug!("couldn't move unparseable declarations: {e:?}");
-                        return;
+        styles
+            .0
+            .iter()
+            .enumerate()
+            .for_each(|(index, rule)| match rule {
+                rules::CssRule::Media(media_rule) => {
+                    self.gather_removed_tokens(&media_rule.rules, context, first_order + index);
+                }
+                rules::CssRule::Style(style_rule) => {
+                    let mut selector = format!("{}", style_rule.selectors);
+                    selector = self.options.strip_allowed_pseudos(selector);
+                    let declarations = match style_rule
+                        .declarations
+                        .to_css_string(printer::PrinterOptions::default())
+                    {
+                        Ok(d) => d,
+                        Err(e) => {
+                            log::debug!("couldn't move unparseable declarations: {e:?}");
+                            return;
+                        }
+                    };
+                    let selected: Vec<_> = match context.root.select(selector.as_str()) {
+                        Ok(i) => i,
+                        Err(e) => {
+                            log::debug!(r#"couldn't move invalid selector "{selector}": {e:?}"#);
+                            return;
+                        }
                     }
-                };
-                let selected: Vec<_> = match context.root.select(selector.as_str()) {
-                    Ok(i) => i,
-                    Err(e) => {
-                        log::debug!(r#"couldn't move invalid selector "{selector}": {e:?}"#);
-                        return;
+                    .collect();
+                    let order = first_order + index;
+                    if let Some(r) = Self::find_removed_classes(
+                        selected.iter(),
+                        style_rule,
+                        &declarations,
+                        order,
+                    ) {
+                        removed_classes.extend(r);
+                    } else if let Some(r) =
+                        Self::find_removed_ids(selected.iter(), style_rule, &declarations, order)
+                    {
+                        removed_ids.extend(r);
+                    } else if let Some(r) =
+                        Self::find_removed_others(selected.iter(), style_rule, &declarations, order)
+                    {
+                        removed_others.extend(r);
                     }
                 }
-                .collect();
-                if let Some(r) =
-                    Self::find_removed_classes(selected.iter(), style_rule, &declarations)
-                {
-                    removed_classes.extend(r);
-                } else if let Some(r) =
-                    Self::find_removed_ids(selected.iter(), style_rule, &declarations)
-                {
-                    removed_ids.extend(r);
-                } else if let Some(r) =
-                    Self::find_removed_others(selected.iter(), style_rule, &declarations)
-                {
-                    removed_others.extend(r);
-                }
-            }
-            _ => unreachable!(),
-        });
+                _ => unreachable!(),
+            });
         RemovedTokens {
             classes: removed_classes,
             ids: removed_ids,
             other: removed_others,
+            next_order: first_order + styles.0.len(),
         }
     }
 
@@ -338,6 +373,7 @@ impl<E: Element> InlineStyles<E> {
         selected: impl Iterator<Item = &'a E>,
         style_rule: &rules::style::StyleRule,
         declarations: &str,
+        order: usize,
     ) -> Option<Vec<RemovedToken<E>>> {
         let matching_classes: Vec<(Vec<E::Atom>, u32)> = style_rule
             .selectors
@@ -346,7 +382,7 @@ impl<E: Element> InlineStyles<E> {
             .map(|s| {
                 let matching_tokens: Vec<_> = s
                     .iter()
-                    .take_while(|t| matches!(t, selector::Component::Class(_)))
+                    .filter(|t| matches!(t, selector::Component::Class(_)))
                     .collect();
                 let specificity = s.specificity();
                 (matching_tokens, specificity)
@@ -374,6 +410,7 @@ impl<E: Element> InlineStyles<E> {
                 element: m.clone(),
                 token: class.clone(),
                 specificity: *specificity,
+                order,
                 declarations: declarations.to_string(),
             })
         });
@@ -389,6 +426,7 @@ impl<E: Element> InlineStyles<E> {
         selected: impl Iterator<Item = &'a E>,
         style_rule: &rules::style::StyleRule,
         declarations: &str,
+        order: usize,
     ) -> Option<Vec<RemovedToken<E>>> {
         let matching_ids: Vec<(E::Atom, u32)> = style_rule
             .selectors
@@ -413,6 +451,7 @@ impl<E: Element> InlineStyles<E> {
                 element: m.clone(),
                 token: vec![id.clone()],
                 specificity: *specificity,
+                order,
                 declarations: declarations.to_string(),
             })
         });
@@ -428,6 +467,7 @@ impl<E: Element> InlineStyles<E> {
         selected: impl Iterator<Item = &'a E>,
         style_rule: &rules::style::StyleRule,
         declarations: &str,
+        order: usize,
     ) -> Option<Vec<RemovedToken<E>>> {
         #[allow(clippy::redundant_closure_for_method_calls)]
         let matching: Vec<u32> = style_rule
@@ -449,6 +489,7 @@ impl<E: Element> InlineStyles<E> {
                     element: m.clone(),
                     token: vec![],
                     specificity: *s,
+                    order,
                     declarations: declarations.to_string(),
                 })
             })
@@ -488,9 +529,16 @@ impl Options {
             rules::CssRule::Media(media_rule) => {
                 let query = &media_rule.query;
                 let debug_query = query.to_css_string(printer::PrinterOptions::default());
-                if !self.is_media_query_useable(query) {
-                    log::debug!("media query not useable: {debug_query:?}");
-                    return true;
+                match self.media.as_ref().and_then(|media| media.matches(query)) {
+                    Some(false) => {
+                        log::debug!("removing media query not matching context: {debug_query:?}");
+                        return false;
+                    }
+                    None if !self.is_media_query_useable(query) => {
+                        log::debug!("media query not useable: {debug_query:?}");
+                        return true;
+                    }
+                    Some(true) | None => {}
                 }
                 let Some(removed_media) =
                     self.take_matching_selectors(&mut media_rule.rules, context)
@@ -687,6 +735,141 @@ impl Options {
     }
 }
 
+impl MediaContext {
+    /// Returns whether the media query list matches the context, or `None` when it depends on
+    /// something the context doesn't specify.
+    fn matches(&self, media_list: &media_query::MediaList) -> Option<bool> {
+        if media_list.media_queries.is_empty() {
+            return Some(true);
+        }
+        any(media_list
+            .media_queries
+            .iter()
+            .map(|query| self.matches_query(query)))
+    }
+
+    fn matches_query(&self, query: &media_query::MediaQuery) -> Option<bool> {
+        let media_type = self.media_type.as_deref().unwrap_or("screen");
+        let matches_type = match &query.media_type {
+            media_query::MediaType::All => true,
+            media_query::MediaType::Print => media_type.eq_ignore_ascii_case("print"),
+            media_query::MediaType::Screen => media_type.eq_ignore_ascii_case("screen"),
+            media_query::MediaType::Custom(custom) => media_type.eq_ignore_ascii_case(custom),
+        };
+        let matches = match &query.condition {
+            Some(condition) if matches_type => self.matches_condition(condition),
+            _ => Some(matches_type),
+        };
+        match query.qualifier {
+            Some(media_query::Qualifier::Not) => matches.map(|matches| !matches),
+            _ => matches,
+        }
+    }
+
+    fn matches_condition(&self, condition: &media_query::MediaCondition) -> Option<bool> {
+        match condition {
+            media_query::MediaCondition::Feature(feature) => self.matches_feature(feature),
+            media_query::MediaCondition::Not(condition) => {
+                self.matches_condition(condition).map(|matches| !matches)
+            }
+            media_query::MediaCondition::Operation {
+                operator,
+                conditions,
+            } => {
+                let conditions = conditions
+                    .iter()
+                    .map(|condition| self.matches_condition(condition));
+                match operator {
+                    media_query::Operator::And => all(conditions),
+                    media_query::Operator::Or => any(conditions),
+                }
+            }
+        }
+    }
+
+    fn matches_feature(&self, feature: &media_query::MediaFeature) -> Option<bool> {
+        use media_query::{MediaFeatureComparison, MediaFeatureId, MediaFeatureName, QueryFeature};
+
+        let compare = |a: f32, operator: &MediaFeatureComparison, b: f32| match operator {
+            MediaFeatureComparison::Equal => (a - b).abs() < f32::EPSILON,
+            MediaFeatureComparison::GreaterThan => a > b,
+            MediaFeatureComparison::GreaterThanEqual => a >= b,
+            MediaFeatureComparison::LessThan => a < b,
+            MediaFeatureComparison::LessThanEqual => a <= b,
+        };
+        let length = |name: &MediaFeatureName<MediaFeatureId>| match name {
+            MediaFeatureName::Standard(MediaFeatureId::Width) => self.width,
+            MediaFeatureName::Standard(MediaFeatureId::Height) => self.height,
+            _ => None,
+        };
+        let px = |value: &media_query::MediaFeatureValue| match value {
+            media_query::MediaFeatureValue::Length(length) => length.to_px(),
+            _ => None,
+        };
+
+        match feature {
+            QueryFeature::Plain {
+                name: MediaFeatureName::Standard(MediaFeatureId::PrefersColorScheme),
+                value: media_query::MediaFeatureValue::Ident(ident),
+            } => Some(
+                self.prefers_color_scheme
+                    .as_deref()?
+                    .eq_ignore_ascii_case(ident.0.as_ref()),
+            ),
+            QueryFeature::Plain { name, value } => Some(compare(
+                length(name)?,
+                &MediaFeatureComparison::Equal,
+                px(value)?,
+            )),
+            QueryFeature::Range {
+                name,
+                operator,
+                value,
+            } => Some(compare(length(name)?, operator, px(value)?)),
+            QueryFeature::Interval {
+                name,
+                start,
+                start_operator,
+                end,
+                end_operator,
+            } => {
+                let length = length(name)?;
+                Some(
+                    compare(px(start)?, start_operator, length)
+                        && compare(length, end_operator, px(end)?),
+                )
+            }
+            QueryFeature::Boolean { .. } => None,
+        }
+    }
+}
+
+/// Returns whether any value is true, or `None` if it can't be known
+fn any(values: impl Iterator<Item = Option<bool>>) -> Option<bool> {
+    let mut result = Some(false);
+    for value in values {
+        match value {
+            Some(true) => return Some(true),
+            Some(false) => {}
+            None => result = None,
+        }
+    }
+    result
+}
+
+/// Returns whether every value is true, or `None` if it can't be known
+fn all(values: impl Iterator<Item = Option<bool>>) -> Option<bool> {
+    let mut result = Some(true);
+    for value in values {
+        match value {
+            Some(false) => return Some(false),
+            Some(true) => {}
+            None => result = None,
+        }
+    }
+    result
+}
+
 fn find_parent_attrs(style_rule: &rules::style::StyleRule) -> ParentTokens {
     macro_rules! get_selector_presentation_attrs {
         ($iter:ident) => {
@@ -1413,5 +1596,74 @@ fn inline_styles() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "inlineStyles": {}, "minifyStyles": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 50 50">
+    <!-- custom properties should be inlined as-is -->
+    <style>
+        :root { --c: red; }
+        .a { fill: var(--c); stroke: var(--stroke, blue); }
+    </style>
+    <path class="a" d="M5 5H10" style="opacity: var(--opacity)"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "inlineStyles": {
+            "media": { "width": 600, "prefersColorScheme": "dark" }
+        } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 50 50">
+    <!-- media queries matching the context should be inlined, and others removed -->
+    <style>
+        @media (min-width: 500px) {
+            .a { fill: red; }
+        }
+        @media (max-width: 499px) {
+            .b { fill: blue; }
+        }
+        @media screen and (prefers-color-scheme: dark) {
+            .c { fill: white; }
+        }
+        @media print, (400px < width < 500px) {
+            .d { fill: green; }
+        }
+        @media (hover) {
+            .e { fill: black; }
+        }
+    </style>
+    <path class="a" d="M5 5H10"/>
+    <path class="b" d="M5 5H10"/>
+    <path class="c" d="M5 5H10"/>
+    <path class="d" d="M5 5H10"/>
+    <path class="e" d="M5 5H10"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "inlineStyles": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Inline styles should win over selectors, and selectors should apply by specificity and then source order -->
+    <style>
+        #b { fill: red; }
+        .a { fill: red; stroke: green; }
+        rect.c { fill: red; }
+        .d rect { fill: green; }
+        rect.e { fill: blue; }
+    </style>
+    <rect id="b" style="fill: blue"/>
+    <rect class="a"/>
+    <rect class="c"/>
+    <g class="d">
+        <rect class="e"/>
+    </g>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
@@ -4,16 +4,17 @@ use lightningcss::{
         svg::SVGPaint,
         Property, PropertyId,
     },
+    values::shape::FillRule,
     vendor_prefix::VendorPrefix,
 };
 use oxvg_ast::{
     attribute::{Attr, Attributes},
     element::Element,
     get_computed_property_factory, get_computed_styles_factory,
-    style::{ComputedStyles, Id, PresentationAttr, PresentationAttrId, Static},
+    style::{Id, PresentationAttr, PresentationAttrId, Static},
     visitor::{Context, ContextFlags, PrepareOutcome, Visitor},
 };
-use oxvg_path::{command, Path};
+use oxvg_path::{command, points::Points, Path};
 use serde::Deserialize;
 
 #[derive(Deserialize, Clone, Default)]
@@ -37,6 +38,7 @@ impl<E: Element> Visitor<E> for MergePaths {
         }
 
         let mut prev_path_data: Option<Path> = None;
+        let mut prev_points: Option<Points> = None;
         let d_name = "d".into();
 
         for window in children.windows(2) {
@@ -49,6 +51,7 @@ impl<E: Element> Visitor<E> for MergePaths {
                         prev_child.set_attribute_local(d_name.clone(), data.to_string().into());
                     }
                     prev_path_data = None;
+                    prev_points = None;
                 };
             }
 
@@ -79,11 +82,7 @@ impl<E: Element> Visitor<E> for MergePaths {
                 continue;
             };
 
-            let computed_styles = ComputedStyles::default().with_all(
-                child,
-                &context.stylesheet,
-                context.element_styles,
-            );
+            let computed_styles = context.computed_styles_of(child);
             get_computed_styles_factory!(computed_styles);
             get_computed_property_factory!(computed_styles);
             if get_computed_styles!(MarkerStart)
@@ -117,6 +116,15 @@ impl<E: Element> Visitor<E> for MergePaths {
                 update_previous_path!();
                 continue;
             }
+            // NOTE: Overlapping subpaths of an evenodd fill cut holes into each other
+            let is_evenodd = get_computed_styles!(FillRule).is_some_and(|s| {
+                s.is_dynamic()
+                    || matches!(
+                        s.inner(),
+                        Static::Attr(PresentationAttr::FillRule(FillRule::Evenodd))
+                            | Static::Css(Property::FillRule(FillRule::Evenodd))
+                    )
+            });
 
             let prev_attrs = prev_child.attributes();
             let attrs = child.attributes();
@@ -143,6 +151,7 @@ impl<E: Element> Visitor<E> for MergePaths {
                 prev_path_data = prev_child
                     .get_attribute_local(&d_name)
                     .and_then(|v| Path::parse(v.as_ref()).ok());
+                prev_points = None;
             }
 
             if let Some(prev_path_data) = &mut prev_path_data {
@@ -153,12 +162,25 @@ impl<E: Element> Visitor<E> for MergePaths {
                     )
                 }) {
                     prev_path_data.0.pop();
+                    if let Some(prev_points) = &mut prev_points {
+                        prev_points.pop();
+                    }
+                }
+                if self.force.unwrap_or(Self::DEFAULT_FORCE) && !is_evenodd {
+                    log::debug!("merging, forced");
+                    prev_path_data.0.extend(current_path_data.0);
+                    prev_points = None;
+                    prev_child.remove();
+                    continue;
                 }
-                if self.force.unwrap_or(Self::DEFAULT_FORCE)
-                    || !prev_path_data.intersects(&current_path_data)
-                {
+                // NOTE: The hulls of the previous paths are kept between merges
+                let prev_points =
+                    prev_points.get_or_insert_with(|| Points::from_path(prev_path_data));
+                let current_points = Points::from_path(&current_path_data);
+                if !prev_points.intersects(&current_points) {
                     log::debug!("merging, current doesn't intersect prev");
                     prev_path_data.0.extend(current_path_data.0);
+                    prev_points.extend(current_points);
                     prev_child.remove();
                     continue;
                 }
@@ -169,6 +191,7 @@ impl<E: Element> Visitor<E> for MergePaths {
                 update_previous_path!();
             } else {
                 prev_path_data = None;
+                prev_points = None;
             }
         }
         if let Some(prev_path_data) = prev_path_data {
@@ -420,5 +443,18 @@ fn merge_paths() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "mergePaths": { "force": true } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- don't force merging overlapping paths of evenodd fills, which would cut holes -->
+    <path fill-rule="evenodd" d="M0 0h20v20H0z"/>
+    <path fill-rule="evenodd" d="M5 5h10v10H5z"/>
+    <path d="M0 0h20v20H0z"/>
+    <path d="M5 5h10v10H5z"/>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
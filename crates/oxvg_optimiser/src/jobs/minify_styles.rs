@@ -1,6 +1,8 @@
 use lightningcss::{
+    declaration::DeclarationBlock,
     printer::PrinterOptions,
-    rules::CssRuleList,
+    properties::PropertyId,
+    rules::{CssRule, CssRuleList},
     stylesheet::{MinifyOptions, ParserFlags, ParserOptions, StyleAttribute, StyleSheet},
 };
 use oxvg_ast::{
@@ -81,6 +83,7 @@ impl MinifyStyles {
         if let Some(matched_selectors) = self.remove_unused_selectors(&mut css.rules, context) {
             css.rules = matched_selectors;
         };
+        merge_duplicate_rules(&mut css.rules);
         let _ = css.minify(MinifyOptions::default());
         let css = match css.to_css(PrinterOptions {
             minify: true,
@@ -171,6 +174,119 @@ impl<'de> Deserialize<'de> for RemoveUnused {
 
 const DEFAULT_REMOVE_UNUSED: RemoveUnused = RemoveUnused::True;
 
+/// Merges each style rule into the next rule with the same selector, when no rule between them
+/// may override any of its declarations.
+///
+/// Since the later rule takes precedence, declarations repeated by it are removed from the
+/// earlier rule.
+fn merge_duplicate_rules(rules: &mut CssRuleList) {
+    for rule in &mut rules.0 {
+        if let CssRule::Media(media) = rule {
+            merge_duplicate_rules(&mut media.rules);
+        }
+    }
+
+    let mut i = 0;
+    while i < rules.0.len() {
+        let Some(selector) = mergeable_selector(&rules.0[i]) else {
+            i += 1;
+            continue;
+        };
+        let CssRule::Style(rule) = &rules.0[i] else {
+            unreachable!("mergeable rules are style rules");
+        };
+        let ids = property_ids(&rule.declarations);
+
+        let mut merge_into = None;
+        for (j, other) in rules.0.iter().enumerate().skip(i + 1) {
+            let Some(other_selector) = mergeable_selector(other) else {
+                break;
+            };
+            if other_selector == selector {
+                merge_into = Some(j);
+                break;
+            }
+            let CssRule::Style(other) = other else {
+                unreachable!("mergeable rules are style rules");
+            };
+            let is_overlapping = property_ids(&other.declarations)
+                .iter()
+                .any(|other_id| ids.iter().any(|id| is_property_overlapping(id, other_id)));
+            if is_overlapping {
+                break;
+            }
+        }
+
+        let Some(j) = merge_into else {
+            i += 1;
+            continue;
+        };
+        log::debug!("merging duplicate rule for {selector}");
+        let CssRule::Style(rule) = rules.0.remove(i) else {
+            unreachable!("mergeable rules are style rules");
+        };
+        let CssRule::Style(other) = &mut rules.0[j - 1] else {
+            unreachable!("mergeable rules are style rules");
+        };
+        let DeclarationBlock {
+            mut declarations,
+            mut important_declarations,
+        } = rule.declarations;
+        // Identical declarations are overridden by the later rule
+        declarations.retain(|declaration| !other.declarations.declarations.contains(declaration));
+        important_declarations.retain(|declaration| {
+            !other
+                .declarations
+                .important_declarations
+                .contains(declaration)
+        });
+        declarations.append(&mut other.declarations.declarations);
+        important_declarations.append(&mut other.declarations.important_declarations);
+        other.declarations = DeclarationBlock {
+            declarations,
+            important_declarations,
+        };
+    }
+}
+
+/// Returns the selector of a style rule which can be merged with other rules
+fn mergeable_selector(rule: &CssRule) -> Option<String> {
+    match rule {
+        CssRule::Style(rule) if rule.rules.0.is_empty() && rule.vendor_prefix.is_empty() => {
+            Some(format!("{}", rule.selectors))
+        }
+        _ => None,
+    }
+}
+
+fn property_ids<'i>(declarations: &DeclarationBlock<'i>) -> Vec<PropertyId<'i>> {
+    declarations
+        .iter()
+        .map(|(property, _)| property.property_id())
+        .collect()
+}
+
+/// Whether the properties may set the same longhand, such as `font` and `line-height`
+fn is_property_overlapping(a: &PropertyId, b: &PropertyId) -> bool {
+    if matches!(a, PropertyId::All) || matches!(b, PropertyId::All) {
+        return true;
+    }
+    let a = expand_longhands(a);
+    let b = expand_longhands(b);
+    a.iter().any(|longhand| b.contains(longhand))
+}
+
+/// Returns each longhand set by the property, where a longhand is expanded to itself
+fn expand_longhands<'i>(id: &PropertyId<'i>) -> Vec<PropertyId<'i>> {
+    match id.longhands() {
+        Some(longhands) => longhands
+            .into_iter()
+            .flat_map(|longhand| expand_longhands(&longhand))
+            .collect(),
+        None => vec![id.clone()],
+    }
+}
+
 #[test]
 #[allow(clippy::too_many_lines)]
 fn minify_styles() -> anyhow::Result<()> {
@@ -334,5 +450,55 @@ fn minify_styles() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "minifyStyles": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <style>
+    .a { fill: red; }
+    .b { stroke: blue; }
+    .a { stroke: red; }
+    .c { opacity: .5; }
+    .d { fill: blue; }
+    .c { opacity: .5; }
+    .e { fill: red; }
+    .f { fill: blue; }
+    .e { stroke: blue; }
+    </style>
+    <!-- merge rules with the same selector, unless a rule between them may override it -->
+    <g class="a b"/>
+    <g class="c d"/>
+    <g class="e f"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "minifyStyles": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <style>
+    .a { line-height: 2; }
+    .b { font: 12px serif; }
+    .a { fill: red; }
+    .c { border-top-color: red; }
+    .d { border-color: blue; }
+    .c { fill: red; }
+    .e { top: 0; }
+    .f { inset: 1px; }
+    .e { fill: red; }
+    .g { fill: red; }
+    .h { all: unset; }
+    .g { stroke: red; }
+    </style>
+    <!-- keep rules apart when a rule between them sets a shorthand of their properties -->
+    <g class="a b"/>
+    <g class="c d"/>
+    <g class="e f"/>
+    <g class="g h"/>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
@@ -1,7 +1,15 @@
-use std::fmt::Display;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    time::{Duration, Instant},
+};
 
 use oxvg_ast::{
+    attribute::{Attr, Attributes},
     element::Element,
+    implementations::markup5ever::{Element5Ever, Node5Ever},
+    name::Name,
+    node, parse, serialize,
     visitor::{ContextFlags, PrepareOutcome, Visitor},
 };
 use serde::Deserialize;
@@ -45,6 +53,34 @@ macro_rules! jobs {
                 })+
                 Ok(count)
             }
+
+            /// Runs each job in the config, returning the time spent by each non-skipped job
+            fn run_jobs_timed(
+                &mut self,
+                element: &mut E,
+            ) -> Result<Vec<(&'static str, Duration)>, String> {
+                let mut timings = vec![];
+                $(if let Some(job) = self.$name.as_mut() {
+                    let start = Instant::now();
+                    if !job.start(element)?.contains(PrepareOutcome::skip) {
+                        timings.push((stringify!($name), start.elapsed()));
+                    }
+                })+
+                Ok(timings)
+            }
+
+            /// Runs each job in the config, returning what each non-skipped job changed
+            fn run_jobs_planned(&mut self, element: &mut E) -> Result<Vec<JobPlan>, String> {
+                let mut plans = vec![];
+                let mut snapshot = Snapshot::new(element);
+                $(if let Some(job) = self.$name.as_mut() {
+                    if !job.start(element)?.contains(PrepareOutcome::skip) {
+                        let before = std::mem::replace(&mut snapshot, Snapshot::new(element));
+                        plans.push(before.plan(stringify!($name), &snapshot));
+                    }
+                })+
+                Ok(plans)
+            }
         }
     };
 }
@@ -52,8 +88,20 @@ macro_rules! jobs {
 jobs! {
     // Non default plugins
     add_attributes_to_svg_element: AddAttributesToSVGElement,
+    add_attributes_by_selector: AddAttributesBySelector,
+    add_dimensions_from_view_box: AddDimensionsFromViewBox,
     add_classes_to_svg: AddClassesToSVG,
     cleanup_list_of_values: CleanupListOfValues,
+    cleanup_animation_timing: CleanupAnimationTiming,
+    evaluate_switch: EvaluateSwitch,
+    convert_one_stop_gradients: ConvertOneStopGradients,
+    convert_style_to_attrs: ConvertStyleToAttrs,
+    remove_nested_svg_dimensions: RemoveNestedSVGDimensions,
+    remove_empty_groups: RemoveEmptyGroups,
+    remove_off_canvas_paths: RemoveOffCanvasPaths,
+    remove_scripts: RemoveScripts,
+    remove_viewport_transforms: RemoveViewportTransforms,
+    reuse_paths: ReusePaths<E>,
 
     // Default plugins
     remove_doctype: RemoveDoctype (is_default: true),
@@ -82,11 +130,13 @@ jobs! {
     collapse_groups: CollapseGroups (is_default: true),
     // NOTE: `apply_transforms` should be before `convert_path_data` in case the order is ever changed
     apply_transforms: ApplyTransforms (is_default: true),
+    // NOTE: `merge_paths` should be before `convert_path_data`, so merged data is converted in the
+    // same pass
+    merge_paths: MergePaths (is_default: true),
     convert_path_data: ConvertPathData (is_default: true),
     convert_transform: ConvertTransform (is_default: true),
     remove_empty_attrs: RemoveEmptyAttrs (is_default: true),
     remove_empty_containers: RemoveEmptyContainers (is_default: true),
-    merge_paths: MergePaths (is_default: true),
     sort_attrs: SortAttrs (is_default: true),
     sort_defs_children: SortDefsChildren (is_default: true),
     remove_title: RemoveTitle (is_default: true),
@@ -96,12 +146,16 @@ jobs! {
 #[derive(Debug)]
 pub enum Error {
     Generic(String),
+    Parse(String),
+    Serialize(String),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Generic(s) => s.fmt(f),
+            Self::Parse(s) => write!(f, "Failed to parse document: {s}"),
+            Self::Serialize(s) => write!(f, "Failed to serialize document: {s}"),
         }
     }
 }
@@ -109,6 +163,18 @@ impl Display for Error {
 impl std::error::Error for Error {}
 
 impl<E: Element> Jobs<E> {
+    /// Returns the default jobs, along with lossy jobs and options which favour a smaller output
+    /// over an exact rendering, such as lower precision numbers and path simplification.
+    pub fn aggressive() -> Self {
+        Self {
+            cleanup_numeric_values: Some(CleanupNumericValues::aggressive()),
+            convert_path_data: Some(ConvertPathData::aggressive()),
+            remove_empty_groups: Some(RemoveEmptyGroups::default()),
+            remove_off_canvas_paths: Some(RemoveOffCanvasPaths::default()),
+            ..Self::default()
+        }
+    }
+
     /// # Errors
     /// When any job fails for the first time
     pub fn run(self, root: &E::ParentChild) -> Result<(), Error> {
@@ -122,6 +188,183 @@ impl<E: Element> Jobs<E> {
         log::debug!("completed {count} jobs");
         Ok(())
     }
+
+    /// Runs each job like [`Jobs::run`], returning how long each non-skipped job took, in the
+    /// order they were run.
+    ///
+    /// # Errors
+    /// When any job fails for the first time
+    pub fn run_timed(self, root: &E::ParentChild) -> Result<Vec<(&'static str, Duration)>, Error> {
+        let Some(mut root_element) = <E as Element>::from_parent(root.clone()) else {
+            log::warn!("No elements found in the document, skipping");
+            return Ok(vec![]);
+        };
+
+        let mut jobs = self.clone();
+        jobs.run_jobs_timed(&mut root_element)
+            .map_err(Error::Generic)
+    }
+
+    /// Reports what each non-skipped job would change, in the order they would be run, without
+    /// changing the document.
+    ///
+    /// The jobs are run against a deep clone of the document, so each job's plan includes the
+    /// changes made by the jobs before it.
+    ///
+    /// # Errors
+    /// When any job fails for the first time
+    pub fn plan(self, root: &E::ParentChild) -> Result<Vec<JobPlan>, Error> {
+        let Some(mut root_element) = <E as Element>::from_parent(node::Node::clone_deep(root))
+        else {
+            log::warn!("No elements found in the document, skipping");
+            return Ok(vec![]);
+        };
+
+        let mut jobs = self.clone();
+        jobs.run_jobs_planned(&mut root_element)
+            .map_err(Error::Generic)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// What a job would change in a document, as reported by [`Jobs::plan`]
+pub struct JobPlan {
+    /// The name of the job, such as `"remove_comments"`
+    pub name: &'static str,
+    /// The number of nodes which would be added, removed, renamed, or have their text changed.
+    ///
+    /// An element which is renamed by replacing it is counted as both removed and added.
+    pub nodes: usize,
+    /// The number of attributes which would be added, removed, or changed on the remaining
+    /// elements
+    pub attributes: usize,
+}
+
+/// The state of each node in a document, keyed by its address
+struct Snapshot<N: node::Node> {
+    nodes: HashMap<usize, (N, NodeState)>,
+}
+
+#[derive(PartialEq)]
+struct NodeState {
+    name: String,
+    value: Option<String>,
+    attributes: BTreeMap<String, String>,
+}
+
+impl<N: node::Node> Snapshot<N> {
+    fn new<E: Element<Child = N>>(element: &E) -> Self {
+        let mut snapshot = Self {
+            nodes: HashMap::new(),
+        };
+        snapshot.collect(element);
+        snapshot
+    }
+
+    fn collect<E: Element<Child = N>>(&mut self, element: &E) {
+        for child in element.child_nodes() {
+            let child_element = E::new(child.clone());
+            let attributes = child_element
+                .as_ref()
+                .map_or_else(BTreeMap::new, |element| {
+                    element
+                        .attributes()
+                        .into_iter()
+                        .map(|attr| {
+                            (
+                                attr.name().formatter().to_string(),
+                                attr.value().to_string(),
+                            )
+                        })
+                        .collect()
+                });
+            let state = NodeState {
+                name: child.node_name().to_string(),
+                value: child.node_value().map(|value| value.to_string()),
+                attributes,
+            };
+            self.nodes.insert(child.as_ptr_byte(), (child, state));
+            if let Some(child_element) = child_element {
+                self.collect(&child_element);
+            }
+        }
+    }
+
+    /// Returns the changes between this snapshot and the `after` snapshot
+    fn plan(&self, name: &'static str, after: &Self) -> JobPlan {
+        let mut plan = JobPlan {
+            name,
+            nodes: 0,
+            attributes: 0,
+        };
+        for (key, (_, before)) in &self.nodes {
+            let Some((_, after)) = after.nodes.get(key) else {
+                plan.nodes += 1;
+                continue;
+            };
+            if before.name != after.name || before.value != after.value {
+                plan.nodes += 1;
+            }
+            plan.attributes += before
+                .attributes
+                .iter()
+                .filter(|(name, value)| after.attributes.get(*name) != Some(*value))
+                .count();
+            plan.attributes += after
+                .attributes
+                .keys()
+                .filter(|name| !before.attributes.contains_key(*name))
+                .count();
+        }
+        plan.nodes += after
+            .nodes
+            .keys()
+            .filter(|key| !self.nodes.contains_key(key))
+            .count();
+        plan
+    }
+}
+
+impl Jobs<Element5Ever> {
+    /// Parses the SVG document, runs each job, and serializes the result
+    ///
+    /// # Errors
+    /// When the document fails to parse or serialize, or when any job fails
+    pub fn run_str(&self, svg: &str) -> Result<String, Error> {
+        self.run_str_with_options(svg, serialize::Options::default())
+    }
+
+    /// Parses the SVG document, runs each job, and serializes the result with the given options
+    ///
+    /// # Errors
+    /// When the document fails to parse or serialize, or when any job fails
+    pub fn run_str_with_options(
+        &self,
+        svg: &str,
+        options: serialize::Options,
+    ) -> Result<String, Error> {
+        let dom: Node5Ever =
+            parse::Node::parse(svg).map_err(|err| Error::Parse(err.to_string()))?;
+        self.clone().run(&dom)?;
+        serialize::Node::serialize_with_options(&dom, options)
+            .map_err(|err| Error::Serialize(err.to_string()))
+    }
+
+    /// Returns whether running the jobs against their own output leaves it unchanged.
+    ///
+    /// A second pass which changes the output either found an optimisation missed by the first,
+    /// or changed the document in a way the first didn't intend.
+    ///
+    /// # Errors
+    /// When the document fails to parse or serialize, or when any job fails
+    pub fn is_idempotent(&self, svg: &str) -> Result<bool, Error> {
+        let once = self.run_str(svg)?;
+        let twice = self.run_str(&once)?;
+        if once != twice {
+            log::debug!("second pass changed output:\n{once}\n{twice}");
+        }
+        Ok(once == twice)
+    }
 }
 
 #[cfg(test)]
@@ -142,20 +385,122 @@ pub(crate) fn test_config_default_svg_comment(
 
 #[cfg(test)]
 pub(crate) fn test_config(config_json: &str, svg: Option<&str>) -> anyhow::Result<String> {
-    use oxvg_ast::{
-        implementations::markup5ever::{Element5Ever, Node5Ever},
-        parse::Node,
-        serialize,
-    };
-
     let jobs: Jobs<Element5Ever> = serde_json::from_str(config_json)?;
-    let dom: Node5Ever = Node::parse(svg.unwrap_or(
+    Ok(jobs.run_str(svg.unwrap_or(
         r#"<svg xmlns="http://www.w3.org/2000/svg">
     test
 </svg>"#,
-    ))?;
-    jobs.run(&dom)?;
-    serialize::Node::serialize(&dom)
+    ))?)
+}
+
+#[test]
+fn test_run_str() -> anyhow::Result<()> {
+    let jobs: Jobs<Element5Ever> = serde_json::from_str(r#"{ "removeComments": {} }"#)?;
+    assert_eq!(
+        jobs.run_str(r#"<svg xmlns="http://www.w3.org/2000/svg"><!-- test --></svg>"#)?,
+        r#"<svg xmlns="http://www.w3.org/2000/svg"></svg>"#
+    );
+    assert_eq!(
+        jobs.run_str_with_options(
+            r#"<svg xmlns="http://www.w3.org/2000/svg"><!-- test --><g/></svg>"#,
+            serialize::Options::pretty()
+        )?,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <g></g>\n</svg>"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_is_idempotent() -> anyhow::Result<()> {
+    let jobs: Jobs<Element5Ever> = serde_json::from_str(r#"{ "convertPathData": {} }"#)?;
+    assert!(jobs.is_idempotent(
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><path d="M-30.69030 70.5039 l25.83118 -81.47482"/></svg>"#
+    )?);
+
+    // Merged paths should have their data converted in the same pass
+    assert!(Jobs::<Element5Ever>::default().is_idempotent(
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><path d="M10 50h10"/><path d="M10 50v10"/></svg>"#
+    )?);
+    Ok(())
+}
+
+#[test]
+fn test_run_timed() -> anyhow::Result<()> {
+    let jobs: Jobs<Element5Ever> =
+        serde_json::from_str(r#"{ "removeComments": {}, "removeDesc": {} }"#)?;
+    let dom: Node5Ever =
+        parse::Node::parse(r#"<svg xmlns="http://www.w3.org/2000/svg"><!-- test --></svg>"#)?;
+    let timings = jobs.run_timed(&dom)?;
+    assert_eq!(
+        timings.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+        vec!["remove_comments", "remove_desc"]
+    );
+    assert_eq!(
+        serialize::Node::serialize(&dom)?,
+        r#"<svg xmlns="http://www.w3.org/2000/svg"></svg>"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_plan() -> anyhow::Result<()> {
+    let jobs: Jobs<Element5Ever> = serde_json::from_str(
+        r#"{ "removeComments": {}, "removeDesc": {}, "convertColors": {}, "removeTitle": true }"#,
+    )?;
+    let svg = r##"<svg xmlns="http://www.w3.org/2000/svg"><!-- a --><!-- b --><desc>Created with Sketch</desc><path fill="#ff0000" stroke="rgb(0, 0, 255)"></path></svg>"##;
+    let dom: Node5Ever = parse::Node::parse(svg)?;
+    let plans = jobs.plan(&dom)?;
+    assert_eq!(
+        plans,
+        vec![
+            JobPlan {
+                name: "remove_comments",
+                nodes: 2,
+                attributes: 0,
+            },
+            JobPlan {
+                name: "convert_colors",
+                nodes: 0,
+                attributes: 2,
+            },
+            JobPlan {
+                name: "remove_title",
+                nodes: 0,
+                attributes: 0,
+            },
+            JobPlan {
+                name: "remove_desc",
+                nodes: 2,
+                attributes: 0,
+            },
+        ]
+    );
+
+    // The document is unchanged
+    assert_eq!(serialize::Node::serialize(&dom)?, svg);
+    Ok(())
+}
+
+#[test]
+fn test_aggressive() -> anyhow::Result<()> {
+    let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+    <g transform="translate(0.123456 0.654321)">
+        <rect x="10.123456" y="10.654321" width="20.987654" height="20.123456" fill="#ff0000"/>
+    </g>
+    <path d="M 10.123456 50.987654 L 20.222222 50.333333 L 30.444444 51.012345 L 40.666666 50.111111 L 50.888888 50.999999 C 60.123456 60.654321 70.987654 40.123456 80.555555 50.444444"/>
+    <circle cx="50.555555" cy="50.444444" r="10.333333"/>
+    <path d="M 200 200 h 10 v 10 h -10 z"/>
+    <g></g>
+</svg>"##;
+    let default = Jobs::<Element5Ever>::default().run_str(svg)?;
+    let aggressive = Jobs::<Element5Ever>::aggressive().run_str(svg)?;
+    assert!(aggressive.len() < default.len());
+
+    // Should still be a valid document
+    let dom: Node5Ever = parse::Node::parse(&aggressive)?;
+    let root = Element5Ever::find_element(dom.clone()).expect("document should have a root");
+    assert_eq!(root.local_name().as_ref(), "svg");
+    Ok(())
 }
 
 #[test]
@@ -168,3 +513,22 @@ fn test_jobs() -> anyhow::Result<()> {
     )
     .map(|_| ())
 }
+
+#[test]
+fn test_foreign_object() -> anyhow::Result<()> {
+    // Should keep XHTML within `<foreignObject>` as-is, while optimising nested SVG
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"><rect width="10" height="10"/><foreignObject width="100" height="100"><div xmlns="http://www.w3.org/1999/xhtml">hi</div></foreignObject></svg>"#;
+    let output = Jobs::<Element5Ever>::default().run_str(svg)?;
+    assert!(
+        output.contains(r#"<div xmlns="http://www.w3.org/1999/xhtml">hi</div>"#),
+        "{output}"
+    );
+
+    let svg = r##"<svg xmlns="http://www.w3.org/2000/svg"><foreignObject><div xmlns="http://www.w3.org/1999/xhtml" class="a   b" style="color: red; color: blue">hi <p title="">there</p><span></span><svg xmlns="http://www.w3.org/2000/svg"><path d="M 10 10 L 20 20" fill="#ff0000"/></svg></div></foreignObject></svg>"##;
+    let output = Jobs::<Element5Ever>::default().run_str(svg)?;
+    assert!(
+        output.contains(r#"<div xmlns="http://www.w3.org/1999/xhtml" class="a   b" style="color: red; color: blue">hi <p title="">there</p><span></span><svg xmlns="http://www.w3.org/2000/svg"><path fill="red" d="m10 10 10 10"></path></svg></div>"#),
+        "{output}"
+    );
+    Ok(())
+}
@@ -232,5 +232,19 @@ fn move_elems_attrs_to_group() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "moveElemsAttrsToGroup": true }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- move inheritable attributes shared by every path, but not non-inheritable ones -->
+    <g>
+        <path fill="red" opacity="0.5" d="M0 0h10"/>
+        <path fill="red" opacity="0.5" d="M0 5h10"/>
+        <path fill="red" opacity="0.5" d="M0 10h10"/>
+    </g>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
@@ -76,8 +76,10 @@ impl<'de> Deserialize<'de> for PreservePattern {
 }
 
 lazy_static! {
-    static ref DEFAULT_PRESERVE_PATTERNS: Vec<PreservePattern> =
-        vec![PreservePattern(regex::Regex::new("^!").unwrap())];
+    static ref DEFAULT_PRESERVE_PATTERNS: Vec<PreservePattern> = vec![
+        PreservePattern(regex::Regex::new("^!").unwrap()),
+        PreservePattern(regex::Regex::new("@license").unwrap()),
+    ];
 }
 
 #[test]
@@ -116,5 +118,17 @@ fn remove_comments() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeComments": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!--! Copyright 2024 -->
+    <!-- @license MIT -->
+    <!-- Generator: Sketch -->
+    test
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
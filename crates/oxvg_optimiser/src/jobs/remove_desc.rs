@@ -5,9 +5,19 @@ use oxvg_ast::{
 };
 use serde::Deserialize;
 
+use super::remove_title::is_first_of_root;
+
 #[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+/// Removes `<desc>` elements which are empty or generated by an editor, or any `<desc>` with
+/// `removeAny`.
+///
+/// With `keepForAccessibility`, the first `<desc>` of the root `<svg>` is kept, as it's the
+/// accessible description of the image.
 pub struct RemoveDesc {
+    #[serde(alias = "remove_any")]
     remove_any: Option<bool>,
+    keep_for_accessibility: Option<bool>,
 }
 
 impl<E: Element> Visitor<E> for RemoveDesc {
@@ -17,6 +27,9 @@ impl<E: Element> Visitor<E> for RemoveDesc {
         if element.prefix().is_some() || element.local_name().as_ref() != "desc" {
             return Ok(());
         }
+        if self.keep_for_accessibility.unwrap_or(false) && is_first_of_root(element) {
+            return Ok(());
+        }
 
         if self.remove_any.unwrap_or(false)
             || element.is_empty()
@@ -51,5 +64,17 @@ fn remove_desc() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeDesc": { "removeAny": true, "keepForAccessibility": true } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" role="img">
+    <desc>Accessible description</desc>
+    <g>
+        <desc>Decorative</desc>
+    </g>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
@@ -0,0 +1,96 @@
+use oxvg_ast::{
+    attribute::{Attr, Attributes},
+    element::Element,
+    name::Name,
+    visitor::{Context, Visitor},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+/// Removes `<g>` elements without children, unlike [`super::RemoveEmptyContainers`] which
+/// removes any kind of empty container.
+///
+/// Groups with an `id`, `transform`, or a `clip-path`, `mask`, or `filter` are kept, since they
+/// may be referenced or still render.
+pub struct RemoveEmptyGroups {
+    /// Whether to keep empty groups with any attributes at all
+    keep_with_attributes: Option<bool>,
+}
+
+/// Attributes which make an empty group worth keeping
+const MEANINGFUL_ATTRS: [&str; 5] = ["id", "transform", "clip-path", "mask", "filter"];
+
+impl<E: Element> Visitor<E> for RemoveEmptyGroups {
+    type Error = String;
+
+    fn exit_element(&mut self, element: &mut E, _context: &Context<E>) -> Result<(), String> {
+        let name = element.qual_name();
+        if name.prefix().is_some() || name.local_name().as_ref() != "g" {
+            return Ok(());
+        }
+        if !element.is_empty() {
+            return Ok(());
+        }
+        if Element::parent_element(element)
+            .is_some_and(|e| e.prefix().is_none() && e.local_name().as_ref() == "switch")
+        {
+            log::debug!("not removing group: in switch");
+            return Ok(());
+        }
+
+        let attributes = element.attributes();
+        if self.keep_with_attributes.unwrap_or(false) {
+            if !attributes.is_empty() {
+                log::debug!("not removing group: has attributes");
+                return Ok(());
+            }
+        } else if attributes.into_iter().any(|attr| {
+            attr.prefix().is_none() && MEANINGFUL_ATTRS.contains(&attr.local_name().as_ref())
+        }) {
+            log::debug!("not removing group: has meaningful attributes");
+            return Ok(());
+        }
+
+        element.remove();
+        Ok(())
+    }
+}
+
+#[test]
+fn remove_empty_groups() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeEmptyGroups": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should remove empty groups, but not other containers -->
+    <defs/>
+    <symbol/>
+    <g/>
+    <g fill="red"><g/></g>
+    <g id="a"/>
+    <g transform="scale(2)"/>
+    <g clip-path="url(#c)"/>
+    <g filter="url(#f)"/>
+    <switch><g/></switch>
+    <g><path d="M0 0h10"/></g>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeEmptyGroups": { "keepWithAttributes": true } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should keep empty groups with any attributes -->
+    <g/>
+    <g fill="red"/>
+    <g><g/></g>
+</svg>"#
+        )
+    )?);
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use oxvg_ast::{
     attribute::Attr,
     element::Element,
     name::Name,
+    node::{self, Node},
     visitor::{Context, Visitor},
 };
 use serde::Deserialize;
@@ -12,6 +13,7 @@ pub struct RemoveEmptyText {
     text: Option<bool>,
     tspan: Option<bool>,
     tref: Option<bool>,
+    whitespace: Option<bool>,
 }
 
 impl<E: Element> Visitor<E> for RemoveEmptyText {
@@ -19,12 +21,15 @@ impl<E: Element> Visitor<E> for RemoveEmptyText {
 
     fn element(&mut self, element: &mut E, _context: &mut Context<E>) -> Result<(), Self::Error> {
         let name = element.qual_name().formatter().to_string();
+        if self.whitespace.unwrap_or(false) {
+            remove_insignificant_whitespace(element);
+        }
 
-        if self.text.unwrap_or(true) && &name == "text" && element.is_empty() {
+        if self.text.unwrap_or(true) && &name == "text" && element.is_blank() {
             element.remove();
         }
 
-        if self.tspan.unwrap_or(true) && &name == "tspan" && element.is_empty() {
+        if self.tspan.unwrap_or(true) && &name == "tspan" && element.is_blank() {
             element.remove();
         }
 
@@ -37,6 +42,43 @@ impl<E: Element> Visitor<E> for RemoveEmptyText {
     }
 }
 
+/// Removes text made of whitespace from the element's children, unless it's within text content
+/// or preserved by `xml:space`, where only text without any content is removed.
+fn remove_insignificant_whitespace<E: Element>(element: &E) {
+    let is_whitespace_significant =
+        is_within_text_content(element) || element.is_whitespace_preserved();
+    for child in element.child_nodes() {
+        if child.node_type() != node::Type::Text {
+            continue;
+        }
+        let Some(value) = child.node_value() else {
+            continue;
+        };
+        let is_insignificant = if is_whitespace_significant {
+            value.as_ref().is_empty()
+        } else {
+            value.as_ref().trim().is_empty()
+        };
+        if is_insignificant {
+            child.remove();
+        }
+    }
+}
+
+fn is_within_text_content<E: Element>(element: &E) -> bool {
+    let mut element = Some(element.clone());
+    while let Some(current) = element.filter(|element| element.node_type() == node::Type::Element) {
+        if matches!(
+            current.local_name().as_ref(),
+            "text" | "tspan" | "textPath" | "tref"
+        ) {
+            return true;
+        }
+        element = Element::parent_element(&current);
+    }
+    false
+}
+
 #[test]
 fn remove_empty_text() -> anyhow::Result<()> {
     use crate::test_config;
@@ -77,5 +119,34 @@ fn remove_empty_text() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeEmptyText": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- keep text with content, or with whitespace that's preserved -->
+    <text>Hello</text>
+    <text> </text>
+    <text xml:space="preserve"> </text>
+    <g xml:space="preserve">
+        <text>Hello <tspan> </tspan>world</text>
+        <text xml:space="default"><tspan> </tspan></text>
+    </g>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeEmptyText": { "whitespace": true } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- remove whitespace between elements, but not within text -->
+    <rect/> <rect/>
+    <text>a <tspan>b</tspan></text>
+    <text>a <a> <tspan>b</tspan></a></text>
+    <g xml:space="preserve"> <rect/> </g>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
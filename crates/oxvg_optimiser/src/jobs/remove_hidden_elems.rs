@@ -20,7 +20,7 @@ use oxvg_collections::collections::NON_RENDERING;
 use oxvg_path::Path;
 use serde::Deserialize;
 
-use crate::utils::find_references;
+use crate::utils::{has_animated_inherited_attr, is_animation_target};
 
 #[derive(Clone, Default, Deserialize)]
 pub struct Options {
@@ -154,19 +154,7 @@ impl<E: Element> Visitor<E> for RemoveHiddenElems<E> {
             return Ok(());
         }
 
-        for attr in element.attributes().into_iter() {
-            let local_name = attr.local_name();
-            let value = attr.value();
-
-            let ids = find_references(local_name.as_ref(), value.as_ref());
-            if let Some(ids) = ids {
-                ids.filter_map(|id| id.get(1))
-                    .map(|id| id.as_str().to_string())
-                    .for_each(|id| {
-                        self.data.all_references.insert(id);
-                    });
-            }
-        }
+        self.data.all_references.extend(element.find_references());
         Ok(())
     }
 
@@ -254,6 +242,7 @@ impl<E: Element> RemoveHiddenElems<E> {
                         .unwrap()
                         .next()
                         .is_none()
+                    && !has_animated_inherited_attr(element, "visibility")
                 {
                     return true;
                 }
@@ -272,6 +261,7 @@ impl<E: Element> RemoveHiddenElems<E> {
                         )))
                     )
                     && name != "marker"
+                    && !is_animation_target(element, "display")
                 {
                     return true;
                 }
@@ -722,5 +712,43 @@ fn remove_hidden_elems() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeHiddenElems": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- preserve hidden elements revealed by an animation -->
+    <g display="none">
+        <set attributeName="display" to="inline" begin="1s"/>
+        <rect width="10" height="10"/>
+    </g>
+    <rect width="10" height="10" visibility="hidden">
+        <set attributeName="visibility" to="visible" begin="1s"/>
+    </rect>
+    <rect width="10" height="10" display="none">
+        <set attributeName="fill" to="red" begin="1s"/>
+    </rect>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeHiddenElems": {} }"#,
+        Some(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+    <!-- preserve hidden elements targeted by an animation's href -->
+    <rect id="a" width="10" height="10" display="none"/>
+    <set href="#a" attributeName="display" to="inline" begin="1s"/>
+    <rect id="b" width="10" height="10" visibility="hidden"/>
+    <set xlink:href="#b" attributeName="visibility" to="visible" begin="1s"/>
+    <!-- remove hidden elements when only a descendant is animated -->
+    <g display="none">
+        <rect width="10" height="10" display="none">
+            <set attributeName="display" to="inline" begin="1s"/>
+        </rect>
+    </g>
+</svg>"##
+        ),
+    )?);
+
     Ok(())
 }
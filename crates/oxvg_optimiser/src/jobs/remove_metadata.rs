@@ -6,21 +6,40 @@ use oxvg_ast::{
 use serde::Deserialize;
 
 #[derive(Deserialize, Clone)]
+#[serde(untagged)]
+/// Removes `<metadata>` elements.
+///
+/// Either a `bool` to enable the job, or its options.
+pub enum RemoveMetadata {
+    Enabled(bool),
+    Options(Options),
+}
+
+#[derive(Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-pub struct RemoveMetadata(bool);
+pub struct Options {
+    /// Whether to keep RDF, Dublin Core, and Creative Commons metadata, such as licensing
+    /// information, while removing any other metadata.
+    keep_rdf: Option<bool>,
+}
+
+/// The namespaces of standards-based metadata
+const STANDARD_NAMESPACES: [&str; 5] = [
+    "http://www.w3.org/1999/02/22-rdf-syntax-ns#",
+    "http://purl.org/dc/elements/1.1/",
+    "http://purl.org/dc/terms/",
+    "http://creativecommons.org/ns#",
+    "http://web.resource.org/cc/",
+];
 
 impl<E: Element> Visitor<E> for RemoveMetadata {
     type Error = String;
 
-    fn prepare(
-        &mut self,
-        _document: &E,
-        _context_flags: &mut ContextFlags,
-    ) -> super::PrepareOutcome {
-        if self.0 {
-            PrepareOutcome::none
-        } else {
+    fn prepare(&mut self, _document: &E, _context_flags: &mut ContextFlags) -> PrepareOutcome {
+        if matches!(self, Self::Enabled(false)) {
             PrepareOutcome::skip
+        } else {
+            PrepareOutcome::none
         }
     }
 
@@ -30,16 +49,39 @@ impl<E: Element> Visitor<E> for RemoveMetadata {
             return Ok(());
         }
 
-        if name.local_name().as_ref() == "metadata" {
-            element.remove();
+        if name.local_name().as_ref() != "metadata" {
+            return Ok(());
+        }
+        let keep_rdf = match self {
+            Self::Enabled(_) => false,
+            Self::Options(options) => options.keep_rdf.unwrap_or(false),
+        };
+        if keep_rdf {
+            remove_non_standard_children(element);
+            if element.has_child_elements() {
+                return Ok(());
+            }
         }
+        element.remove();
         Ok(())
     }
 }
 
 impl Default for RemoveMetadata {
     fn default() -> Self {
-        Self(true)
+        Self::Enabled(true)
+    }
+}
+
+fn remove_non_standard_children<E: Element>(element: &E) {
+    for child in element.children() {
+        let ns = child.qual_name().ns().as_ref();
+        if STANDARD_NAMESPACES.contains(&ns) {
+            remove_non_standard_children(&child);
+        } else {
+            log::debug!("removing non-standard metadata: {}", child.local_name());
+            child.remove();
+        }
     }
 }
 
@@ -57,5 +99,38 @@ fn remove_metadata() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeMetadata": { "keepRdf": true } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:cc="http://creativecommons.org/ns#" xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd">
+    <!-- keep licensing, but remove editor metadata -->
+    <metadata>
+        <rdf:RDF>
+            <cc:Work rdf:about="">
+                <dc:format>image/svg+xml</dc:format>
+                <cc:license rdf:resource="http://creativecommons.org/licenses/by/4.0/"/>
+                <sodipodi:namedview pagecolor="white"/>
+            </cc:Work>
+        </rdf:RDF>
+        <sodipodi:namedview pagecolor="white"/>
+    </metadata>
+    <g/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeMetadata": { "keepRdf": true } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd">
+    <!-- remove metadata with only editor metadata -->
+    <metadata>
+        <sodipodi:namedview pagecolor="white"/>
+    </metadata>
+    <g/>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
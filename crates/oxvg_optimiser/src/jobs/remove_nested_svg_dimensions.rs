@@ -0,0 +1,85 @@
+use lightningcss::{
+    traits::Parse,
+    values::{length::LengthPercentage, percentage::Percentage},
+};
+use oxvg_ast::{
+    element::Element,
+    name::Name,
+    visitor::{Context, ContextFlags, PrepareOutcome, Visitor},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+/// Removes `width="100%"` and `height="100%"` from nested `<svg>` elements, as it's the
+/// same as the default size within the parent's viewport.
+pub struct RemoveNestedSVGDimensions(bool);
+
+impl<E: Element> Visitor<E> for RemoveNestedSVGDimensions {
+    type Error = String;
+
+    fn prepare(&mut self, _document: &E, _context_flags: &mut ContextFlags) -> PrepareOutcome {
+        if self.0 {
+            PrepareOutcome::none
+        } else {
+            PrepareOutcome::skip
+        }
+    }
+
+    fn element(&mut self, element: &mut E, context: &mut Context<E>) -> Result<(), String> {
+        let name = element.qual_name();
+        if name.prefix().is_some() || name.local_name().as_ref() != "svg" {
+            return Ok(());
+        }
+        if context.flags.contains(ContextFlags::within_foreign_object) {
+            return Ok(());
+        }
+        if element.closest_local(&"svg".into()).is_none() {
+            log::debug!("not removing dimensions of root svg");
+            return Ok(());
+        }
+
+        for name in ["width", "height"] {
+            let name = name.into();
+            let is_full_size = element
+                .get_attribute_local(&name)
+                .is_some_and(|value| is_full_size(value.as_ref()));
+            if is_full_size {
+                log::debug!("removing redundant {name:?}");
+                element.remove_attribute_local(&name);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether the length is `100%`, the default size of a nested `<svg>`
+fn is_full_size(value: &str) -> bool {
+    matches!(
+        LengthPercentage::parse_string(value.trim()),
+        Ok(LengthPercentage::Percentage(Percentage(p))) if p == 1.0
+    )
+}
+
+#[test]
+fn remove_nested_svg_dimensions() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeNestedSvgDimensions": true }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100%" height="100%" viewBox="0 0 100 100">
+    <!-- remove redundant 100% dimensions from nested svg -->
+    <svg width="100%" height="100%" viewBox="0 0 10 10"/>
+    <g>
+        <svg width="100.0%" height="100%"/>
+    </g>
+    <!-- keep explicit dimensions -->
+    <svg width="50" height="50px" viewBox="0 0 10 10"/>
+    <svg width="100%" height="50%"/>
+</svg>"#
+        ),
+    )?);
+
+    Ok(())
+}
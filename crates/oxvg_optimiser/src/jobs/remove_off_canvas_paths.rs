@@ -0,0 +1,298 @@
+use lightningcss::{
+    properties::{svg::SVGPaint, Property, PropertyId},
+    values::{length::LengthValue, percentage::DimensionPercentage},
+};
+use oxvg_ast::{
+    element::Element,
+    get_computed_styles_factory,
+    style::{Id, PresentationAttr, PresentationAttrId, Static},
+    visitor::{Context, ContextFlags, PrepareOutcome, Visitor},
+};
+use oxvg_path::{command, Path};
+use serde::Deserialize;
+
+use crate::utils::view_box::ViewBox;
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Removes paths which are drawn entirely outside of the viewport established by their nearest
+/// `<svg>`, whether it's the root or a nested `<svg>`.
+///
+/// Paths with a transform, or within an element with a transform, are assumed to be visible.
+/// The same goes for paths within a nested `<svg>` that doesn't clip its overflow, and the
+/// paths in a `<symbol>`, `<pattern>`, or `<marker>`, which are drawn in a different viewport.
+pub struct RemoveOffCanvasPaths {
+    /// The viewports established by each `<svg>` the visitor is within, if known
+    #[serde(skip_deserializing)]
+    viewports: Vec<Option<ViewBox>>,
+}
+
+impl<E: Element> Visitor<E> for RemoveOffCanvasPaths {
+    type Error = String;
+
+    fn prepare(&mut self, _document: &E, _context_flags: &mut ContextFlags) -> PrepareOutcome {
+        PrepareOutcome::use_style
+    }
+
+    fn use_style(&self, element: &E) -> bool {
+        element.prefix().is_none() && element.local_name().as_ref() == "path"
+    }
+
+    fn element(&mut self, element: &mut E, context: &mut Context<E>) -> Result<(), String> {
+        if element.prefix().is_some() {
+            return Ok(());
+        }
+        let name = element.local_name().as_ref();
+        if name == "svg" {
+            let is_overflow_visible = !element.is_root()
+                && element
+                    .get_attribute_local(&"overflow".into())
+                    .is_some_and(|value| matches!(value.as_ref(), "visible" | "auto"));
+            self.viewports.push(if is_overflow_visible {
+                None
+            } else {
+                ViewBox::new(element)
+            });
+        }
+        if matches!(name, "symbol" | "pattern" | "marker") {
+            log::debug!("skipping element drawn in another viewport");
+            context.flags.visit_skip();
+            return Ok(());
+        }
+
+        if element.has_attribute_local(&"transform".into()) {
+            log::debug!("skipping transformed element");
+            context.flags.visit_skip();
+            return Ok(());
+        }
+        if name != "path"
+            || context
+                .flags
+                .intersects(ContextFlags::within_defs | ContextFlags::within_clip_or_mask)
+        {
+            return Ok(());
+        }
+        let Some(Some(view_box)) = self.viewports.last() else {
+            return Ok(());
+        };
+        let Some(stroke_extent) = stroke_extent(context) else {
+            log::debug!("skipping path with unknown stroke");
+            return Ok(());
+        };
+        let mut view_box = view_box.clone();
+        view_box.translate(-stroke_extent, -stroke_extent);
+        view_box.width += stroke_extent * 2.0;
+        view_box.height += stroke_extent * 2.0;
+        let Some(d) = element.get_attribute_local(&"d".into()) else {
+            return Ok(());
+        };
+        let Ok(mut path) = Path::parse(d.as_ref()) else {
+            return Ok(());
+        };
+        drop(d);
+
+        // consider that a move within the viewport is visible
+        if path.0.iter().any(|command| match command {
+            command::Data::MoveTo([x, y]) => view_box.contains(*x, *y),
+            _ => false,
+        }) {
+            return Ok(());
+        }
+        if path.0.len() == 2 {
+            // close the path, as it's too short for checking intersections
+            path.0.push(command::Data::ClosePath);
+        }
+        if !view_box.path().intersects(&path) {
+            log::debug!("removing off-canvas path");
+            element.remove();
+        }
+        Ok(())
+    }
+
+    fn exit_element(&mut self, element: &mut E, _context: &Context<E>) -> Result<(), String> {
+        if element.prefix().is_none() && element.local_name().as_ref() == "svg" {
+            self.viewports.pop();
+        }
+        Ok(())
+    }
+}
+
+/// Returns how far the stroke of a path may be drawn beyond its outline, or `None` when it can't
+/// be determined.
+///
+/// This allows for miter joins up to the `stroke-miterlimit`, which reach the furthest.
+fn stroke_extent<E: Element>(context: &Context<E>) -> Option<f64> {
+    let computed_styles = &context.computed_styles;
+    get_computed_styles_factory!(computed_styles);
+    let Some(stroke) = get_computed_styles!(Stroke) else {
+        return Some(0.0);
+    };
+    if stroke.is_dynamic() {
+        return None;
+    }
+    if matches!(
+        stroke.inner(),
+        Static::Attr(PresentationAttr::Stroke(SVGPaint::None))
+            | Static::Css(Property::Stroke(SVGPaint::None))
+    ) {
+        return Some(0.0);
+    }
+
+    let stroke_width = match get_computed_styles!(StrokeWidth) {
+        Some(stroke_width) if stroke_width.is_dynamic() => return None,
+        Some(stroke_width) => match stroke_width.inner() {
+            Static::Attr(PresentationAttr::StrokeWidth(DimensionPercentage::Dimension(
+                LengthValue::Px(width),
+            )))
+            | Static::Css(Property::StrokeWidth(DimensionPercentage::Dimension(
+                LengthValue::Px(width),
+            ))) => f64::from(width),
+            _ => return None,
+        },
+        None => 1.0,
+    };
+    let miter_limit = match get_computed_styles!(StrokeMiterlimit) {
+        Some(miter_limit) if miter_limit.is_dynamic() => return None,
+        Some(miter_limit) => match miter_limit.inner() {
+            Static::Attr(PresentationAttr::StrokeMiterlimit(limit))
+            | Static::Css(Property::StrokeMiterlimit(limit)) => f64::from(limit),
+            _ => return None,
+        },
+        None => 4.0,
+    };
+    Some(stroke_width / 2.0 * miter_limit.max(std::f64::consts::SQRT_2))
+}
+
+#[test]
+#[allow(clippy::too_many_lines)]
+fn remove_off_canvas_paths() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeOffCanvasPaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+    <!-- Should remove paths outside of the viewBox -->
+    <path d="M10 10 h 80 v 80 h -80 z"/>
+    <path d="M10 -90 h 80 v 80 h -80 z"/>
+    <path d="M110 10 h 80 v 80 h -80 z"/>
+    <path d="M10 110 h 80 v 80 h -80 z"/>
+    <path d="M-90 10 h 80 v 80 h -80 z"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeOffCanvasPaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" height="1000" width="1000">
+    <!-- Should use width and height without a viewBox, keeping partially visible paths -->
+    <path d="M10 10 h 80 v 80 h -80 z"/>
+    <path d="M-100 -100 h 1200 v 1200 h -1200 z"/>
+    <path d="M-100 -100 l 200 200 v -200 z"/>
+    <path d="M-100 -100 h 50 v 50 h -50 z"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeOffCanvasPaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+    <!-- Should keep transformed paths -->
+    <g transform="translate(200 0)">
+        <path d="M110 10 h 80 v 80 h -80 z"/>
+    </g>
+    <path transform="translate(-200 0)" d="M110 10 h 80 v 80 h -80 z"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeOffCanvasPaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+    <!-- Should use the viewport of nested svg elements -->
+    <svg x="0" y="0" width="50" height="50" viewBox="200 200 100 100">
+        <path d="M210 210 h 80 v 80 h -80 z"/>
+        <path d="M10 10 h 80 v 80 h -80 z"/>
+    </svg>
+    <svg x="50" y="50" width="50%" height="50%">
+        <path d="M210 210 h 80 v 80 h -80 z"/>
+    </svg>
+    <path d="M210 210 h 80 v 80 h -80 z"/>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeOffCanvasPaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should keep paths in the area letterboxed by `meet` -->
+    <svg width="200" height="100" viewBox="0 0 100 100">
+        <path d="M-40 10 h 20 v 20 h -20 z"/>
+        <path d="M-80 10 h 20 v 20 h -20 z"/>
+    </svg>
+    <svg width="200" height="100" viewBox="0 0 100 100" preserveAspectRatio="xMinYMin meet">
+        <path d="M-40 10 h 20 v 20 h -20 z"/>
+        <path d="M120 10 h 20 v 20 h -20 z"/>
+    </svg>
+    <svg width="200" height="100" viewBox="0 0 100 100" preserveAspectRatio="xMidYMid slice">
+        <path d="M-40 10 h 20 v 20 h -20 z"/>
+    </svg>
+    <svg width="200" height="100" viewBox="0 0 100 100" preserveAspectRatio="none">
+        <path d="M-40 10 h 20 v 20 h -20 z"/>
+    </svg>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeOffCanvasPaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+    <!-- Should keep paths drawn in the viewport of a symbol, pattern, or marker -->
+    <symbol id="a" viewBox="200 200 100 100">
+        <path d="M210 210 h 80 v 80 h -80 z"/>
+    </symbol>
+    <pattern id="b" width="10" height="10" viewBox="200 200 10 10">
+        <path d="M200 200 h 10 v 10 h -10 z"/>
+    </pattern>
+    <marker id="c" viewBox="200 200 10 10">
+        <path d="M200 200 h 10 v 10 h -10 z"/>
+    </marker>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeOffCanvasPaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+    <!-- Should keep paths overflowing a nested svg -->
+    <svg width="50" height="50" viewBox="0 0 50 50" overflow="visible">
+        <path d="M60 10 h 20 v 20 h -20 z"/>
+    </svg>
+    <svg width="50" height="50" viewBox="0 0 50 50">
+        <path d="M60 10 h 20 v 20 h -20 z"/>
+    </svg>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeOffCanvasPaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+    <!-- Should keep paths with a stroke reaching into the viewBox -->
+    <path d="M110 10 h 20 v 20 h -20 z" stroke="black" stroke-width="30"/>
+    <path d="M110 10 h 20 v 20 h -20 z" style="stroke:black;stroke-width:30px"/>
+    <path d="M110 10 h 20 v 20 h -20 z" stroke="black"/>
+    <path d="M110 10 h 20 v 20 h -20 z" stroke-width="30"/>
+</svg>"#
+        )
+    )?);
+
+    Ok(())
+}
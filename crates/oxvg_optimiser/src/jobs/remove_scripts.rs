@@ -0,0 +1,159 @@
+use oxvg_ast::{
+    attribute::{Attr, Attributes},
+    element::Element,
+    name::Name,
+    node::{self, Node},
+    visitor::{Context, Visitor},
+};
+use oxvg_collections::collections::{AttrsGroups, Group};
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+/// Removes scripts from the document, such as `<script>` elements, event handler attributes
+/// like `onclick`, and `javascript:` links.
+pub struct RemoveScripts {
+    /// Whether to remove event handler attributes, such as `onclick`
+    remove_event_attrs: Option<bool>,
+    /// Whether to remove `<script>` elements, and to replace `javascript:` links with their
+    /// children
+    remove_script_elements: Option<bool>,
+}
+
+impl<E: Element> Visitor<E> for RemoveScripts {
+    type Error = String;
+
+    fn visit_foreign_content(&self) -> bool {
+        true
+    }
+
+    fn element(&mut self, element: &mut E, _context: &mut Context<E>) -> Result<(), String> {
+        if self.remove_script_elements.unwrap_or(true) && is_named(element, "script") {
+            element.remove();
+            return Ok(());
+        }
+
+        if self.remove_event_attrs.unwrap_or(true) {
+            element.attributes().retain(|attr| {
+                let is_event = attr.prefix().is_none() && is_event_attr(attr.local_name().as_ref());
+                !is_event
+            });
+        }
+        Ok(())
+    }
+
+    fn exit_element(&mut self, element: &mut E, _context: &Context<E>) -> Result<(), String> {
+        if !self.remove_script_elements.unwrap_or(true) || !is_named(element, "a") {
+            return Ok(());
+        }
+        let is_javascript_link = element.attributes().into_iter().any(|attr| {
+            attr.local_name().as_ref() == "href" && is_javascript_url(attr.value().as_ref())
+        });
+        if !is_javascript_link {
+            return Ok(());
+        }
+
+        log::debug!("replacing javascript link with its children");
+        for child in element.child_nodes() {
+            if child.node_type() == node::Type::Text {
+                child.remove();
+            }
+        }
+        element.flatten();
+        Ok(())
+    }
+}
+
+/// Whether the element has the local name in the SVG or XHTML namespace, where it's treated as
+/// that element by browsers
+fn is_named<E: Element>(element: &E, local_name: &str) -> bool {
+    element.local_name().as_ref() == local_name
+        && matches!(
+            element.qual_name().ns().as_ref(),
+            "" | "http://www.w3.org/2000/svg" | "http://www.w3.org/1999/xhtml"
+        )
+}
+
+/// Whether the URL has the `javascript:` scheme, ignoring its case along with the surrounding
+/// whitespace and the tabs and newlines which are stripped when the URL is parsed
+fn is_javascript_url(url: &str) -> bool {
+    const SCHEME: &str = "javascript:";
+    let scheme: String = url
+        .trim_matches(|c: char| c.is_ascii_control() || c == ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .take(SCHEME.len())
+        .collect();
+    scheme.eq_ignore_ascii_case(SCHEME)
+}
+
+fn is_event_attr(name: &str) -> bool {
+    AttrsGroups::AnimationEvent.matches(name)
+        || AttrsGroups::DocumentEvent.matches(name)
+        || AttrsGroups::DocumentElementEvent.matches(name)
+        || AttrsGroups::GlobalEvent.matches(name)
+        || AttrsGroups::GraphicalEvent.matches(name)
+}
+
+#[test]
+fn remove_scripts() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" onload="init()">
+    <script>alert('hello')</script>
+    <a href="javascript:alert('hello')">
+        <rect width="10" height="10" onclick="alert('hello')"/>
+    </a>
+    <a xlink:href="javascript:alert('hello')">
+        <circle r="10"/>
+    </a>
+    <a href="https://example.com">
+        <rect width="10" height="10" onmouseover="highlight()" data-onclick="kept"/>
+    </a>
+</svg>"#;
+
+    insta::assert_snapshot!(test_config(r#"{ "removeScripts": {} }"#, Some(svg))?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeScripts": { "removeEventAttrs": false } }"#,
+        Some(svg)
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeScripts": { "removeScriptElements": false } }"#,
+        Some(svg)
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeScripts": { "removeEventAttrs": false, "removeScriptElements": false } }"#,
+        Some(svg)
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeScripts": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should remove scripts from foreign content -->
+    <foreignObject width="100" height="100">
+        <div xmlns="http://www.w3.org/1999/xhtml">
+            <script>alert('hello')</script>
+            <button onclick="alert('hello')">Hello</button>
+        </div>
+    </foreignObject>
+</svg>"#
+        )
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeScripts": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:svg="http://www.w3.org/2000/svg">
+    <!-- Should remove scripts regardless of prefix or case -->
+    <svg:script>alert('hello')</svg:script>
+    <a href=" JavaScript:alert('hello')"><rect width="10" height="10"/></a>
+</svg>"#
+        )
+    )?);
+
+    Ok(())
+}
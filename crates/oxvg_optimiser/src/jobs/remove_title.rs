@@ -1,24 +1,38 @@
 use oxvg_ast::{
     element::Element,
+    node::{self, Node},
     visitor::{Context, PrepareOutcome, Visitor},
 };
 use serde::Deserialize;
 
+use super::ContextFlags;
+
 #[derive(Deserialize, Clone)]
-pub struct RemoveTitle(bool);
+#[serde(untagged)]
+/// Removes `<title>` elements.
+///
+/// Either a `bool` to enable the job, or its options.
+pub enum RemoveTitle {
+    Enabled(bool),
+    Options(Options),
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Options {
+    /// Whether to keep the first `<title>` of the root `<svg>`, as it's the accessible name of
+    /// the image.
+    keep_for_accessibility: Option<bool>,
+}
 
 impl<E: Element> Visitor<E> for RemoveTitle {
     type Error = String;
 
-    fn prepare(
-        &mut self,
-        _document: &E,
-        _context_flags: &mut oxvg_ast::visitor::ContextFlags,
-    ) -> oxvg_ast::visitor::PrepareOutcome {
-        if self.0 {
-            PrepareOutcome::none
-        } else {
+    fn prepare(&mut self, _document: &E, _context_flags: &mut ContextFlags) -> PrepareOutcome {
+        if matches!(self, Self::Enabled(false)) {
             PrepareOutcome::skip
+        } else {
+            PrepareOutcome::none
         }
     }
 
@@ -26,6 +40,13 @@ impl<E: Element> Visitor<E> for RemoveTitle {
         if element.prefix().is_some() || element.local_name().as_ref() != "title" {
             return Ok(());
         }
+        let keep_for_accessibility = match self {
+            Self::Enabled(_) => false,
+            Self::Options(options) => options.keep_for_accessibility.unwrap_or(false),
+        };
+        if keep_for_accessibility && is_first_of_root(element) {
+            return Ok(());
+        }
         element.remove();
 
         Ok(())
@@ -34,8 +55,31 @@ impl<E: Element> Visitor<E> for RemoveTitle {
 
 impl Default for RemoveTitle {
     fn default() -> Self {
-        Self(true)
+        Self::Enabled(true)
+    }
+}
+
+/// Returns whether the element is the first of its name among the children of the root `<svg>`
+pub(crate) fn is_first_of_root<E: Element>(element: &E) -> bool {
+    let Some(parent) = Element::parent_element(element) else {
+        return false;
+    };
+    if parent.prefix().is_some()
+        || parent.local_name().as_ref() != "svg"
+        || parent
+            .parent_node()
+            .is_some_and(|n| n.node_type() != node::Type::Document)
+    {
+        return false;
+    }
+    let mut sibling = element.previous_element_sibling();
+    while let Some(current) = sibling {
+        if current.prefix().is_none() && current.local_name() == element.local_name() {
+            return false;
+        }
+        sibling = current.previous_element_sibling();
     }
+    true
 }
 
 #[test]
@@ -52,5 +96,18 @@ fn remove_title() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeTitle": { "keepForAccessibility": true } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" role="img">
+    <title>Accessible name</title>
+    <title>...</title>
+    <g>
+        <title>...</title>
+    </g>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
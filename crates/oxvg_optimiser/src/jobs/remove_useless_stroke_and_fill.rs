@@ -1,7 +1,10 @@
 use std::cell::RefCell;
 
 use lightningcss::{
-    properties::{svg::SVGPaint, Property, PropertyId},
+    properties::{
+        svg::{SVGPaint, StrokeDasharray},
+        Property, PropertyId,
+    },
     traits::Zero,
     values::alpha::AlphaValue,
 };
@@ -65,6 +68,7 @@ impl<E: Element> Visitor<E> for RemoveUselessStrokeAndFill {
         }
 
         self.remove_stroke(element, context);
+        self.remove_dasharray(element, context);
         self.remove_fill(element, context);
         Ok(())
     }
@@ -190,6 +194,54 @@ impl RemoveUselessStrokeAndFill {
         }
     }
 
+    fn remove_dasharray<E: Element>(&self, element: &E, context: &Context<E>) {
+        if !self.stroke.unwrap_or(Self::DEFAULT_STROKE) {
+            return;
+        }
+
+        let computed_styles = &context.computed_styles;
+        get_computed_styles_factory!(computed_styles);
+
+        let Some(dasharray) = get_computed_styles!(StrokeDasharray) else {
+            return;
+        };
+        if dasharray.is_dynamic() {
+            return;
+        }
+        let Static::Attr(PresentationAttr::StrokeDasharray(value)) = dasharray.inner() else {
+            return;
+        };
+        if !is_dasharray_solid(&value) {
+            return;
+        }
+
+        let parent_dasharray = computed_styles
+            .inherited
+            .get(&Id::CSS(PropertyId::StrokeDasharray))
+            .or_else(|| {
+                computed_styles
+                    .inherited
+                    .get(&Id::Attr(PresentationAttrId::StrokeDasharray))
+            });
+        let is_parent_solid = parent_dasharray.is_none_or(|s| {
+            s.is_static()
+                && match s.inner() {
+                    Static::Css(Property::StrokeDasharray(value))
+                    | Static::Attr(PresentationAttr::StrokeDasharray(value)) => {
+                        is_dasharray_solid(&value)
+                    }
+                    _ => false,
+                }
+        });
+        if !is_parent_solid {
+            log::debug!("keeping dasharray, would inherit dashes from parent");
+            return;
+        }
+
+        log::debug!("removing dasharray producing a solid line");
+        element.remove_attribute_local(&"stroke-dasharray".into());
+    }
+
     fn remove_fill<E: Element>(&self, element: &E, context: &mut Context<E>) {
         if !self.fill.unwrap_or(Self::DEFAULT_FILL) {
             return;
@@ -239,7 +291,28 @@ impl RemoveUselessStrokeAndFill {
     }
 }
 
+/// Whether the dasharray renders the same as a solid line, i.e. when all of its
+/// values are zero or every gap is zero.
+fn is_dasharray_solid(value: &StrokeDasharray) -> bool {
+    let StrokeDasharray::Values(values) = value else {
+        return true;
+    };
+    if values.iter().all(Zero::is_zero) {
+        return true;
+    }
+    // An odd number of values is repeated to yield an even number of values
+    let len = if values.len() % 2 == 1 {
+        values.len() * 2
+    } else {
+        values.len()
+    };
+    (1..len)
+        .step_by(2)
+        .all(|i| values[i % values.len()].is_zero())
+}
+
 #[test]
+#[allow(clippy::too_many_lines)]
 fn remove_useless_stroke_and_fill() -> anyhow::Result<()> {
     use crate::test_config;
 
@@ -345,5 +418,45 @@ fn remove_useless_stroke_and_fill() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeUselessStrokeAndFill": {} }"#,
+        Some(
+            r##"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- remove dasharray that produces a solid line -->
+    <circle fill="red" stroke="#000" stroke-dasharray="0" cx="60" cy="60" r="50"/>
+    <circle fill="red" stroke="#000" stroke-dasharray="5 0" cx="60" cy="60" r="50"/>
+    <circle fill="red" stroke="#000" stroke-dasharray="none" cx="60" cy="60" r="50"/>
+    <circle fill="red" stroke="none" stroke-dasharray="5 5" cx="60" cy="60" r="50"/>
+    <!-- keep dash patterns -->
+    <circle fill="red" stroke="#000" stroke-dasharray="5" cx="60" cy="60" r="50"/>
+    <circle fill="red" stroke="#000" stroke-dasharray="5 0 5" cx="60" cy="60" r="50"/>
+    <g stroke-dasharray="5 5">
+        <circle fill="red" stroke="#000" stroke-dasharray="none" cx="60" cy="60" r="50"/>
+    </g>
+</svg>"##
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeUselessStrokeAndFill": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- keep `none` overriding an inherited stroke -->
+    <g stroke="red">
+        <path stroke="none" d="M0 0h10v10z"/>
+        <g>
+            <path stroke="none" d="M0 0h10v10z"/>
+        </g>
+    </g>
+    <!-- remove stroke with an inherited zero width -->
+    <g stroke-width="0">
+        <g>
+            <path stroke="red" d="M0 0h10v10z"/>
+        </g>
+    </g>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
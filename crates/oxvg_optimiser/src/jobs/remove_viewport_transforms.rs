@@ -0,0 +1,107 @@
+use lightningcss::{properties::transform::Matrix, traits::Parse};
+use oxvg_ast::{
+    element::Element,
+    name::Name,
+    style::SVGTransformList,
+    visitor::{Context, ContextFlags, PrepareOutcome, Visitor},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+/// Removes `transform` from the children of a nested `<svg>` or `<symbol>` when it resolves
+/// to the identity matrix, as the content is already drawn in the coordinates established
+/// by the viewport.
+///
+/// Transforms on the nested `<svg>` and `<symbol>` elements themselves are kept, since some
+/// renderers apply them as per SVG 2.
+pub struct RemoveViewportTransforms(bool);
+
+impl<E: Element> Visitor<E> for RemoveViewportTransforms {
+    type Error = String;
+
+    fn prepare(&mut self, _document: &E, _context_flags: &mut ContextFlags) -> PrepareOutcome {
+        if self.0 {
+            PrepareOutcome::none
+        } else {
+            PrepareOutcome::skip
+        }
+    }
+
+    fn element(&mut self, element: &mut E, context: &mut Context<E>) -> Result<(), String> {
+        if context.flags.contains(ContextFlags::within_foreign_object) {
+            return Ok(());
+        }
+        let Some(parent) = element.parent_element() else {
+            return Ok(());
+        };
+        let parent_name = parent.qual_name();
+        let is_viewport = parent_name.prefix().is_none()
+            && match parent_name.local_name().as_ref() {
+                "symbol" => true,
+                "svg" => parent.closest_local(&"svg".into()).is_some(),
+                _ => false,
+            };
+        if !is_viewport {
+            return Ok(());
+        }
+
+        let transform = "transform".into();
+        let is_identity = element
+            .get_attribute_local(&transform)
+            .is_some_and(|value| {
+                SVGTransformList::parse_string(value.as_ref())
+                    .ok()
+                    .and_then(|list| list.to_matrix_2d())
+                    .is_some_and(|matrix| {
+                        matrix
+                            == Matrix {
+                                a: 1.0,
+                                b: 0.0,
+                                c: 0.0,
+                                d: 1.0,
+                                e: 0.0,
+                                f: 0.0,
+                            }
+                    })
+            });
+        if is_identity {
+            log::debug!("removing identity transform within viewport");
+            element.remove_attribute_local(&transform);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn remove_viewport_transforms() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "removeViewportTransforms": true }"#,
+        Some(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 100 100">
+    <svg x="10" y="10" width="50" height="50" viewBox="0 0 10 10">
+        <!-- remove redundant transforms within the nested viewport -->
+        <g transform="translate(0 0)">
+            <rect width="5" height="5"/>
+        </g>
+        <rect width="5" height="5" transform="translate(2 3) translate(-2 -3)"/>
+        <!-- keep effective transforms -->
+        <rect width="5" height="5" transform="rotate(45)"/>
+        <!-- keep transform of symbol, which may be applied as per SVG 2 -->
+        <symbol id="a" viewBox="0 0 5 5" transform="translate(5 5) scale(2)">
+            <rect width="5" height="5" transform="scale(1)"/>
+        </symbol>
+        <g transform="translate(1 1)">
+            <use xlink:href="#a" width="5" height="5"/>
+        </g>
+    </svg>
+    <!-- keep transforms outside of nested viewports -->
+    <rect width="5" height="5" transform="translate(0 0)"/>
+</svg>"##
+        ),
+    )?);
+
+    Ok(())
+}
@@ -0,0 +1,342 @@
+use std::collections::{HashMap, HashSet};
+
+use derive_where::derive_where;
+use lightningcss::{
+    properties::PropertyId,
+    stylesheet::{ParserOptions, StyleAttribute},
+};
+use oxvg_ast::{
+    attribute::{Attr, Attributes},
+    document::Document,
+    element::Element,
+    name::Name,
+    visitor::{Context, ContextFlags, PrepareOutcome, Visitor},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Options {
+    /// The minimum length of a path's `d` attribute for it to be reused
+    min_length: Option<usize>,
+    /// The minimum number of identical paths needed for them to be reused
+    min_occurrences: Option<usize>,
+}
+
+#[derive(Clone)]
+#[derive_where(Default)]
+/// Moves paths which are drawn more than once to `<defs>`, replacing each of them with a
+/// `<use>` of the definition.
+///
+/// Paths are only considered identical when they have the same `d`, `fill`, `stroke`, and
+/// `pathLength`. A path with an `id` may be referenced for its shape, so it's only reused when it
+/// has no other attributes, in which case the definition takes its `id`. The `fill` and `stroke`
+/// are kept on each `<use>` when any of them also declares it in a `style`, as the declaration
+/// would otherwise be overridden by the attribute of the definition.
+/// Short paths can be cheaper to repeat than to reference, so `minLength` can be used to
+/// skip them.
+pub struct ReusePaths<E: Element> {
+    options: Options,
+    /// The first `<svg>`, which will hold the definitions
+    svg: Option<E>,
+    /// The index into `paths` of each key of `d`, `fill`, and `stroke`
+    keys: HashMap<String, usize>,
+    /// Each group of identical paths, in document order
+    paths: Vec<Vec<E>>,
+    /// The ids already used in the document
+    ids: HashSet<String>,
+}
+
+impl<E: Element> Visitor<E> for ReusePaths<E> {
+    type Error = String;
+
+    fn prepare(&mut self, document: &E, context_flags: &mut ContextFlags) -> PrepareOutcome {
+        context_flags.query_has_stylesheet(document);
+        if context_flags.contains(ContextFlags::has_stylesheet) {
+            log::debug!("ReusePaths::prepare: skipping, selectors may depend on `<path>`");
+            return PrepareOutcome::skip;
+        }
+        PrepareOutcome::none
+    }
+
+    fn element(&mut self, element: &mut E, context: &mut Context<E>) -> Result<(), String> {
+        if let Some(id) = element.get_attribute_local(&"id".into()) {
+            self.ids.insert(id.to_string());
+        }
+        if element.prefix().is_some() {
+            return Ok(());
+        }
+        let name = element.local_name().as_ref();
+        if name == "svg" && self.svg.is_none() {
+            self.svg = Some(element.clone());
+            return Ok(());
+        }
+        if name != "path"
+            || element.has_child_nodes()
+            || context.flags.contains(ContextFlags::within_defs)
+        {
+            return Ok(());
+        }
+
+        if element.has_attribute_local(&"id".into())
+            && element.attributes().into_iter().any(|attr| {
+                let name = attr.local_name().as_ref();
+                attr.prefix().is_some()
+                    || (name != "id" && !Self::SHARED_ATTRIBUTES.contains(&name))
+            })
+        {
+            return Ok(());
+        }
+        let Some(d) = element.get_attribute_local(&"d".into()) else {
+            return Ok(());
+        };
+        if d.as_ref().len() < self.options.min_length.unwrap_or(Self::DEFAULT_MIN_LENGTH) {
+            return Ok(());
+        }
+        drop(d);
+        let key = Self::SHARED_ATTRIBUTES
+            .iter()
+            .map(|name| {
+                element
+                    .get_attribute_local(&(*name).into())
+                    .map_or_else(String::new, |value| value.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(";");
+
+        if let Some(index) = self.keys.get(&key) {
+            self.paths[*index].push(element.clone());
+        } else {
+            self.keys.insert(key, self.paths.len());
+            self.paths.push(vec![element.clone()]);
+        }
+        Ok(())
+    }
+
+    fn exit_document(&mut self, document: &mut E, _context: &Context<E>) -> Result<(), String> {
+        let Some(svg) = &self.svg else {
+            return Ok(());
+        };
+        let min_occurrences = self
+            .options
+            .min_occurrences
+            .unwrap_or(Self::DEFAULT_MIN_OCCURRENCES);
+
+        let mut defs = None;
+        let mut index = 0;
+        for paths in &self.paths {
+            // only one path can lend its `id` to the definition
+            let original = paths
+                .iter()
+                .find(|path| path.has_attribute_local(&"id".into()));
+            let paths: Vec<_> = paths
+                .iter()
+                .filter(|path| Some(*path) == original || !path.has_attribute_local(&"id".into()))
+                .collect();
+            if paths.len() < min_occurrences {
+                continue;
+            }
+            let defs = defs.get_or_insert_with(|| Self::get_or_create_defs(document, svg));
+
+            let original_id = original.and_then(|original| {
+                let id = original.get_attribute_local(&"id".into())?.to_string();
+                original.remove_attribute_local(&"id".into());
+                Some(id)
+            });
+            let id = if let Some(id) = original_id {
+                id
+            } else {
+                loop {
+                    let id = format!("reuse-{index}");
+                    index += 1;
+                    if !self.ids.contains(&id) {
+                        break id;
+                    }
+                }
+            };
+            let reusable_path = document.as_document().create_element(E::Name::new_ns(
+                None,
+                "path".into(),
+                svg.qual_name().ns().clone(),
+            ));
+            reusable_path.set_attribute_local("id".into(), id.as_str().into());
+            let shared_attributes: Vec<_> = Self::SHARED_ATTRIBUTES
+                .into_iter()
+                .filter(|name| {
+                    !Self::PRESENTATION_ATTRIBUTES.contains(name)
+                        || !paths
+                            .iter()
+                            .any(|path| Self::is_declared_by_style(path, name))
+                })
+                .collect();
+            for name in &shared_attributes {
+                let name = (*name).into();
+                let Some(value) = paths[0].get_attribute_local(&name) else {
+                    continue;
+                };
+                let value = value.clone();
+                reusable_path.set_attribute_local(name, value);
+            }
+            defs.append(reusable_path.as_child());
+
+            let href = format!("#{id}");
+            for path in paths {
+                for name in &shared_attributes {
+                    path.remove_attribute_local(&(*name).into());
+                }
+                path.set_attribute(Self::xlink_href(), href.as_str().into());
+                path.clone().set_local_name("use".into());
+            }
+        }
+        if defs.is_some() {
+            let xmlns_xlink = <E::Attr as Attr>::Name::new_ns(
+                Some("xmlns".into()),
+                "xlink".into(),
+                XMLNS_NAMESPACE.into(),
+            );
+            if !svg.has_attribute(&xmlns_xlink) {
+                svg.set_attribute(xmlns_xlink, XLINK_NAMESPACE.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
+
+impl<E: Element> ReusePaths<E> {
+    const DEFAULT_MIN_LENGTH: usize = 0;
+    const DEFAULT_MIN_OCCURRENCES: usize = 2;
+    /// The attributes which are moved from each path to the definition
+    const SHARED_ATTRIBUTES: [&'static str; 4] = ["d", "fill", "stroke", "pathLength"];
+    /// The shared attributes which may be overridden by a `style`
+    const PRESENTATION_ATTRIBUTES: [&'static str; 2] = ["fill", "stroke"];
+
+    /// Returns whether the `style` of the path has a declaration for the property
+    fn is_declared_by_style(path: &E, name: &str) -> bool {
+        path.get_attribute_local(&"style".into())
+            .is_some_and(|code| {
+                StyleAttribute::parse(code.as_ref(), ParserOptions::default())
+                    .is_ok_and(|style| style.declarations.get(&PropertyId::from(name)).is_some())
+            })
+    }
+
+    fn xlink_href() -> <E::Attr as Attr>::Name {
+        <E::Attr as Attr>::Name::new_ns(Some("xlink".into()), "href".into(), XLINK_NAMESPACE.into())
+    }
+
+    /// Returns the `<defs>` of the `<svg>`, or a new one inserted as its first child
+    fn get_or_create_defs(document: &E, svg: &E) -> E {
+        if let Some(defs) = svg
+            .children()
+            .into_iter()
+            .find(|child| child.prefix().is_none() && child.local_name().as_ref() == "defs")
+        {
+            return defs;
+        }
+        let defs = document.as_document().create_element(E::Name::new_ns(
+            None,
+            "defs".into(),
+            svg.qual_name().ns().clone(),
+        ));
+        svg.prepend(defs.as_child());
+        defs
+    }
+}
+
+impl<'de, E: Element> Deserialize<'de> for ReusePaths<E> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let options = Options::deserialize(deserializer)?;
+        Ok(Self {
+            options,
+            ..Self::default()
+        })
+    }
+}
+
+#[test]
+fn reuse_paths() -> anyhow::Result<()> {
+    use crate::test_config;
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "reusePaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should reuse paths with the same `d`, `fill`, and `stroke` -->
+    <path d="M0 0h10v10H0z" fill="red" transform="translate(10)"/>
+    <path d="M0 0h10v10H0z" fill="red" transform="translate(20)"/>
+    <path d="M0 0h10v10H0z" fill="blue"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "reusePaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should add to existing defs and avoid existing ids -->
+    <defs>
+        <linearGradient id="reuse-0"/>
+    </defs>
+    <path d="M0 0h10v10H0z" id="a" transform="translate(10)"/>
+    <path d="M0 0h10v10H0z"/>
+    <path d="M0 0h10v10H0z"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "reusePaths": { "minLength": 4 } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should not reuse paths shorter than `minLength` -->
+    <path d="M0z"/>
+    <path d="M0z"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "reusePaths": { "minOccurrences": 3 } }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should only reuse paths occurring at least `minOccurrences` times -->
+    <path d="M0 0h10v10H0z"/>
+    <path d="M0 0h10v10H0z"/>
+    <path d="M0 0h20v20H0z"/>
+    <path d="M0 0h20v20H0z"/>
+    <path d="M0 0h20v20H0z"/>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "reusePaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should move `id` and `pathLength` to the definition -->
+    <path d="M0 0h10v10H0z" pathLength="10" id="a"/>
+    <path d="M0 0h10v10H0z" pathLength="10" transform="translate(10)"/>
+    <path d="M0 0h10v10H0z" pathLength="10" id="b" transform="translate(20)"/>
+    <text><textPath href="#a">text</textPath></text>
+</svg>"#
+        ),
+    )?);
+
+    insta::assert_snapshot!(test_config(
+        r#"{ "reusePaths": {} }"#,
+        Some(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+    <!-- Should keep `fill` on each use when a `style` declares it -->
+    <path d="M0 0h10v10H0z" fill="blue" stroke="green"/>
+    <path d="M0 0h10v10H0z" fill="blue" stroke="green" style="fill:red"/>
+</svg>"#
+        ),
+    )?);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use oxvg_ast::{
     attribute::Attributes,
     element::Element,
+    name::Name,
     visitor::{Context, Visitor},
 };
 use serde::Deserialize;
@@ -18,6 +19,8 @@ enum XMLNSOrder {
 pub struct SortAttrs {
     order: Option<Vec<String>>,
     xmlns_order: Option<XMLNSOrder>,
+    /// Qualified names of attributes pinned to the front of the root `<svg>`, in the given order
+    root_order: Option<Vec<String>>,
 }
 
 impl<E: Element> Visitor<E> for SortAttrs {
@@ -28,10 +31,40 @@ impl<E: Element> Visitor<E> for SortAttrs {
         let xmlns_order = self.xmlns_order.is_none() || self.xmlns_order == Some(XMLNSOrder::Front);
         element.attributes().sort(order, xmlns_order);
 
+        if let Some(root_order) = &self.root_order {
+            if element.is_root() && element.local_name().as_ref() == "svg" {
+                Self::pin_attributes(element, root_order);
+            }
+        }
+
         Ok(())
     }
 }
 
+impl SortAttrs {
+    /// Moves the attributes with the given names to the front, keeping the order of the rest
+    fn pin_attributes<E: Element>(element: &E, pinned_order: &[String]) {
+        let attributes = element.attributes();
+        let pinned: Vec<_> = pinned_order
+            .iter()
+            .filter_map(|name| attributes.remove_named_item(&E::Name::parse(name)))
+            .collect();
+        if pinned.is_empty() {
+            return;
+        }
+
+        let rest: Vec<_> = element
+            .attributes()
+            .into_iter()
+            .map(|attr| attr.clone())
+            .collect();
+        attributes.retain(|_| false);
+        for attr in pinned.into_iter().chain(rest) {
+            attributes.set_named_item(attr);
+        }
+    }
+}
+
 lazy_static! {
     pub static ref DEFAULT_ORDER: Vec<String> = vec![
         String::from("id"),
@@ -99,5 +132,15 @@ fn sort_attrs() -> anyhow::Result<()> {
         ),
     )?);
 
+    insta::assert_snapshot!(test_config(
+        r#"{ "sortAttrs": { "rootOrder": ["xmlns", "viewBox", "width", "height"] } }"#,
+        Some(
+            r#"<svg height="10" fill="red" width="10" viewBox="0 0 10 10" xmlns="http://www.w3.org/2000/svg">
+    <!-- pin root attributes in a canonical order -->
+    <svg height="5" viewBox="0 0 5 5" width="5"/>
+</svg>"#
+        ),
+    )?);
+
     Ok(())
 }
@@ -0,0 +1,69 @@
+use oxvg_ast::{
+    attribute::{Attr, Attributes},
+    element::Element,
+    node::Type,
+};
+use oxvg_collections::collections::{ElementGroup, Group};
+
+/// Whether the element is an animation element changing the attribute of the given name
+pub fn is_animation_of(element: &impl Element, name: &str) -> bool {
+    let local_name = element.local_name();
+    let local_name: &str = local_name.as_ref();
+    Group::set(&ElementGroup::Animation).contains(local_name)
+        && element
+            .get_attribute_local(&"attributeName".into())
+            .is_some_and(|attr| attr.as_ref() == name)
+}
+
+/// Whether the element or any of its descendants is an animation element changing the
+/// attribute of the given name
+pub fn has_animated_attr(element: &impl Element, name: &str) -> bool {
+    std::iter::once(element.clone())
+        .chain(element.breadth_first())
+        .any(|child| is_animation_of(&child, name))
+}
+
+/// Returns the id referenced by the `href` or `xlink:href` of an animation element
+fn animation_href<E: Element>(animation: &E) -> Option<String> {
+    animation
+        .attributes()
+        .into_iter()
+        .find(|attr| attr.local_name().as_ref() == "href")
+        .map(|attr| attr.value().to_string())
+}
+
+/// Whether an animation changing the attribute of the given name targets the element, either as
+/// a child without an `href` or through an `href` to the element's id
+pub fn is_animation_target<E: Element>(element: &E, name: &str) -> bool {
+    let is_child_target = element
+        .children()
+        .iter()
+        .any(|child| is_animation_of(child, name) && animation_href(child).is_none());
+    if is_child_target {
+        return true;
+    }
+
+    let Some(id) = element.get_attribute_local(&"id".into()) else {
+        return false;
+    };
+    let Some(document) = element.document() else {
+        return false;
+    };
+    let id: &str = id.as_ref();
+    document.breadth_first().any(|animation| {
+        is_animation_of(&animation, name)
+            && animation_href(&animation).is_some_and(|href| href.strip_prefix('#') == Some(id))
+    })
+}
+
+/// Whether the inherited attribute of the given name may be changed by an animation targeting
+/// the element or one of its ancestors
+pub fn has_animated_inherited_attr<E: Element>(element: &E, name: &str) -> bool {
+    std::iter::once(element.clone())
+        .chain(std::iter::successors(
+            Element::parent_element(element),
+            Element::parent_element,
+        ))
+        .take_while(|ancestor| ancestor.node_type() == Type::Element)
+        .any(|ancestor| is_animation_target(&ancestor, name))
+}
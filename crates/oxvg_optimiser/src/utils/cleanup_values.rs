@@ -29,20 +29,26 @@ pub trait CleanupValues {
         &self,
         attr: &mut impl DerefMut<Target = A>,
     ) -> anyhow::Result<A::Atom> {
-        let mut rounded_list: Vec<String> = Vec::new();
-        let Options {
-            float_precision,
-            do_convert_to_px,
-            leading_zero,
-            default_px,
-        } = self.get_options();
+        let name = attr.local_name();
+        if name.as_ref() == "viewBox" {
+            return Ok(match self.round_view_box(attr.value().as_ref())? {
+                Some(view_box) => view_box.into(),
+                None => attr.value().clone(),
+            });
+        }
+        if matches!(self.get_mode(), Mode::List) && name.as_ref() == "points" {
+            if let Some(points) = self.round_points(attr.value().as_ref())? {
+                return Ok(points.into());
+            }
+        }
 
+        let mut rounded_list: Vec<String> = Vec::new();
         for value in self.get_mode().separate_value(attr) {
             if value.is_empty() {
                 continue;
             }
 
-            let Some(captures) = NUMERIC_VALUES.captures(value) else {
+            let Some(number) = self.round_value(value)? else {
                 if value.contains("new") {
                     rounded_list.push("new".to_string());
                 } else {
@@ -50,40 +56,105 @@ pub trait CleanupValues {
                 }
                 continue;
             };
+            rounded_list.push(number);
+        }
+        Ok(rounded_list.join(" ").into())
+    }
+
+    /// Rounds a numeric value, or returns `None` if the value isn't numeric
+    fn round_value(&self, value: &str) -> anyhow::Result<Option<String>> {
+        let Options {
+            float_precision,
+            do_convert_to_px,
+            leading_zero,
+            default_px,
+        } = self.get_options();
+
+        let Some(captures) = NUMERIC_VALUES.captures(value) else {
+            return Ok(None);
+        };
 
-            let mut number: f64 = captures.get(1).unwrap().as_str().parse()?;
-            let exponent = captures.get(2).map(|capture| capture.as_str());
-            let mut unit = captures.get(3).map(|capture| capture.as_str());
-            if do_convert_to_px {
-                if let Some(unwrapped_unit) = unit {
-                    let new_number = convert_to_px(number, unwrapped_unit);
-                    if (new_number - number).abs() > f64::EPSILON {
-                        unit = Some("px");
-                        number = new_number;
-                    }
+        let mut number: f64 = captures.get(1).unwrap().as_str().parse()?;
+        let mut unit = captures.get(3).map(|capture| capture.as_str());
+        if do_convert_to_px {
+            if let Some(unwrapped_unit) = unit {
+                let new_number = convert_to_px(number, unwrapped_unit);
+                if (new_number - number).abs() > f64::EPSILON {
+                    unit = Some("px");
+                    number = new_number;
                 }
             }
+        }
 
-            let mut number = format!("{number:.float_precision$}");
-            if leading_zero {
-                number = number
-                    .trim_end_matches('0')
-                    .trim_end_matches('.')
-                    .to_string();
-                if number.starts_with("0.") {
-                    number.remove(0);
-                } else if number.starts_with("-0.") {
-                    number.remove(1);
-                }
+        let mut number = format!("{number:.float_precision$}");
+        if number.contains('.') {
+            number = number
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_string();
+        }
+        if number == "-0" {
+            number = String::from("0");
+        }
+        if leading_zero {
+            if number.starts_with("0.") {
+                number.remove(0);
+            } else if number.starts_with("-0.") {
+                number.remove(1);
             }
+        }
 
-            if default_px && matches!(unit, Some("px")) {
-                unit = None;
+        if default_px && matches!(unit, Some("px")) {
+            unit = None;
+        }
+
+        // The exponent has already been applied to the number
+        Ok(Some(number + unit.unwrap_or("")))
+    }
+
+    /// Rounds each coordinate of a `points` list, joining them with the shortest separators that
+    /// keep each pair apart.
+    ///
+    /// Returns `None` if the list contains anything other than numbers and separators.
+    fn round_points(&self, value: &str) -> anyhow::Result<Option<String>> {
+        let mut numbers = Vec::new();
+        let mut end = 0;
+        for number in POINTS_NUMBER.find_iter(value) {
+            let separator = &value[end..number.start()];
+            if !separator.chars().all(|c| c.is_whitespace() || c == ',') {
+                return Ok(None);
             }
+            end = number.end();
+            let Some(number) = self.round_value(number.as_str())? else {
+                return Ok(None);
+            };
+            numbers.push(number);
+        }
+        if !value[end..].chars().all(|c| c.is_whitespace() || c == ',') {
+            return Ok(None);
+        }
+        Ok(Some(join_points(numbers)))
+    }
 
-            rounded_list.push(number + exponent.unwrap_or("") + unit.unwrap_or(""));
+    /// Rounds each number of a `viewBox`, separating them with spaces.
+    ///
+    /// Returns `None` if the `viewBox` contains anything other than numbers and separators, such
+    /// as units, since it's invalid and shouldn't be made valid.
+    fn round_view_box(&self, value: &str) -> anyhow::Result<Option<String>> {
+        let mut numbers = Vec::with_capacity(4);
+        for number in SEPARATOR.split(value.trim()) {
+            if POINTS_NUMBER
+                .find(number)
+                .is_none_or(|found| found.as_str() != number)
+            {
+                return Ok(None);
+            }
+            let Some(number) = self.round_value(number)? else {
+                return Ok(None);
+            };
+            numbers.push(number);
         }
-        Ok(rounded_list.join(" ").into())
+        Ok(Some(numbers.join(" ")))
     }
 
     fn element<E: Element>(&self, element: &E, _context: &mut Context<E>) -> Result<(), String> {
@@ -148,6 +219,23 @@ impl Mode {
     }
 }
 
+/// Joins the coordinates of a `points` list, separating each pair with a space.
+///
+/// The `x` and `y` of a pair are only packed together when `y` is negative, since some
+/// renderers reject lists packed across pairs or with `.` as a separator.
+fn join_points(numbers: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    let mut result = String::new();
+    for (i, number) in numbers.into_iter().enumerate() {
+        let number = number.as_ref();
+        let is_packed = i % 2 == 1 && number.starts_with('-');
+        if i > 0 && !is_packed {
+            result.push(' ');
+        }
+        result.push_str(number);
+    }
+    result
+}
+
 pub fn convert_to_px(number: f64, unit: &str) -> f64 {
     number
         * match unit {
@@ -160,8 +248,23 @@ pub fn convert_to_px(number: f64, unit: &str) -> f64 {
         }
 }
 
+#[test]
+fn join_points_pairs() {
+    assert_eq!(
+        join_points(["10", "-5", "-.5", ".25", ".5", ".5"]),
+        "10-5 -.5 .25 .5 .5"
+    );
+    // A negative `x` is still separated from the previous pair
+    assert_eq!(
+        join_points(["1", "-1", "-1", "-1", "-1", "1"]),
+        "1-1 -1-1 -1 1"
+    );
+}
+
 lazy_static! {
     static ref SEPARATOR: regex::Regex = regex::Regex::new(r"\s+,?\s*|,\s*").unwrap();
+    pub static ref POINTS_NUMBER: regex::Regex =
+        regex::Regex::new(r"[-+]?\d*\.?\d+([eE][-+]?\d+)?").unwrap();
     static ref NUMERIC_VALUES: regex::Regex =
         regex::Regex::new(r"^([-+]?\d*\.?\d+([eE][-+]?\d+)?)(px|pt|pc|mm|cm|m|in|ft|em|ex|%)?$")
             .unwrap();
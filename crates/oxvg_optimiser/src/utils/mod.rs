@@ -1,5 +1,6 @@
+mod animation;
 pub(crate) mod cleanup_values;
-mod find_references;
 pub(crate) mod transform;
+pub(crate) mod view_box;
 
-pub(crate) use find_references::find_references;
+pub(crate) use animation::{has_animated_attr, has_animated_inherited_attr, is_animation_target};
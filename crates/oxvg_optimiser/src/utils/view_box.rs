@@ -0,0 +1,218 @@
+use std::fmt::Display;
+
+use oxvg_ast::element::Element;
+use oxvg_path::{command, Path};
+
+use super::cleanup_values::POINTS_NUMBER;
+
+#[derive(Clone, Debug, PartialEq)]
+/// The area of user space established by the `viewBox` of an `<svg>`
+pub struct ViewBox {
+    pub left: f64,
+    pub top: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ViewBox {
+    /// Returns the viewport from the `viewBox`, or `width` and `height`, of an `<svg>`
+    ///
+    /// When the `viewBox` is letterboxed into the `width` and `height` by `preserveAspectRatio`,
+    /// the viewport is expanded to include the area around the `viewBox`.
+    pub fn new(element: &impl Element) -> Option<Self> {
+        let size = match (
+            element.get_attribute_local(&"width".into()),
+            element.get_attribute_local(&"height".into()),
+        ) {
+            (Some(width), Some(height)) => {
+                Some(format!("0 0 {} {}", width.as_ref(), height.as_ref()))
+            }
+            _ => None,
+        };
+        let size = size.and_then(|size| Self::parse(&size));
+        let Some(view_box) = element.get_attribute_local(&"viewBox".into()) else {
+            return size;
+        };
+        let mut view_box = Self::parse(view_box.as_ref())?;
+        if let Some(size) = size {
+            let preserve_aspect_ratio = element
+                .get_attribute_local(&"preserveAspectRatio".into())
+                .map(|value| value.to_string());
+            view_box.letterbox(&size, preserve_aspect_ratio.as_deref().unwrap_or_default());
+        }
+        Some(view_box)
+    }
+
+    /// Parses a `viewBox`, where the numbers may be packed together or have a `px` unit
+    pub fn parse(view_box: &str) -> Option<Self> {
+        let view_box = view_box.replace("px", " ");
+        let is_separator = |s: &str| s.chars().all(|c| c == ',' || c.is_whitespace());
+        let mut values = Vec::with_capacity(4);
+        let mut end = 0;
+        for number in POINTS_NUMBER.find_iter(&view_box) {
+            if !is_separator(&view_box[end..number.start()]) {
+                return None;
+            }
+            end = number.end();
+            values.push(number.as_str().parse::<f64>().ok()?);
+        }
+        if !is_separator(&view_box[end..]) {
+            return None;
+        }
+        let [left, top, width, height] = values.as_slice() else {
+            return None;
+        };
+        let view_box = Self {
+            left: *left,
+            top: *top,
+            width: *width,
+            height: *height,
+        };
+        if view_box.width < 0.0 || view_box.height < 0.0 {
+            return None;
+        }
+        Some(view_box)
+    }
+
+    /// Expands the `viewBox` to the area visible when it's fit within the `size` with `meet`.
+    ///
+    /// The area visible with `slice` or `none` is never larger than the `viewBox`, so it's left
+    /// as-is.
+    pub fn letterbox(&mut self, size: &Self, preserve_aspect_ratio: &str) {
+        let mut parts = preserve_aspect_ratio
+            .split_whitespace()
+            .skip_while(|part| *part == "defer");
+        let align = parts.next().unwrap_or("xMidYMid");
+        let meet_or_slice = parts.next().unwrap_or("meet");
+        if align == "none" || meet_or_slice != "meet" || self.width == 0.0 || self.height == 0.0 {
+            return;
+        }
+        let Some((align_x, align_y)) = align
+            .strip_prefix('x')
+            .and_then(|align| align.split_once('Y'))
+        else {
+            return;
+        };
+
+        let scale = f64::min(size.width / self.width, size.height / self.height);
+        let mut visible = ViewBox {
+            left: 0.0,
+            top: 0.0,
+            ..*size
+        };
+        visible.scale(scale.recip(), scale.recip());
+        let Some(left) = Self::align(align_x, visible.width - self.width) else {
+            return;
+        };
+        let Some(top) = Self::align(align_y, visible.height - self.height) else {
+            return;
+        };
+        visible.translate(self.left - left, self.top - top);
+        *self = visible;
+    }
+
+    /// Returns the amount of `space` placed before the `viewBox` for an alignment
+    fn align(align: &str, space: f64) -> Option<f64> {
+        match align {
+            "Min" => Some(0.0),
+            "Mid" => Some(space / 2.0),
+            "Max" => Some(space),
+            _ => None,
+        }
+    }
+
+    /// Moves the `viewBox` by `dx` and `dy`
+    pub fn translate(&mut self, dx: f64, dy: f64) {
+        self.left += dx;
+        self.top += dy;
+    }
+
+    /// Scales the `viewBox` about the origin by `sx` and `sy`
+    pub fn scale(&mut self, sx: f64, sy: f64) {
+        self.left *= sx;
+        self.top *= sy;
+        self.width *= sx;
+        self.height *= sy;
+    }
+
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.left
+            && x <= self.left + self.width
+            && y >= self.top
+            && y <= self.top + self.height
+    }
+
+    pub fn path(&self) -> Path {
+        Path(vec![
+            command::Data::MoveTo([self.left, self.top]),
+            command::Data::HorizontalLineBy([self.width]),
+            command::Data::VerticalLineBy([self.height]),
+            command::Data::HorizontalLineTo([self.left]),
+            command::Data::ClosePath,
+        ])
+    }
+}
+
+impl Display for ViewBox {
+    /// Writes the `viewBox` separated by spaces and without leading zeros
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let numbers = [self.left, self.top, self.width, self.height].map(|n| {
+            let mut n = format!("{}", n + 0.0);
+            if n.starts_with("0.") {
+                n.remove(0);
+            } else if n.starts_with("-0.") {
+                n.remove(1);
+            }
+            n
+        });
+        f.write_str(&numbers.join(" "))
+    }
+}
+
+#[test]
+fn view_box() {
+    let mut view_box = ViewBox::parse("0.0 0.0 24.00 24").unwrap();
+    assert_eq!(view_box.to_string(), "0 0 24 24");
+
+    view_box.translate(-0.5, -12.0);
+    assert_eq!(view_box.to_string(), "-.5 -12 24 24");
+
+    view_box.scale(0.5, 2.0);
+    assert_eq!(view_box.to_string(), "-.25 -24 12 48");
+
+    assert_eq!(
+        ViewBox::parse("-.5,.5 10px 10").map(|v| v.to_string()),
+        Some(String::from("-.5 .5 10 10"))
+    );
+    assert_eq!(ViewBox::parse("-.25-24 12 48"), Some(view_box));
+    assert_eq!(ViewBox::parse("0 0 -10 10"), None);
+    assert_eq!(ViewBox::parse("0 0 10"), None);
+    assert_eq!(ViewBox::parse("0 0 10 10 10"), None);
+    assert_eq!(ViewBox::parse("0 0 10em 10"), None);
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn view_box_round_trip() {
+    // A fixed xorshift sequence, so that failing cases can be reproduced
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut number = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let magnitude = [1.0, 1e-3, 1e3, 1e5][(state % 4) as usize];
+        let value = ((state >> 8) % 20_001) as f64 / 1e4 - 1.0;
+        value * magnitude
+    };
+
+    for _ in 0..500 {
+        let view_box = ViewBox {
+            left: number(),
+            top: number(),
+            width: number().abs(),
+            height: number().abs(),
+        };
+        let string = view_box.to_string();
+        assert_eq!(ViewBox::parse(&string), Some(view_box), "`{string}`");
+    }
+}
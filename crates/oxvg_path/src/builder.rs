@@ -0,0 +1,255 @@
+//! A builder for creating paths from code, rather than from a path definition.
+use crate::{command::Data, Path};
+
+#[derive(Debug, Clone, Default)]
+/// Builds a [Path] one command at a time.
+///
+/// Each method appends the command of the same name, where methods ending in `_to` use
+/// absolute coordinates and methods ending in `_by` use coordinates relative to the current
+/// point.
+///
+/// # Example
+///
+/// ```
+/// use oxvg_path::builder::PathBuilder;
+///
+/// let path = PathBuilder::new()
+///     .move_to(10.0, 10.0)
+///     .line_to(20.0, 10.0)
+///     .curve_to(25.0, 10.0, 30.0, 15.0, 30.0, 20.0)
+///     .close()
+///     .build()
+///     .unwrap();
+/// assert_eq!(path.to_string(), "M10 10L20 10C25 10 30 15 30 20Z");
+/// ```
+///
+/// A path must start with a move, so building fails otherwise
+///
+/// ```
+/// use oxvg_path::builder::PathBuilder;
+///
+/// assert!(PathBuilder::new().line_to(10.0, 10.0).build().is_err());
+/// ```
+pub struct PathBuilder {
+    path: Vec<Data>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    InvalidFirstCommand,
+}
+
+impl PathBuilder {
+    /// Creates a builder for an empty path
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a command to the path
+    #[must_use]
+    pub fn command(mut self, command: Data) -> Self {
+        self.path.push(command);
+        self
+    }
+
+    /// Starts a new subpath at `x`, `y`
+    #[must_use]
+    pub fn move_to(self, x: f64, y: f64) -> Self {
+        self.command(Data::MoveTo([x, y]))
+    }
+
+    /// Starts a new subpath offset from the current point
+    #[must_use]
+    pub fn move_by(self, dx: f64, dy: f64) -> Self {
+        self.command(Data::MoveBy([dx, dy]))
+    }
+
+    /// Draws a line to `x`, `y`
+    #[must_use]
+    pub fn line_to(self, x: f64, y: f64) -> Self {
+        self.command(Data::LineTo([x, y]))
+    }
+
+    /// Draws a line offset from the current point
+    #[must_use]
+    pub fn line_by(self, dx: f64, dy: f64) -> Self {
+        self.command(Data::LineBy([dx, dy]))
+    }
+
+    /// Draws a horizontal line to `x`
+    #[must_use]
+    pub fn horizontal_line_to(self, x: f64) -> Self {
+        self.command(Data::HorizontalLineTo([x]))
+    }
+
+    /// Draws a horizontal line offset from the current point
+    #[must_use]
+    pub fn horizontal_line_by(self, dx: f64) -> Self {
+        self.command(Data::HorizontalLineBy([dx]))
+    }
+
+    /// Draws a vertical line to `y`
+    #[must_use]
+    pub fn vertical_line_to(self, y: f64) -> Self {
+        self.command(Data::VerticalLineTo([y]))
+    }
+
+    /// Draws a vertical line offset from the current point
+    #[must_use]
+    pub fn vertical_line_by(self, dy: f64) -> Self {
+        self.command(Data::VerticalLineBy([dy]))
+    }
+
+    /// Draws a cubic bezier curve to `x`, `y` with the control points `x1`, `y1` and `x2`, `y2`
+    #[must_use]
+    pub fn curve_to(self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64) -> Self {
+        self.command(Data::CubicBezierTo([x1, y1, x2, y2, x, y]))
+    }
+
+    /// Draws a cubic bezier curve where each point is offset from the current point
+    #[must_use]
+    pub fn curve_by(self, dx1: f64, dy1: f64, dx2: f64, dy2: f64, dx: f64, dy: f64) -> Self {
+        self.command(Data::CubicBezierBy([dx1, dy1, dx2, dy2, dx, dy]))
+    }
+
+    /// Draws a cubic bezier curve to `x`, `y`, with a first control point reflected from the
+    /// previous curve
+    #[must_use]
+    pub fn smooth_curve_to(self, x2: f64, y2: f64, x: f64, y: f64) -> Self {
+        self.command(Data::SmoothBezierTo([x2, y2, x, y]))
+    }
+
+    /// Draws a smooth cubic bezier curve where each point is offset from the current point
+    #[must_use]
+    pub fn smooth_curve_by(self, dx2: f64, dy2: f64, dx: f64, dy: f64) -> Self {
+        self.command(Data::SmoothBezierBy([dx2, dy2, dx, dy]))
+    }
+
+    /// Draws a quadratic bezier curve to `x`, `y` with the control point `x1`, `y1`
+    #[must_use]
+    pub fn quadratic_curve_to(self, x1: f64, y1: f64, x: f64, y: f64) -> Self {
+        self.command(Data::QuadraticBezierTo([x1, y1, x, y]))
+    }
+
+    /// Draws a quadratic bezier curve where each point is offset from the current point
+    #[must_use]
+    pub fn quadratic_curve_by(self, dx1: f64, dy1: f64, dx: f64, dy: f64) -> Self {
+        self.command(Data::QuadraticBezierBy([dx1, dy1, dx, dy]))
+    }
+
+    /// Draws a quadratic bezier curve to `x`, `y`, with a control point reflected from the
+    /// previous curve
+    #[must_use]
+    pub fn smooth_quadratic_curve_to(self, x: f64, y: f64) -> Self {
+        self.command(Data::SmoothQuadraticBezierTo([x, y]))
+    }
+
+    /// Draws a smooth quadratic bezier curve offset from the current point
+    #[must_use]
+    pub fn smooth_quadratic_curve_by(self, dx: f64, dy: f64) -> Self {
+        self.command(Data::SmoothQuadraticBezierBy([dx, dy]))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    /// Draws an elliptical arc to `x`, `y`
+    #[must_use]
+    pub fn arc_to(
+        self,
+        rx: f64,
+        ry: f64,
+        x_axis_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        x: f64,
+        y: f64,
+    ) -> Self {
+        self.command(Data::ArcTo([
+            rx,
+            ry,
+            x_axis_rotation,
+            f64::from(u8::from(large_arc)),
+            f64::from(u8::from(sweep)),
+            x,
+            y,
+        ]))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    /// Draws an elliptical arc to a point offset from the current point
+    #[must_use]
+    pub fn arc_by(
+        self,
+        rx: f64,
+        ry: f64,
+        x_axis_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        dx: f64,
+        dy: f64,
+    ) -> Self {
+        self.command(Data::ArcBy([
+            rx,
+            ry,
+            x_axis_rotation,
+            f64::from(u8::from(large_arc)),
+            f64::from(u8::from(sweep)),
+            dx,
+            dy,
+        ]))
+    }
+
+    /// Closes the current subpath
+    #[must_use]
+    pub fn close(self) -> Self {
+        self.command(Data::ClosePath)
+    }
+
+    /// Returns the built path
+    ///
+    /// # Errors
+    /// If the path isn't empty and doesn't start with a move
+    pub fn build(self) -> Result<Path, Error> {
+        match self.path.first() {
+            None | Some(Data::MoveTo(_) | Data::MoveBy(_)) => Ok(Path(self.path)),
+            Some(_) => Err(Error::InvalidFirstCommand),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidFirstCommand => f.write_str("Expected path to start with `m` or `M`"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[test]
+#[cfg(feature = "default")]
+fn test_path_builder() {
+    let built = PathBuilder::new()
+        .move_to(0.0, 0.0)
+        .horizontal_line_by(10.0)
+        .vertical_line_to(10.0)
+        .smooth_curve_by(5.0, 5.0, -10.0, 0.0)
+        .quadratic_curve_to(-5.0, 5.0, 0.0, 0.0)
+        .smooth_quadratic_curve_by(5.0, 5.0)
+        .arc_to(5.0, 5.0, 0.0, false, true, 0.0, 20.0)
+        .move_by(5.0, 5.0)
+        .line_by(1.0, 1.0)
+        .close()
+        .build()
+        .unwrap();
+    assert_eq!(
+        built.to_string(),
+        "M0 0h10V10s5 5-10 0Q-5 5 0 0t5 5A5 5 0 0 1 0 20m5 5l1 1Z"
+    );
+
+    assert_eq!(PathBuilder::new().build().unwrap(), Path(vec![]));
+    assert_eq!(
+        PathBuilder::new().close().build(),
+        Err(Error::InvalidFirstCommand)
+    );
+}
@@ -111,6 +111,18 @@ pub struct Position {
     pub s_data: Option<Curve>,
 }
 
+#[derive(Debug, Clone)]
+/// A command with absolute coordinates, as yielded by
+/// [`Path::iter_absolute`](crate::Path::iter_absolute)
+pub struct AbsCommand {
+    /// The absolute equivalent of the command. Shorthands, such as `H` or `S`, are kept as-is.
+    pub command: Data,
+    /// The current point before the command is drawn
+    pub start: Point,
+    /// The current point after the command is drawn
+    pub end: Point,
+}
+
 impl Data {
     pub fn id(&self) -> ID {
         match self {
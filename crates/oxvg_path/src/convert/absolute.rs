@@ -0,0 +1,109 @@
+use crate::{command, Path};
+
+/// Convert path data to absolute coordinates, expanding shorthands such as `H`, `V`, `S`, and
+/// `T` into their longhand equivalents.
+pub fn absolute(path: &Path) -> Path {
+    let mut start = [0.0; 2];
+    let mut cursor = [0.0; 2];
+    // The last control point of the previous cubic or quadratic bezier, to reflect for smooth
+    // curves
+    let mut prev_cubic_control = None;
+    let mut prev_quadratic_control = None;
+
+    let result = path
+        .0
+        .iter()
+        // A repeated close path doesn't draw anything
+        .filter(|command| !matches!(command, command::Data::Implicit(c) if **c == command::Data::ClosePath))
+        .map(|command| {
+            let command = command.as_explicit();
+            let base = if command.is_by() { cursor } else { [0.0; 2] };
+            let point = |x: f64, y: f64| [base[0] + x, base[1] + y];
+            let mut cubic_control = None;
+            let mut quadratic_control = None;
+
+            let absolute = match *command {
+                command::Data::MoveTo([x, y]) | command::Data::MoveBy([x, y]) => {
+                    cursor = point(x, y);
+                    start = cursor;
+                    command::Data::MoveTo(cursor)
+                }
+                command::Data::ClosePath => {
+                    cursor = start;
+                    command::Data::ClosePath
+                }
+                command::Data::LineTo([x, y]) | command::Data::LineBy([x, y]) => {
+                    cursor = point(x, y);
+                    command::Data::LineTo(cursor)
+                }
+                command::Data::HorizontalLineTo([x]) | command::Data::HorizontalLineBy([x]) => {
+                    cursor[0] = base[0] + x;
+                    command::Data::LineTo(cursor)
+                }
+                command::Data::VerticalLineTo([y]) | command::Data::VerticalLineBy([y]) => {
+                    cursor[1] = base[1] + y;
+                    command::Data::LineTo(cursor)
+                }
+                command::Data::CubicBezierTo([x1, y1, x2, y2, x, y])
+                | command::Data::CubicBezierBy([x1, y1, x2, y2, x, y]) => {
+                    let [x1, y1] = point(x1, y1);
+                    let [x2, y2] = point(x2, y2);
+                    cursor = point(x, y);
+                    cubic_control = Some([x2, y2]);
+                    command::Data::CubicBezierTo([x1, y1, x2, y2, cursor[0], cursor[1]])
+                }
+                command::Data::SmoothBezierTo([x2, y2, x, y])
+                | command::Data::SmoothBezierBy([x2, y2, x, y]) => {
+                    let [x1, y1] = reflect(prev_cubic_control, cursor);
+                    let [x2, y2] = point(x2, y2);
+                    cursor = point(x, y);
+                    cubic_control = Some([x2, y2]);
+                    command::Data::CubicBezierTo([x1, y1, x2, y2, cursor[0], cursor[1]])
+                }
+                command::Data::QuadraticBezierTo([x1, y1, x, y])
+                | command::Data::QuadraticBezierBy([x1, y1, x, y]) => {
+                    let [x1, y1] = point(x1, y1);
+                    cursor = point(x, y);
+                    quadratic_control = Some([x1, y1]);
+                    command::Data::QuadraticBezierTo([x1, y1, cursor[0], cursor[1]])
+                }
+                command::Data::SmoothQuadraticBezierTo([x, y])
+                | command::Data::SmoothQuadraticBezierBy([x, y]) => {
+                    let [x1, y1] = reflect(prev_quadratic_control, cursor);
+                    cursor = point(x, y);
+                    quadratic_control = Some([x1, y1]);
+                    command::Data::QuadraticBezierTo([x1, y1, cursor[0], cursor[1]])
+                }
+                command::Data::ArcTo([rx, ry, angle, large_arc, sweep, x, y])
+                | command::Data::ArcBy([rx, ry, angle, large_arc, sweep, x, y]) => {
+                    cursor = point(x, y);
+                    command::Data::ArcTo([rx, ry, angle, large_arc, sweep, cursor[0], cursor[1]])
+                }
+                command::Data::Implicit(_) => unreachable!("command should be explicit"),
+            };
+            prev_cubic_control = cubic_control;
+            prev_quadratic_control = quadratic_control;
+            absolute
+        })
+        .collect();
+    Path(result)
+}
+
+/// Reflects the previous control point about the current point, or uses the current point when
+/// the previous command had no matching control point.
+fn reflect(control: Option<[f64; 2]>, cursor: [f64; 2]) -> [f64; 2] {
+    match control {
+        Some([x, y]) => [2.0 * cursor[0] - x, 2.0 * cursor[1] - y],
+        None => cursor,
+    }
+}
+
+#[test]
+fn test_absolute() {
+    let path =
+        Path::parse("m10 10h10v10l-5 5c1 1 2 2 3 3s4 4 5 5q1 1 2 2t3 3a5 5 0 0 1 5 5z").unwrap();
+    assert_eq!(
+        absolute(&path).to_string(),
+        "M10 10L20 10L20 20L15 25C16 26 17 27 18 28C19 29 22 32 23 33Q24 34 25 35Q26 36 28 38A5 5 0 0 1 33 43Z"
+    );
+}
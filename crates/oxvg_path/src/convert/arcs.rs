@@ -0,0 +1,252 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::{command, convert::absolute, Path};
+
+/// Replaces every elliptical arc in the path with one or more cubic beziers, for renderers which
+/// don't support arc commands.
+///
+/// Arcs are split so that each curve spans at most 90°, keeping the approximation visually
+/// indistinguishable from the original. The result uses absolute commands.
+pub fn arcs_to_curves(path: &mut Path) {
+    let absolute = absolute(path);
+    let mut result = Vec::with_capacity(absolute.0.len());
+    let mut start = [0.0; 2];
+    let mut cursor = [0.0; 2];
+
+    for command in absolute.0 {
+        match command {
+            command::Data::MoveTo(point) => {
+                start = point;
+                cursor = point;
+                result.push(command);
+            }
+            command::Data::ClosePath => {
+                cursor = start;
+                result.push(command);
+            }
+            command::Data::ArcTo(arc) => {
+                result.extend(arc_to_curves(cursor, &arc));
+                cursor = [arc[5], arc[6]];
+            }
+            command => {
+                let args = command.args();
+                cursor = [args[args.len() - 2], args[args.len() - 1]];
+                result.push(command);
+            }
+        }
+    }
+    path.0 = result;
+}
+
+/// The center parameterization of an elliptical arc
+pub(crate) struct CenterArc {
+    pub center: [f64; 2],
+    pub radii: [f64; 2],
+    /// The sine and cosine of the ellipse's rotation
+    pub rotation: (f64, f64),
+    /// The parameter of the ellipse where the arc starts, in radians
+    pub theta: f64,
+    /// The extent of the arc, in radians
+    pub delta_theta: f64,
+}
+
+impl CenterArc {
+    /// Converts an absolute arc drawn from `from` into its center parameterization, using the
+    /// conversion from the SVG implementation notes.
+    ///
+    /// Returns `None` when the arc should be drawn as a straight line or not at all.
+    ///
+    /// See <https://www.w3.org/TR/SVG11/implnote.html#ArcImplementationNotes>
+    #[allow(clippy::similar_names)]
+    pub fn new(from: [f64; 2], arc: &[f64; 7]) -> Option<Self> {
+        let [rx, ry, angle, large_arc, sweep, x, y] = *arc;
+        let [x1, y1] = from;
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        if (x1 == x && y1 == y) || rx == 0.0 || ry == 0.0 {
+            return None;
+        }
+
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let dx = (x1 - x) / 2.0;
+        let dy = (y1 - y) / 2.0;
+        let x1_prime = cos * dx + sin * dy;
+        let y1_prime = -sin * dx + cos * dy;
+
+        // Scale up radii which are too small to reach the end point
+        let lambda = (x1_prime / rx).powi(2) + (y1_prime / ry).powi(2);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let rx2 = rx * rx;
+        let ry2 = ry * ry;
+        let numerator = rx2 * ry2 - rx2 * y1_prime * y1_prime - ry2 * x1_prime * x1_prime;
+        let denominator = rx2 * y1_prime * y1_prime + ry2 * x1_prime * x1_prime;
+        let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+        let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+        let cx_prime = coefficient * rx * y1_prime / ry;
+        let cy_prime = -coefficient * ry * x1_prime / rx;
+        let cx = cos * cx_prime - sin * cy_prime + f64::midpoint(x1, x);
+        let cy = sin * cx_prime + cos * cy_prime + f64::midpoint(y1, y);
+
+        let theta = f64::atan2((y1_prime - cy_prime) / ry, (x1_prime - cx_prime) / rx);
+        let end_theta = f64::atan2((-y1_prime - cy_prime) / ry, (-x1_prime - cx_prime) / rx);
+        let mut delta_theta = end_theta - theta;
+        if sweep == 0.0 && delta_theta > 0.0 {
+            delta_theta -= 2.0 * PI;
+        } else if sweep != 0.0 && delta_theta < 0.0 {
+            delta_theta += 2.0 * PI;
+        }
+
+        Some(Self {
+            center: [cx, cy],
+            radii: [rx, ry],
+            rotation: (sin, cos),
+            theta,
+            delta_theta,
+        })
+    }
+
+    /// Returns the point of the ellipse at the parameter `t`
+    pub fn point(&self, t: f64) -> [f64; 2] {
+        let Self {
+            center: [cx, cy],
+            radii: [rx, ry],
+            rotation: (sin, cos),
+            ..
+        } = *self;
+        let (sin_t, cos_t) = t.sin_cos();
+        [
+            cx + rx * cos_t * cos - ry * sin_t * sin,
+            cy + rx * cos_t * sin + ry * sin_t * cos,
+        ]
+    }
+
+    /// Returns the derivative of the ellipse at the parameter `t`
+    fn derivative(&self, t: f64) -> [f64; 2] {
+        let Self {
+            radii: [rx, ry],
+            rotation: (sin, cos),
+            ..
+        } = *self;
+        let (sin_t, cos_t) = t.sin_cos();
+        [
+            -rx * sin_t * cos - ry * cos_t * sin,
+            -rx * sin_t * sin + ry * cos_t * cos,
+        ]
+    }
+}
+
+/// Converts an absolute arc drawn from `from` into cubic beziers.
+fn arc_to_curves(from: [f64; 2], arc: &[f64; 7]) -> Vec<command::Data> {
+    let [.., x, y] = *arc;
+    let Some(center_arc) = CenterArc::new(from, arc) else {
+        if from == [x, y] {
+            // An arc to the same point isn't rendered
+            return vec![];
+        }
+        return vec![command::Data::LineTo([x, y])];
+    };
+    let CenterArc {
+        theta, delta_theta, ..
+    } = center_arc;
+
+    // Allow a little error so that exact quarter arcs aren't split in two
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let segments = ((delta_theta.abs() / FRAC_PI_2) - 1e-9).ceil().max(1.0) as usize;
+    #[allow(clippy::cast_precision_loss)]
+    let segment_theta = delta_theta / segments as f64;
+    let k = 4.0 / 3.0 * (segment_theta / 4.0).tan();
+    (0..segments)
+        .map(|i| {
+            #[allow(clippy::cast_precision_loss)]
+            let t1 = theta + segment_theta * i as f64;
+            let t2 = t1 + segment_theta;
+            let [x1, y1] = center_arc.point(t1);
+            let [dx1, dy1] = center_arc.derivative(t1);
+            let [dx2, dy2] = center_arc.derivative(t2);
+            let [x2, y2] = if i == segments - 1 {
+                [x, y]
+            } else {
+                center_arc.point(t2)
+            };
+            command::Data::CubicBezierTo([
+                x1 + k * dx1,
+                y1 + k * dy1,
+                x2 - k * dx2,
+                y2 - k * dy2,
+                x2,
+                y2,
+            ])
+        })
+        .collect()
+}
+
+#[test]
+fn test_arcs_to_curves() {
+    use crate::geometry::de_casteljau;
+
+    // A rotated ellipse centered at (50, 50), drawn from 0° to 200° of its parameter
+    let (cx, cy, rx, ry, angle) = (50.0, 50.0, 30.0, 10.0, 30.0_f64);
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let point = |t: f64| {
+        let (sin_t, cos_t) = t.to_radians().sin_cos();
+        [
+            cx + rx * cos_t * cos - ry * sin_t * sin,
+            cy + rx * cos_t * sin + ry * sin_t * cos,
+        ]
+    };
+    let [x1, y1] = point(0.0);
+    let [x2, y2] = point(200.0);
+    let mut path =
+        Path::parse(&format!("M{x1} {y1}A{rx} {ry} {angle} 1 1 {x2} {y2}l10 0")).unwrap();
+    arcs_to_curves(&mut path);
+
+    assert!(matches!(path.0[0], command::Data::MoveTo(_)));
+    // 200° is split into three curves of at most 90°
+    let curves: Vec<_> = path.0[1..4]
+        .iter()
+        .map(|command| match command {
+            command::Data::CubicBezierTo(args) => *args,
+            _ => panic!("expected a cubic bezier, found {command:?}"),
+        })
+        .collect();
+    assert_eq!(path.0[4], command::Data::LineTo([x2 + 10.0, y2]));
+
+    let mut from = [x1, y1];
+    let mut prev_t = 0.0;
+    for [x1, y1, x2, y2, x, y] in curves {
+        for i in 0..=10 {
+            let t = f64::from(i) / 10.0;
+            let sample_x = de_casteljau(&[from[0], x1, x2, x], t);
+            let sample_y = de_casteljau(&[from[1], y1, y2, y], t);
+            // Find the closest point of the original arc by inverting the ellipse's transform
+            let (dx, dy) = (sample_x - cx, sample_y - cy);
+            let local_x = (cos * dx + sin * dy) / rx;
+            let local_y = (-sin * dx + cos * dy) / ry;
+            let mut sample_t = local_y.atan2(local_x).to_degrees();
+            if sample_t < prev_t - 1.0 {
+                sample_t += 360.0;
+            }
+            assert!(
+                (-1e-6..=200.0 + 1e-6).contains(&sample_t),
+                "{sample_t}° should be within the arc"
+            );
+            let [arc_x, arc_y] = point(sample_t);
+            let distance = f64::hypot(sample_x - arc_x, sample_y - arc_y);
+            assert!(
+                distance < 0.05,
+                "({sample_x}, {sample_y}) is {distance} from the arc"
+            );
+            prev_t = sample_t;
+        }
+        from = [x, y];
+    }
+    assert_eq!(from, [x2, y2]);
+
+    // Degenerate arcs become lines or are removed
+    let mut path = Path::parse("M0 0a0 5 0 0 1 10 10a5 5 0 0 1 0 0").unwrap();
+    arcs_to_curves(&mut path);
+    assert_eq!(path.to_string(), "M0 0L10 10");
+}
@@ -39,6 +39,21 @@ pub fn cleanup(path: &Path) -> Path {
     result
 }
 
+/// Replaces implicit lines immediately following a move with explicit `L` or `l` commands
+pub fn explicit_lineto(path: &mut crate::Path) {
+    let mut is_after_move = false;
+    for command in &mut path.0 {
+        if is_after_move {
+            if let command::Data::Implicit(inner) = command {
+                if matches!(**inner, command::Data::LineTo(_) | command::Data::LineBy(_)) {
+                    *command = (**inner).clone();
+                }
+            }
+        }
+        is_after_move = matches!(command, command::Data::MoveTo(_) | command::Data::MoveBy(_));
+    }
+}
+
 fn remove_repeated_moves(path: &Path) -> Path {
     let mut new_path: Vec<_> = path.0.clone().into_iter().map(Some).collect();
     (0..new_path.len()).for_each(|index| {
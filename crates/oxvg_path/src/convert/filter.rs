@@ -74,10 +74,16 @@ pub fn filter(
                 item.command,
                 command::Data::SmoothBezierBy(_) | command::Data::CubicBezierBy(_)
             ));
-            let arc_state = arc::Convert::curve(prev, item, next_paths, options, state, s_data);
-            if arc_state.is_some_and(|s| s.remove_item) {
-                *item_option = None;
-                return;
+            if let Some(arc_state) =
+                arc::Convert::curve(prev, item, next_paths, options, state, s_data)
+            {
+                let relative_subpoint = &mut state.relative_subpoints[index];
+                relative_subpoint[0] += arc_state.relative_subpoint.0[0];
+                relative_subpoint[1] += arc_state.relative_subpoint.0[1];
+                if arc_state.remove_item {
+                    *item_option = None;
+                    return;
+                }
             }
         }
 
@@ -95,7 +101,7 @@ pub fn filter(
             return;
         }
         from::curve_to_shorthand(prev, item, options, state);
-        if remove::useless_segment(item, options, info) {
+        if remove::useless_segment(prev, item, options, info) {
             *item_option = None;
             return;
         }
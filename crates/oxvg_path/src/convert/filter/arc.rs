@@ -16,7 +16,8 @@ pub struct Convert {
     angle: f64,
     suffix: String,
     relative_circle: Circle,
-    relative_subpoint: Point,
+    /// The change to the relative subpoint caused by updating the previous command
+    pub(crate) relative_subpoint: Point,
     output: Vec<Position>,
     arc_curves: Vec<Position>,
     has_prev: usize,
@@ -88,6 +89,10 @@ impl Convert {
         state: &filter::State,
         s_data: &Curve,
     ) -> Option<Self> {
+        if options.flags.preserve_point_count() {
+            // Arcs may replace multiple curves
+            return None;
+        }
         let make_arcs = &options.make_arcs;
 
         let Some(mut arc_state) = Self::init(item, options, state, s_data) else {
@@ -298,6 +303,7 @@ impl Convert {
                 .iter_mut()
                 .for_each(|a| *a = options.round(*a, error));
             relative_subpoint.0[0] += prev_arc_args[5] - prev_args[prev_args.len() - 2];
+            relative_subpoint.0[1] += prev_arc_args[6] - prev_args[prev_args.len() - 1];
             prev.command = command::Data::ArcBy(prev_arc_args.try_into().unwrap());
             prev.end = prev_arc.end;
             item.start = prev_arc.end;
@@ -15,7 +15,8 @@ pub fn straight_curve_to_line(
     if !options.flags.straight_curves() {
         return;
     }
-    let filter::State { error, saggita, .. } = state;
+    let filter::State { saggita, .. } = state;
+    let error = &options.straight_curve_tolerance.unwrap_or(state.error);
     match item.command {
         command::Data::CubicBezierBy(ref a) if Curve::is_data_straight(a, *error) => {
             make_specific_longhand(next, &command::ID::SmoothBezierBy, a);
@@ -37,6 +38,12 @@ pub fn straight_curve_to_line(
                 command::Data::QuadraticBezierBy(_) | command::Data::SmoothQuadraticBezierBy(_)
             ) =>
         {
+            // The control point is at the start, so a following `t` reflects it onto the end
+            make_specific_longhand(
+                next,
+                &command::ID::SmoothQuadraticBezierBy,
+                &[0.0, 0.0, a[0], a[1]],
+            );
             item.command = command::Data::LineBy(a);
         }
         command::Data::ArcBy(a)
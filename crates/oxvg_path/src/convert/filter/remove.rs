@@ -19,6 +19,7 @@ pub fn repeated_close_path(
     // prev may not have been `z`, but state is too close to curent position to be considered
     // useful
     state.options.flags.remove_useless()
+        && !state.options.flags.preserve_point_count()
         && state.info.contains(StyleInfo::is_safe_to_use_z)
         && (item.start.0[0] - item.end.0[0]).abs() < state.error / 10.0
         && (item.start.0[1] - item.end.0[1]).abs() < state.error / 10.0
@@ -32,6 +33,7 @@ pub fn repeated(
 ) -> bool {
     let command = &item.command;
     if !options.flags.collapse_repeated()
+        || options.flags.preserve_point_count()
         || info.contains(StyleInfo::has_marker_mid)
         || !matches!(
             command,
@@ -67,10 +69,19 @@ pub fn repeated(
     true
 }
 
-pub fn useless_segment(item: &Position, options: &convert::Options, info: &StyleInfo) -> bool {
+pub fn useless_segment(
+    prev: &Position,
+    item: &Position,
+    options: &convert::Options,
+    info: &StyleInfo,
+) -> bool {
+    if !options.flags.remove_useless() || options.flags.preserve_point_count() {
+        return false;
+    }
+    // A zero-length segment following a drawn segment doesn't draw a cap of its own
     let maybe_has_stroke_and_linecap =
         info.contains(StyleInfo::maybe_has_stroke) && info.contains(StyleInfo::maybe_has_linecap);
-    if !options.flags.remove_useless() || maybe_has_stroke_and_linecap {
+    if maybe_has_stroke_and_linecap && !is_drawn(prev) {
         return false;
     }
 
@@ -99,3 +110,11 @@ pub fn useless_segment(item: &Position, options: &convert::Options, info: &Style
     };
     args[5] == 0.0 && args[6] == 0.0
 }
+
+fn is_drawn(item: &Position) -> bool {
+    !matches!(
+        item.command,
+        command::Data::MoveTo(_) | command::Data::MoveBy(_) | command::Data::ClosePath
+    ) && ((item.start.0[0] - item.end.0[0]).abs() > f64::EPSILON
+        || (item.start.0[1] - item.end.0[1]).abs() > f64::EPSILON)
+}
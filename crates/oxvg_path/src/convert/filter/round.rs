@@ -68,18 +68,19 @@ fn update_relative_subpoint(
                 args[i] += item.start.0[i % 2] - relative_subpoint[i % 2];
             }
         }
-        command::Data::HorizontalLineBy(mut a) => {
+        command::Data::HorizontalLineBy(_) => {
             let relative_subpoint = state.relative_subpoints[index];
-            a[0] += item.start.0[0] - relative_subpoint[0];
+            item.command.args_mut()[0] += item.start.0[0] - relative_subpoint[0];
         }
-        command::Data::VerticalLineBy(mut a) => {
+        command::Data::VerticalLineBy(_) => {
             let relative_subpoint = state.relative_subpoints[index];
-            a[0] += item.start.0[1] - relative_subpoint[1];
+            item.command.args_mut()[0] += item.start.0[1] - relative_subpoint[1];
         }
-        command::Data::ArcBy(mut a) => {
+        command::Data::ArcBy(_) => {
             let relative_subpoint = state.relative_subpoints[index];
-            a[5] += item.start.0[0] - relative_subpoint[0];
-            a[6] += item.start.0[1] - relative_subpoint[1];
+            let args = item.command.args_mut();
+            args[5] += item.start.0[0] - relative_subpoint[0];
+            args[6] += item.start.0[1] - relative_subpoint[1];
         }
         command::Data::Implicit(c) => {
             let mut new_position = Position {
@@ -114,7 +115,7 @@ pub fn arc_smart(item: &mut Position, options: &convert::Options, state: &mut fi
     };
 
     for precision_new in (0..precision).rev() {
-        let radius = math::to_fixed(args[0], precision_new);
+        let radius = options.rounding_mode.to_fixed(args[0], precision_new);
         let Some(saggita_new) = math::saggita(
             &[radius, radius, args[2], args[3], args[4], args[5], args[6]],
             state.error,
@@ -1,6 +1,7 @@
 use crate::{
     command::{self, Position},
     convert,
+    geometry::Point,
     positioned::Path,
 };
 
@@ -9,7 +10,16 @@ use crate::{
 /// # Panics
 /// If internal assertions fail
 pub fn mixed(path: &Path, options: &convert::Options) -> Path {
+    let error = options.error();
     let mut new_path: Vec<_> = path.0.clone().into_iter().map(Some).collect();
+    // The current point as drawn by the rounded commands written so far, so that absolute
+    // coordinates land where the rounded relative coordinates would.
+    let mut current_point = path
+        .0
+        .first()
+        .map_or([0.0; 2], |item| end_point(&item.command, [0.0; 2]));
+    options.round_data(&mut current_point, error);
+    let mut subpath_start = current_point;
     (0..new_path.len()).for_each(|index| {
         let Some((prev, item_option, _)) = Path::split_mut(&mut new_path, index)
         else {
@@ -20,12 +30,19 @@ pub fn mixed(path: &Path, options: &convert::Options) -> Path {
             .expect("`split_mut` guard would have returned if item is `None`");
 
         if matches!(item.command, command::Data::ClosePath) {
+            current_point = subpath_start;
             return;
         }
 
-        let error = options.error();
-        let mut absolute_command = to_absolute(item);
+        let mut absolute_command = to_absolute(&Position {
+            start: Point(current_point),
+            ..item.clone()
+        });
         options.round_data(absolute_command.args_mut(), error);
+        let is_move = matches!(
+            item.command.as_explicit(),
+            command::Data::MoveTo(_) | command::Data::MoveBy(_)
+        );
         let mut relative_command = item.command.clone();
         options.round_data(relative_command.args_mut(), error);
 
@@ -36,6 +53,11 @@ pub fn mixed(path: &Path, options: &convert::Options) -> Path {
         if absolute_command_max_len >= relative_command_max_len
             && !options.flags.force_absolute_path()
         {
+            current_point = end_point(&relative_command, current_point);
+            options.round_data(&mut current_point, error);
+            if is_move {
+                subpath_start = current_point;
+            }
             return;
         }
 
@@ -57,6 +79,11 @@ pub fn mixed(path: &Path, options: &convert::Options) -> Path {
         if !is_relative_better || options.flags.force_absolute_path() {
             item.command = absolute_command;
         }
+        current_point = end_point(&item.command, current_point);
+        options.round_data(&mut current_point, error);
+        if is_move {
+            subpath_start = current_point;
+        }
     });
     let result = Path(new_path.into_iter().flatten().collect());
     #[cfg(debug_assertions)]
@@ -70,6 +97,25 @@ pub fn mixed(path: &Path, options: &convert::Options) -> Path {
     result
 }
 
+/// Returns the point a command draws to from the current point
+fn end_point(command: &command::Data, current_point: [f64; 2]) -> [f64; 2] {
+    let command = command.as_explicit();
+    let args = command.args();
+    let mut end = match command {
+        command::Data::HorizontalLineTo([x]) => return [*x, current_point[1]],
+        command::Data::HorizontalLineBy([x]) => return [current_point[0] + x, current_point[1]],
+        command::Data::VerticalLineTo([y]) => return [current_point[0], *y],
+        command::Data::VerticalLineBy([y]) => return [current_point[0], current_point[1] + y],
+        _ if args.len() < 2 => return current_point,
+        _ => [args[args.len() - 2], args[args.len() - 1]],
+    };
+    if command.is_by() {
+        end[0] += current_point[0];
+        end[1] += current_point[1];
+    }
+    end
+}
+
 pub fn to_absolute(item: &Position) -> command::Data {
     match item.command {
         command::Data::MoveBy(_)
@@ -10,17 +10,24 @@
 //!    compressed
 //! 4. Cleanup, doing a bit of post-processing to make sure any mistakes made prior are fixed
 
+mod absolute;
+mod arcs;
 mod cleanup;
 pub mod filter;
 mod mixed;
 mod relative;
+mod simplify;
 
-pub use crate::convert::cleanup::{cleanup, cleanup_unpositioned};
+pub use crate::convert::absolute::absolute;
+pub use crate::convert::arcs::arcs_to_curves;
+pub(crate) use crate::convert::arcs::CenterArc;
+pub use crate::convert::cleanup::{cleanup, cleanup_unpositioned, explicit_lineto};
 pub use crate::convert::filter::filter;
 pub use crate::convert::mixed::{mixed, to_absolute};
 pub use crate::convert::relative::relative;
+pub use crate::convert::simplify::simplify;
 use crate::geometry::MakeArcs;
-use crate::math::to_fixed;
+pub use crate::math::RoundingMode;
 use crate::{command, Path};
 
 #[cfg(feature = "oxvg")]
@@ -74,6 +81,11 @@ bitflags! {
         const negative_extra_space_flag = 0b10_0000_0000;
         /// Whether to not strongly force relative commands, even when suboptimal
         const utilize_absolute_flag = 0b0_0100_0000_0000;
+        /// Whether to omit the `L` or `l` of lines immediately following a move
+        const implicit_lineto_flag = 0b_1000_0000_0000;
+        /// Whether to avoid optimisations which change the number of vertices in the path, such
+        /// as collapsing repeated lines, removing useless segments, or merging curves into arcs
+        const preserve_point_count_flag = 0b1_0000_0000_0000;
     }
 }
 
@@ -97,6 +109,11 @@ pub struct Options {
     pub flags: Flags,
     pub make_arcs: MakeArcs,
     pub precision: Precision,
+    /// The distance a curve may bend away from the line between its ends for it to be
+    /// converted to a line, instead of the error of the precision.
+    pub straight_curve_tolerance: Option<f64>,
+    /// How numbers are rounded to the precision
+    pub rounding_mode: RoundingMode,
 }
 
 /// Returns an optimised version of the input path
@@ -136,6 +153,9 @@ pub fn run(path: &Path, options: &Options, style_info: &StyleInfo) -> Path {
     positioned_path = cleanup(&positioned_path);
 
     let mut path = positioned_path.take();
+    if !options.flags.implicit_lineto() {
+        explicit_lineto(&mut path);
+    }
     let has_marker = style_info.contains(StyleInfo::has_marker);
     let is_markers_only_path = has_marker
         && includes_vertices
@@ -301,16 +321,72 @@ impl Flags {
     fn utilize_absolute(&self) -> bool {
         self.contains(Self::utilize_absolute_flag)
     }
+
+    fn implicit_lineto(&self) -> bool {
+        self.contains(Self::implicit_lineto_flag)
+    }
+
+    fn preserve_point_count(&self) -> bool {
+        self.contains(Self::preserve_point_count_flag)
+    }
 }
 
 impl Default for Flags {
     fn default() -> Self {
         let mut flags = Self::all();
         flags.set(Self::force_absolute_path_flag, false);
+        flags.set(Self::preserve_point_count_flag, false);
         flags
     }
 }
 
+macro_rules! flag_builders {
+    ($($method:ident => $flag:ident,)+) => {
+        /// Builder methods for setting a single flag from an optional boolean, such as an option
+        /// of a config, where `None` leaves the flag as it was.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use oxvg_path::convert::Flags;
+        ///
+        /// let flags = Flags::default()
+        ///     .with_convert_to_q(Some(false))
+        ///     .with_line_shorthands(None);
+        /// assert!(!flags.contains(Flags::convert_to_q_flag));
+        /// assert!(flags.contains(Flags::line_shorthands_flag));
+        /// ```
+        impl Flags {
+            $(
+                #[doc = concat!("Sets [`Flags::", stringify!($flag), "`] when `enabled` is `Some`")]
+                #[must_use]
+                pub fn $method(mut self, enabled: Option<bool>) -> Self {
+                    if let Some(enabled) = enabled {
+                        self.set(Self::$flag, enabled);
+                    }
+                    self
+                }
+            )+
+        }
+    };
+}
+
+flag_builders! {
+    with_remove_useless => remove_useless_flag,
+    with_smart_arc_rounding => smart_arc_rounding_flag,
+    with_straight_curves => straight_curves_flag,
+    with_convert_to_q => convert_to_q_flag,
+    with_line_shorthands => line_shorthands_flag,
+    with_collapse_repeated => collapse_repeated_flag,
+    with_curve_smooth_shorthands => curve_smooth_shorthands_flag,
+    with_convert_to_z => convert_to_z_flag,
+    with_force_absolute_path => force_absolute_path_flag,
+    with_negative_extra_space => negative_extra_space_flag,
+    with_utilize_absolute => utilize_absolute_flag,
+    with_implicit_lineto => implicit_lineto_flag,
+    with_preserve_point_count => preserve_point_count_flag,
+}
+
 impl Options {
     /// Converts the precision into a tolerance that can be compared against
     pub fn error(&self) -> f64 {
@@ -327,18 +403,19 @@ impl Options {
     pub fn round(&self, data: f64, error: f64) -> f64 {
         let precision = self.precision.unwrap_or(0);
         if precision > 0 && precision < 20 {
-            let fixed = to_fixed(data, precision);
+            let mode = self.rounding_mode;
+            let fixed = mode.to_fixed(data, precision);
             if (fixed - data).abs() == 0.0 {
                 return data;
             }
-            let rounded = to_fixed(data, precision - 1);
-            if to_fixed((rounded - data).abs(), precision + 1) >= error {
+            let rounded = mode.to_fixed(data, precision - 1);
+            if mode.to_fixed((rounded - data).abs(), precision + 1) >= error {
                 fixed
             } else {
                 rounded
             }
         } else {
-            data.round()
+            self.rounding_mode.round(data)
         }
     }
 
@@ -360,6 +437,8 @@ impl Options {
             flags: Flags::default(),
             make_arcs: MakeArcs::default(),
             precision: Precision::conservative(),
+            straight_curve_tolerance: None,
+            rounding_mode: RoundingMode::default(),
         }
     }
 }
@@ -15,7 +15,7 @@ pub fn relative(path: &Path) -> positioned::Path {
             .collect(),
     );
     #[cfg(debug_assertions)]
-    {
+    if log::log_enabled!(log::Level::Debug) {
         let result_dbg = result.clone().take().to_string();
         if path.to_string() != result_dbg {
             log::debug!(
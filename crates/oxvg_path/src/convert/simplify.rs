@@ -0,0 +1,147 @@
+use crate::{command, convert::absolute, geometry::Curve, Path};
+
+/// Lossily reduces the number of commands in a path, using the Ramer–Douglas–Peucker algorithm
+/// to remove points that are within `tolerance` of the lines between their neighbours.
+///
+/// Curves which are flat to within `tolerance` are treated as lines, while other curves and arcs
+/// are kept as-is. The result uses absolute commands.
+pub fn simplify(path: &Path, tolerance: f64) -> Path {
+    let absolute = absolute(path);
+    let mut result = Vec::with_capacity(absolute.0.len());
+    // The points of consecutive lines, starting with the point the first line is drawn from
+    let mut polyline = vec![];
+    let mut start = [0.0; 2];
+    let mut cursor = [0.0; 2];
+
+    for command in absolute.0 {
+        let line_end = match command {
+            command::Data::LineTo(end) => Some(end),
+            command::Data::CubicBezierTo([x1, y1, x2, y2, x, y])
+                if Curve::is_data_straight(
+                    &[
+                        x1 - cursor[0],
+                        y1 - cursor[1],
+                        x2 - cursor[0],
+                        y2 - cursor[1],
+                        x - cursor[0],
+                        y - cursor[1],
+                    ],
+                    tolerance,
+                ) =>
+            {
+                Some([x, y])
+            }
+            command::Data::QuadraticBezierTo([x1, y1, x, y])
+                if Curve::is_data_straight(
+                    &[x1 - cursor[0], y1 - cursor[1], x - cursor[0], y - cursor[1]],
+                    tolerance,
+                ) =>
+            {
+                Some([x, y])
+            }
+            _ => None,
+        };
+        if let Some(end) = line_end {
+            if polyline.is_empty() {
+                polyline.push(cursor);
+            }
+            polyline.push(end);
+            cursor = end;
+            continue;
+        }
+
+        flush_polyline(&mut polyline, tolerance, &mut result);
+        match command {
+            command::Data::MoveTo(point) => {
+                start = point;
+                cursor = point;
+            }
+            command::Data::ClosePath => cursor = start,
+            _ => {
+                let args = command.args();
+                cursor = [args[args.len() - 2], args[args.len() - 1]];
+            }
+        }
+        result.push(command);
+    }
+    flush_polyline(&mut polyline, tolerance, &mut result);
+    Path(result)
+}
+
+/// Pushes the simplified lines of the polyline to the result, emptying the polyline
+fn flush_polyline(polyline: &mut Vec<[f64; 2]>, tolerance: f64, result: &mut Vec<command::Data>) {
+    if polyline.is_empty() {
+        return;
+    }
+    let mut keep = vec![false; polyline.len()];
+    keep[0] = true;
+    keep[polyline.len() - 1] = true;
+    ramer_douglas_peucker(polyline, tolerance, &mut keep);
+
+    result.extend(
+        polyline
+            .iter()
+            .zip(keep)
+            .skip(1)
+            .filter(|(_, keep)| *keep)
+            .map(|(point, _)| command::Data::LineTo(*point)),
+    );
+    polyline.clear();
+}
+
+/// Marks the points furthest from the line between the first and last point as kept, until the
+/// remaining points are within the tolerance.
+fn ramer_douglas_peucker(points: &[[f64; 2]], tolerance: f64, keep: &mut [bool]) {
+    let len = points.len();
+    if len < 3 {
+        return;
+    }
+    let first = points[0];
+    let last = points[len - 1];
+    let Some((index, distance)) = points[1..len - 1]
+        .iter()
+        .enumerate()
+        .map(|(i, point)| (i + 1, distance_to_segment(*point, first, last)))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+    else {
+        return;
+    };
+    if distance <= tolerance {
+        return;
+    }
+
+    keep[index] = true;
+    ramer_douglas_peucker(&points[..=index], tolerance, &mut keep[..=index]);
+    ramer_douglas_peucker(&points[index..], tolerance, &mut keep[index..]);
+}
+
+fn distance_to_segment(point: [f64; 2], start: [f64; 2], end: [f64; 2]) -> f64 {
+    let segment = [end[0] - start[0], end[1] - start[1]];
+    let relative = [point[0] - start[0], point[1] - start[1]];
+    let length_squared = segment[0] * segment[0] + segment[1] * segment[1];
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        ((relative[0] * segment[0] + relative[1] * segment[1]) / length_squared).clamp(0.0, 1.0)
+    };
+    f64::hypot(relative[0] - t * segment[0], relative[1] - t * segment[1])
+}
+
+#[test]
+fn test_simplify() {
+    // Should drop points of a dense polyline within tolerance
+    let dense: String = std::iter::once("M0 0".to_string())
+        .chain((1..=100).map(|i| format!("L{i} {}", if i % 2 == 0 { 0.0 } else { 0.05 })))
+        .chain((1..=100).map(|i| format!("L100 {i}")))
+        .collect();
+    let path = Path::parse(&dense).unwrap();
+    assert_eq!(path.0.len(), 201);
+    assert_eq!(simplify(&path, 0.1).to_string(), "M0 0L100 0L100 100");
+
+    // Should keep points outside of tolerance
+    assert_eq!(path.simplify(0.01).0.len(), 102);
+
+    // Should treat flat curves as lines, but keep others
+    let path = Path::parse("M0 0c3 .01 7-.01 10 0l10 0q5 5 10 0z").unwrap();
+    assert_eq!(simplify(&path, 0.1).to_string(), "M0 0L20 0Q25 5 30 0Z");
+}
@@ -117,25 +117,36 @@ impl Curve {
     }
 
     /// Returns whether the arc fits on a straight line
+    ///
+    /// Near-collinear control points are accepted when the curve itself strays no further than
+    /// `error` from the line between its start and end points, and doesn't overshoot either end.
     pub fn is_data_straight(args: &[f64], error: f64) -> bool {
-        // Get line equation a·x + b·y + c = 0 coefficients a, b (c = 0) by start and end points.
         let i = args.len() - 2;
-        let a = -args[i + 1]; // y1 − y2 (y1 = 0)
-        let b = args[i]; // x2 − x1 (x1 = 0)
-        let d = 1.0 / (a * a + b * b); // same part for all points
+        let x = args[i];
+        let y = args[i + 1];
+        let length = f64::hypot(x, y);
 
-        if i <= 1 || !d.is_finite() {
+        if i <= 1 || length == 0.0 || !length.is_finite() {
             // curve that ends at start point isn't the case
             return false;
         }
 
-        // Distance from point (x0, y0) to the line is sqrt((c − a·x0 − b·y0)² / (a² + b²))
-        for i in (0..=(i - 2)).rev().step_by(2) {
-            if f64::sqrt(f64::powi(a * args[i] + b * args[i + 1], 2) * d) > error {
-                return false;
-            }
+        // Project each control point onto the normal and direction of the line through the
+        // start (0, 0) and end points.
+        let mut normal = vec![0.0];
+        let mut tangent = vec![0.0];
+        for point in args[..i].chunks_exact(2) {
+            normal.push((x * point[1] - y * point[0]) / length);
+            tangent.push((x * point[0] + y * point[1]) / length);
         }
-        true
+        normal.push(0.0);
+        tangent.push(length);
+
+        let (normal_min, normal_max) = bezier_bounds(&normal);
+        let (tangent_min, tangent_max) = bezier_bounds(&tangent);
+        normal_max.max(-normal_min) <= error
+            && tangent_min >= -error
+            && tangent_max <= length + error
     }
 
     /// Returns the angle from the start of an arc to the end
@@ -317,3 +328,54 @@ impl Circle {
         f64::acos((x1 * x2 + y1 * y2) / f64::sqrt((x1 * x1 + y1 * y1) * (x2 * x2 + y2 * y2)))
     }
 }
+
+/// Returns the minimum and maximum values of a one-dimensional quadratic or cubic bezier over
+/// `t` in `[0, 1]`.
+fn bezier_bounds(values: &[f64]) -> (f64, f64) {
+    let mut extrema = vec![0.0, 1.0];
+    match *values {
+        [p0, p1, p2] => {
+            let denominator = p0 - 2.0 * p1 + p2;
+            if denominator != 0.0 {
+                extrema.push((p0 - p1) / denominator);
+            }
+        }
+        [p0, p1, p2, p3] => {
+            // Roots of the derivative, a·t² + b·t + c = 0
+            let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+            let b = 2.0 * (p0 - 2.0 * p1 + p2);
+            let c = p1 - p0;
+            if a == 0.0 {
+                if b != 0.0 {
+                    extrema.push(-c / b);
+                }
+            } else {
+                let discriminant = b * b - 4.0 * a * c;
+                if discriminant >= 0.0 {
+                    let root = discriminant.sqrt();
+                    extrema.push((-b + root) / (2.0 * a));
+                    extrema.push((-b - root) / (2.0 * a));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    extrema
+        .into_iter()
+        .filter(|t| (0.0..=1.0).contains(t))
+        .map(|t| de_casteljau(values, t))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        })
+}
+
+pub(crate) fn de_casteljau(values: &[f64], t: f64) -> f64 {
+    let mut values = values.to_vec();
+    for n in (1..values.len()).rev() {
+        for i in 0..n {
+            values[i] += (values[i + 1] - values[i]) * t;
+        }
+    }
+    values[0]
+}
@@ -21,6 +21,8 @@
 #[macro_use]
 extern crate bitflags;
 
+#[cfg(feature = "optimise")]
+pub mod builder;
 #[cfg(feature = "optimise")]
 pub mod command;
 #[cfg(feature = "optimise")]
@@ -36,7 +38,7 @@ pub mod points;
 #[cfg(feature = "optimise")]
 pub mod positioned;
 
-use points::{Point, Points};
+use points::Points;
 
 #[cfg(feature = "parse")]
 use crate::parser::Parser;
@@ -71,65 +73,329 @@ impl Path {
         Parser::default().parse(definition)
     }
 
+    #[cfg(feature = "parse")]
+    /// Parses a path definition into the existing path, reusing the capacity of its
+    /// commands to avoid reallocating in hot loops.
+    ///
+    /// The path is left empty when the definition fails to parse.
+    ///
+    /// # Errors
+    /// If the definition is invalid
+    pub fn parse_into(&mut self, definition: &str) -> Result<(), parser::Error> {
+        Parser::default().parse_into(definition, self)
+    }
+
+    #[cfg(feature = "optimise")]
+    /// Returns a lossy simplification of the path, dropping points which are within `tolerance`
+    /// of the surrounding lines.
+    ///
+    /// See [`convert::simplify`]
+    pub fn simplify(&self, tolerance: f64) -> Self {
+        convert::simplify(self, tolerance)
+    }
+
+    #[cfg(feature = "optimise")]
+    /// Returns the path with each coordinate moved to the nearest multiple of `1 / scale`, so
+    /// that paths which differ only by floating point error have identical output.
+    ///
+    /// Relative commands are quantized by their absolute position, so that rounding errors don't
+    /// accumulate along the path. Arc radii and angles are rounded to the same grid, and arc
+    /// flags are left as-is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxvg_path::Path;
+    ///
+    /// let path = Path::parse("M 0.1 0.2 l 0.30000000000000004 0.1").unwrap();
+    /// assert_eq!(path.to_fixed_point(100.0).to_string(), "M.1.2l.3.1");
+    /// ```
+    pub fn to_fixed_point(&self, scale: f64) -> Self {
+        // The current point of the original path
+        let mut cursor = [0.0_f64; 2];
+        let mut subpath_start = [0.0_f64; 2];
+        // The current point of the quantized path, as a number of grid units
+        let mut cursor_units = [0.0_f64; 2];
+        let mut subpath_start_units = [0.0_f64; 2];
+        let mut path = self.clone();
+        for command in &mut path.0 {
+            let command = match command {
+                command::Data::Implicit(inner) => inner.as_mut(),
+                command => command,
+            };
+            let is_by = command.is_by();
+            // The axis of each argument, or `None` for arguments which aren't coordinates
+            let arg_axes: &[Option<usize>] = match command {
+                command::Data::ClosePath => &[],
+                command::Data::HorizontalLineTo(_) | command::Data::HorizontalLineBy(_) => {
+                    &[Some(0)]
+                }
+                command::Data::VerticalLineTo(_) | command::Data::VerticalLineBy(_) => &[Some(1)],
+                command::Data::ArcTo(_) | command::Data::ArcBy(_) => {
+                    &[None, None, None, None, None, Some(0), Some(1)]
+                }
+                _ => &[Some(0), Some(1), Some(0), Some(1), Some(0), Some(1)],
+            };
+            let mut end = cursor;
+            let mut end_units = cursor_units;
+            for (index, (arg, axis)) in command.args_mut().iter_mut().zip(arg_axes).enumerate() {
+                match axis {
+                    Some(axis) => {
+                        let value = if is_by { cursor[*axis] + *arg } else { *arg };
+                        let units = (value * scale).round();
+                        let base_units = if is_by { cursor_units[*axis] } else { 0.0 };
+                        *arg = (units - base_units) / scale;
+                        end[*axis] = value;
+                        end_units[*axis] = units;
+                    }
+                    // Arc flags
+                    None if index == 3 || index == 4 => {}
+                    None => *arg = (*arg * scale).round() / scale,
+                }
+            }
+
+            if matches!(command, command::Data::ClosePath) {
+                cursor = subpath_start;
+                cursor_units = subpath_start_units;
+            } else {
+                cursor = end;
+                cursor_units = end_units;
+            }
+            if matches!(command, command::Data::MoveTo(_) | command::Data::MoveBy(_)) {
+                subpath_start = cursor;
+                subpath_start_units = cursor_units;
+            }
+        }
+        path
+    }
+
+    #[cfg(feature = "optimise")]
+    /// Returns an iterator of each command with absolute coordinates, along with the current
+    /// point before and after the command.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxvg_path::Path;
+    ///
+    /// let path = Path::parse("M 10 10 l 5 5 h 5").unwrap();
+    /// let ends: Vec<_> = path.iter_absolute().map(|c| c.end.0).collect();
+    /// assert_eq!(ends, vec![[10.0, 10.0], [15.0, 15.0], [20.0, 15.0]]);
+    /// ```
+    pub fn iter_absolute(&self) -> impl Iterator<Item = command::AbsCommand> {
+        // A repeated close path doesn't draw anything
+        let path = Self(
+            self.0
+                .iter()
+                .filter(|command| !matches!(command, command::Data::Implicit(c) if **c == command::Data::ClosePath))
+                .cloned()
+                .collect(),
+        );
+        convert::relative(&path)
+            .0
+            .into_iter()
+            .map(|item| command::AbsCommand {
+                command: convert::to_absolute(&item),
+                start: item.start,
+                end: item.end,
+            })
+    }
+
+    #[cfg(feature = "optimise")]
+    /// Checks whether two paths draw the same shape, regardless of how the commands are
+    /// written.
+    ///
+    /// Each path is flattened into line segments, and every vertex of either path must be
+    /// within `tolerance` of the other. Moves which don't draw anything are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxvg_path::Path;
+    ///
+    /// let a = Path::parse("M0 0h10v10H0z").unwrap();
+    /// let b = Path::parse("m0 0 10 0 0 10-10 0 0-10").unwrap();
+    /// assert!(a.geometrically_eq(&b, 1e-9));
+    /// assert!(!a.geometrically_eq(&Path::parse("M0 0h10v10z").unwrap(), 1e-9));
+    /// ```
+    pub fn geometrically_eq(&self, other: &Self, tolerance: f64) -> bool {
+        // Leave half of the tolerance for the error of flattening curves
+        let flatness = tolerance / 2.0;
+        let (a, b) = (self.flatten(flatness), other.flatten(flatness));
+        let is_near = |point: &[f64; 2], segments: &[[[f64; 2]; 2]]| {
+            segments
+                .iter()
+                .any(|segment| distance_to_segment(*point, segment) <= tolerance)
+        };
+        a.iter()
+            .all(|segment| segment.iter().all(|point| is_near(point, &b)))
+            && b.iter()
+                .all(|segment| segment.iter().all(|point| is_near(point, &a)))
+    }
+
+    #[cfg(feature = "optimise")]
+    /// Returns the geometric length of the path, as used by `pathLength` and stroke dashing.
+    ///
+    /// Curves and arcs are measured by the straight segments within `flatness` of them, so the
+    /// result may be slightly short of the exact length. Moves don't add to the length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxvg_path::Path;
+    ///
+    /// let path = Path::parse("M0 0h10v10M20 20z").unwrap();
+    /// assert_eq!(path.length(1e-3), 20.0);
+    ///
+    /// let circle = Path::parse("M-10 0a10 10 0 1 1 20 0a10 10 0 1 1-20 0").unwrap();
+    /// assert!((circle.length(1e-6) - 20.0 * std::f64::consts::PI).abs() < 1e-3);
+    /// ```
+    pub fn length(&self, flatness: f64) -> f64 {
+        self.flatten(flatness)
+            .iter()
+            .map(|[from, to]| f64::hypot(to[0] - from[0], to[1] - from[1]))
+            .sum()
+    }
+
+    #[cfg(feature = "optimise")]
+    /// Returns the straight segments approximating each drawn command of the path, where curves
+    /// are within `flatness` of their segments.
+    fn flatten(&self, flatness: f64) -> Vec<[[f64; 2]; 2]> {
+        let mut segments = vec![];
+        // The last control point of the previous command, and whether it was a cubic curve
+        let mut control: Option<(bool, [f64; 2])> = None;
+        for command::AbsCommand {
+            command,
+            start: geometry::Point(start),
+            end: geometry::Point(end),
+        } in self.iter_absolute()
+        {
+            let reflect = |is_cubic: bool| match control {
+                Some((is_prev_cubic, [x, y])) if is_prev_cubic == is_cubic => {
+                    [2.0 * start[0] - x, 2.0 * start[1] - y]
+                }
+                _ => start,
+            };
+            let quadratic = |[x, y]: [f64; 2]| {
+                let elevate = |from: [f64; 2]| {
+                    [
+                        from[0] + 2.0 / 3.0 * (x - from[0]),
+                        from[1] + 2.0 / 3.0 * (y - from[1]),
+                    ]
+                };
+                [start, elevate(start), elevate(end), end]
+            };
+            let (curve, next_control) = match command {
+                command::Data::MoveTo(_) => (None, None),
+                command::Data::CubicBezierTo([x1, y1, x2, y2, ..]) => (
+                    Some([start, [x1, y1], [x2, y2], end]),
+                    Some((true, [x2, y2])),
+                ),
+                command::Data::SmoothBezierTo([x2, y2, ..]) => (
+                    Some([start, reflect(true), [x2, y2], end]),
+                    Some((true, [x2, y2])),
+                ),
+                command::Data::QuadraticBezierTo([x1, y1, ..]) => {
+                    (Some(quadratic([x1, y1])), Some((false, [x1, y1])))
+                }
+                command::Data::SmoothQuadraticBezierTo(_) => {
+                    let control = reflect(false);
+                    (Some(quadratic(control)), Some((false, control)))
+                }
+                command::Data::ArcTo(arc) => {
+                    flatten_arc(start, &arc, flatness, &mut segments);
+                    (None, None)
+                }
+                _ => {
+                    segments.push([start, end]);
+                    (None, None)
+                }
+            };
+            control = next_control;
+            let Some(curve) = curve else {
+                continue;
+            };
+
+            // The distance between a cubic curve and its chords is at most `1 / 8` of the
+            // curve's second derivative over the number of steps squared
+            let [p0, p1, p2, p3] = curve;
+            let second_derivative = f64::max(
+                f64::hypot(p0[0] - 2.0 * p1[0] + p2[0], p0[1] - 2.0 * p1[1] + p2[1]),
+                f64::hypot(p1[0] - 2.0 * p2[0] + p3[0], p1[1] - 2.0 * p2[1] + p3[1]),
+            ) * 6.0;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let steps = (second_derivative / 8.0 / flatness)
+                .sqrt()
+                .ceil()
+                .clamp(1.0, 10_000.0) as u32;
+            let mut from = start;
+            for step in 1..=steps {
+                let t = f64::from(step) / f64::from(steps);
+                let point =
+                    [0, 1].map(|axis| geometry::de_casteljau(&curve.map(|point| point[axis]), t));
+                segments.push([from, point]);
+                from = point;
+            }
+        }
+        segments
+    }
+
     /// Checks if two paths have an intersection by checking convex hulls collision using
     /// Gilbert-Johnson-Keerthi distance algorithm.
     ///
+    /// When checking a path against many others, prefer building [`Points`] for each path
+    /// once and using [`Points::intersects`].
+    ///
     /// # Panics
     /// If internal assertions fail
     pub fn intersects(&self, other: &Self) -> bool {
-        let points_1 = Points::from_positioned(&convert::relative(self));
-        let points_2 = Points::from_positioned(&convert::relative(other));
-
-        if points_1.max_x <= points_2.min_x
-            || points_2.max_x <= points_1.min_x
-            || points_1.max_y <= points_2.min_y
-            || points_2.max_y <= points_1.min_y
-            || points_1.list.iter().all(|set_1| {
-                points_2.list.iter().all(|set_2| {
-                    set_1.list[set_1.max_x].0[0] <= set_2.list[set_2.min_x].0[0]
-                        || set_2.list[set_2.max_x].0[0] <= set_1.list[set_1.min_x].0[0]
-                        || set_1.list[set_1.max_y].0[1] <= set_2.list[set_2.min_y].0[1]
-                        || set_2.list[set_2.max_y].0[1] <= set_1.list[set_1.min_y].0[1]
-                })
-            })
-        {
-            log::debug!("no intersection, bounds check failed");
-            return false;
-        }
-
-        let mut hull_nest_1 = points_1.list.iter().map(Point::convex_hull);
-        let hull_nest_2: Vec<_> = points_2.list.iter().map(Point::convex_hull).collect();
+        Points::from_path(self).intersects(&Points::from_path(other))
+    }
+}
 
-        hull_nest_1.any(|hull_1| {
-            if hull_1.list.len() < 3 {
-                return false;
-            }
+#[cfg(feature = "optimise")]
+fn flatten_arc(start: [f64; 2], arc: &[f64; 7], flatness: f64, segments: &mut Vec<[[f64; 2]; 2]>) {
+    let [.., x, y] = *arc;
+    let Some(center_arc) = convert::CenterArc::new(start, arc) else {
+        segments.push([start, [x, y]]);
+        return;
+    };
+    // Each chord is within `flatness` of the ellipse when its angle is small enough
+    let radius = f64::max(center_arc.radii[0], center_arc.radii[1]);
+    let max_step = 2.0 * f64::acos((1.0 - flatness / radius).max(0.0));
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let steps = (center_arc.delta_theta.abs() / max_step)
+        .ceil()
+        .clamp(1.0, 10_000.0) as u32;
+    let mut from = start;
+    for step in 1..=steps {
+        let t = center_arc.theta + center_arc.delta_theta * f64::from(step) / f64::from(steps);
+        let point = center_arc.point(t);
+        segments.push([from, point]);
+        from = point;
+    }
+}
 
-            hull_nest_2.iter().any(|hull_2| {
-                if hull_2.list.len() < 3 {
-                    return false;
-                }
+#[cfg(feature = "optimise")]
+fn distance_to_segment(point: [f64; 2], [from, to]: &[[f64; 2]; 2]) -> f64 {
+    let [dx, dy] = [to[0] - from[0], to[1] - from[1]];
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        (((point[0] - from[0]) * dx + (point[1] - from[1]) * dy) / length_squared).clamp(0.0, 1.0)
+    };
+    f64::hypot(point[0] - (from[0] + t * dx), point[1] - (from[1] + t * dy))
+}
 
-                let mut simplex = vec![hull_1.get_support(hull_2, geometry::Point([1.0, 0.0]))];
-                let mut direction = simplex[0].minus();
-                let mut iterations = 10_000;
+#[cfg(feature = "format")]
+impl Path {
+    /// Appends the formatted path to the buffer, avoiding the allocation of a new string for
+    /// each path when formatting many paths.
+    pub fn write_to(&self, buf: &mut String) {
+        use std::fmt::Write;
 
-                loop {
-                    iterations -= 1;
-                    if iterations == 0 {
-                        log::error!("Infinite loop while finding path intersections");
-                        return true;
-                    }
-                    simplex.push(hull_1.get_support(hull_2, direction));
-                    if direction.dot(simplex.last().unwrap()) <= 0.0 {
-                        return false;
-                    }
-                    if geometry::Point::process_simplex(&mut simplex, &mut direction) {
-                        return true;
-                    }
-                }
-            })
-        })
+        write!(buf, "{self}").expect("writing to a string is infallible");
     }
 }
 
@@ -151,11 +417,16 @@ impl std::fmt::Display for Path {
                 if i == 0 {
                     prev.fmt(f)?;
                 }
-                let str = current.to_string();
-                if current.is_space_needed(prev) && !str.starts_with('-') {
+                // Only implicit commands start with an argument, which may be signed
+                let starts_with_minus = current.is_implicit()
+                    && current
+                        .args()
+                        .first()
+                        .is_some_and(|a| a.is_sign_negative() && *a != 0.0);
+                if current.is_space_needed(prev) && !starts_with_minus {
                     f.write_char(' ')?;
                 }
-                f.write_str(&str)?;
+                current.fmt(f)?;
                 Ok(())
             })
     }
@@ -203,3 +474,273 @@ fn test_path_parse() {
     // Should error when args are missing
     assert!(Path::parse("m1").is_err());
 }
+
+#[test]
+#[cfg(feature = "default")]
+fn test_path_write_to() {
+    let path =
+        Path::parse("M 10,50 C 1,2 3,4 5,6.5 .1 .2 .3 .4 .5 -.05176e-005 L -1 -2 -3 -4").unwrap();
+    let mut buf = String::from("d=");
+    path.write_to(&mut buf);
+    assert_eq!(buf, format!("d={path}"));
+
+    let positioned = convert::relative(&path);
+    let mut buf = String::new();
+    positioned.write_to(&mut buf);
+    assert_eq!(buf, positioned.to_string());
+}
+
+#[test]
+#[cfg(feature = "default")]
+fn test_path_parse_into() {
+    let mut path = Path::parse("M 10,50 C 20,30 40,50 60,70 C 10,20 30,40 50,60").unwrap();
+    let capacity = path.0.capacity();
+    let ptr = path.0.as_ptr();
+
+    // Should parse and reuse allocation
+    path.parse_into("M 10,50 L 20,30").unwrap();
+    assert_eq!(path, Path::parse("M 10,50 L 20,30").unwrap());
+    assert_eq!(path.0.capacity(), capacity);
+    assert_eq!(path.0.as_ptr(), ptr);
+
+    // Should clear path on error, keeping allocation
+    assert!(path.parse_into("m1").is_err());
+    assert!(path.0.is_empty());
+    assert_eq!(path.0.capacity(), capacity);
+}
+
+#[test]
+#[cfg(feature = "default")]
+fn test_convert_implicit_lineto() {
+    let path = Path::parse("M 10,10 L 20,20 L 30,10 m 10,10 l 10,10").unwrap();
+    let style_info = convert::StyleInfo::conservative();
+
+    let implicit = convert::run(&path, &convert::Options::default(), &style_info);
+    let explicit = convert::run(
+        &path,
+        &convert::Options {
+            flags: convert::Flags::default() - convert::Flags::implicit_lineto_flag,
+            ..convert::Options::default()
+        },
+        &style_info,
+    );
+    assert_eq!(implicit.to_string(), "m10 10 10 10 10-10m10 10 10 10");
+    assert_eq!(explicit.to_string(), "m10 10l10 10 10-10m10 10l10 10");
+
+    // Should re-parse to the same commands
+    let implicit = Path::parse(&implicit.to_string()).unwrap();
+    let explicit = Path::parse(&explicit.to_string()).unwrap();
+    assert!(implicit
+        .0
+        .iter()
+        .map(command::Data::as_explicit)
+        .eq(explicit.0.iter().map(command::Data::as_explicit)));
+}
+
+#[test]
+#[cfg(feature = "default")]
+fn test_iter_absolute() {
+    let path = Path::parse("m10 10 10 10h5v-5c1 1 2 2 3 3s1 1 2 2a5 5 0 0 1 5 5zl5 5").unwrap();
+    let commands: Vec<_> = path.iter_absolute().collect();
+
+    assert_eq!(
+        commands
+            .iter()
+            .map(|c| c.command.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            command::Data::MoveTo([10.0, 10.0]),
+            command::Data::LineTo([20.0, 20.0]),
+            command::Data::HorizontalLineTo([25.0]),
+            command::Data::VerticalLineTo([15.0]),
+            command::Data::CubicBezierTo([26.0, 16.0, 27.0, 17.0, 28.0, 18.0]),
+            command::Data::SmoothBezierTo([29.0, 19.0, 30.0, 20.0]),
+            command::Data::ArcTo([5.0, 5.0, 0.0, 0.0, 1.0, 35.0, 25.0]),
+            command::Data::ClosePath,
+            command::Data::LineTo([15.0, 15.0]),
+        ]
+    );
+    assert_eq!(commands[2].start.0, [20.0, 20.0]);
+    assert_eq!(commands[2].end.0, [25.0, 20.0]);
+    // Closing returns to the start of the subpath
+    assert_eq!(commands[7].start.0, [35.0, 25.0]);
+    assert_eq!(commands[7].end.0, [10.0, 10.0]);
+}
+
+#[test]
+#[cfg(feature = "default")]
+fn test_convert_preserve_point_count() {
+    let style_info = convert::StyleInfo::default();
+    let options = convert::Options {
+        flags: convert::Flags::default() | convert::Flags::preserve_point_count_flag,
+        ..convert::Options::default()
+    };
+    for definition in [
+        // Repeated lines
+        "M10 10h10h10v10v10",
+        // Useless segments
+        "M10 10l0 0 10 10z",
+        // Curves which may become arcs
+        "M7.234 19.474C6.562 19.811 5.803 20 5 20c-2.761 0-5-2.239-5-5 0-1.767.917-3.32 2.301-4.209",
+        // Straight curves
+        "M0 0c5 0 10 0 15 0c5 0 10 0 15 0",
+    ] {
+        let path = Path::parse(definition).unwrap();
+        let converted = convert::run(&path, &options, &style_info);
+        assert_eq!(
+            path.iter_absolute().count(),
+            converted.iter_absolute().count(),
+            "{definition} became {converted}"
+        );
+
+        let converted = convert::run(&path, &convert::Options::default(), &style_info);
+        assert_ne!(
+            path.iter_absolute().count(),
+            converted.iter_absolute().count(),
+            "{definition} should change without `preserve_point_count`"
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "default")]
+fn test_convert_implicit_repeats() {
+    let style_info = convert::StyleInfo::default();
+    for (definition, expected) in [
+        // Lines following a move are implied by the move
+        ("M0 0L1 1L2 3", "m0 0 1 1 1 2"),
+        ("M0 0C1 1 2 2 3 4L5 5L6 7", "M0 0c1 1 2 2 3 4l2 1 1 2"),
+        ("M0 0C1 1 2 2 3 3C4 5 6 7 8 9", "m0 0 3 3c1 2 3 4 5 6"),
+        ("M0 0Q1 1 2 3Q4 7 6 5", "M0 0q1 1 2 3 2 4 4 2"),
+        (
+            "M0 0A5 5 0 0 1 10 0A5 5 0 0 1 20 0",
+            "M0 0a5 5 0 0 1 10 0 5 5 0 0 1 10 0",
+        ),
+        // Repeated moves are merged rather than repeated
+        ("M0 0M1 1L5 7", "m1 1 4 6"),
+    ] {
+        let path = Path::parse(definition).unwrap();
+        let converted = convert::run(&path, &convert::Options::default(), &style_info);
+        assert_eq!(converted.to_string(), expected, "converting {definition}");
+
+        let reparsed = Path::parse(expected).unwrap();
+        assert_eq!(
+            path.iter_absolute()
+                .filter(|command| !matches!(command.command, command::Data::MoveTo(_)))
+                .map(|command| command.end.0)
+                .collect::<Vec<_>>(),
+            reparsed
+                .iter_absolute()
+                .filter(|command| !matches!(command.command, command::Data::MoveTo(_)))
+                .map(|command| command.end.0)
+                .collect::<Vec<_>>(),
+            "{expected} should draw the same points as {definition}"
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "default")]
+fn test_to_fixed_point() {
+    let scale = 1000.0;
+    // The same path, with different floating point error from previous calculations
+    let a = Path::parse("M0.1 0.2c0.1 0.1 0.2 0.2 0.30000000000000004 0.3a5 5 30 0 1 0.7 0.2Z")
+        .unwrap();
+    let b = Path::parse("M0.10000000000000002 0.2c0.1 0.1 0.2 0.2 0.3 0.30000000000000004a5.0000001 5 30 0 1 0.7 0.19999999999999998Z")
+        .unwrap();
+    assert_ne!(a, b);
+    let (a, b) = (a.to_fixed_point(scale), b.to_fixed_point(scale));
+    assert_eq!(a, b);
+    assert_eq!(a.to_string(), "M.1.2c.1.1.2.2.3.3a5 5 30 0 1 .7.2Z");
+
+    // Relative coordinates don't accumulate rounding error
+    let path = Path::parse("M0 0l0.0004 0 0.0004 0 0.0004 0").unwrap();
+    let path = path.to_fixed_point(scale);
+    assert_eq!(path.to_string(), "M0 0l0 0 .001 0 0 0");
+    for command in path.iter_absolute() {
+        for value in command.end.0 {
+            assert_eq!(value * scale, (value * scale).round());
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "default")]
+fn test_convert_geometric_equivalence() {
+    #[allow(clippy::cast_precision_loss)]
+    fn coordinate(next: &mut impl FnMut(u64) -> u64) -> f64 {
+        (next(2000) as f64 - 1000.0) / 10.0
+    }
+
+    let style_info = convert::StyleInfo::default();
+    let options = convert::Options::default();
+    let assert_eq = |definition: &str| {
+        let path = Path::parse(definition).unwrap();
+        let converted = convert::run(&path, &options, &style_info);
+        assert!(
+            path.geometrically_eq(&converted, 0.01),
+            "{definition} became {converted}"
+        );
+    };
+
+    // A smooth quadratic following one drawn as a line reflects its control point
+    assert_eq("M19.9-3.7T48.9-41.2T-7.8-77.2");
+    assert_eq("M0 0h10t10 10t10-10");
+
+    // A small xorshift generator, so that failures are reproducible
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move |max: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % max
+    };
+    for _ in 0..500 {
+        let mut definition = format!("M{} {}", coordinate(&mut next), coordinate(&mut next));
+        for _ in 0..=next(8) {
+            let command = b"MmLlHhVvCcSsQqTtAaZz"[usize::try_from(next(20)).unwrap()] as char;
+            let args: Vec<f64> = match command.to_ascii_lowercase() {
+                'z' => vec![],
+                'h' | 'v' => vec![coordinate(&mut next)],
+                'c' => (0..6).map(|_| coordinate(&mut next)).collect(),
+                's' | 'q' => (0..4).map(|_| coordinate(&mut next)).collect(),
+                #[allow(clippy::cast_precision_loss)]
+                'a' => vec![
+                    next(30) as f64 + 1.0,
+                    next(30) as f64 + 1.0,
+                    next(90) as f64,
+                    next(2) as f64,
+                    next(2) as f64,
+                    coordinate(&mut next),
+                    coordinate(&mut next),
+                ],
+                _ => vec![coordinate(&mut next), coordinate(&mut next)],
+            };
+            definition.push(command);
+            definition.push_str(
+                &args
+                    .iter()
+                    .map(f64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+        }
+        assert_eq(&definition);
+    }
+}
+
+#[test]
+#[cfg(all(feature = "default", feature = "serde"))]
+fn test_path_serde() {
+    let path = Path::parse("M10 50C1 2 3 4 5 6.5.1.2.3.4.5-5.176e-7a25 25-30 0 1 0 0z").unwrap();
+    let json = serde_json::to_string(&path).unwrap();
+    assert_eq!(serde_json::from_str::<Path>(&json).unwrap(), path);
+
+    // Should serialize individual commands
+    let command = command::Data::ArcTo([25.0, 25.0, -30.0, 0.0, 1.0, 0.0, 0.0]);
+    let json = serde_json::to_string(&command).unwrap();
+    assert_eq!(
+        serde_json::from_str::<command::Data>(&json).unwrap(),
+        command
+    );
+}
@@ -1,7 +1,48 @@
-/// Rounds a number to a specified number of decimal points
-pub fn to_fixed(data: f64, precision: i32) -> f64 {
-    let pow = 10.0_f64.powi(precision);
-    (data * pow).round() / pow
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// How to round a number which lies between two integers
+pub enum RoundingMode {
+    /// Round to the nearest integer, with halves rounded towards positive infinity, like
+    /// `Math.round` in JavaScript, as used by SVGO
+    #[default]
+    HalfUp,
+    /// Round to the nearest integer, with halves rounded to the even integer
+    HalfEven,
+    /// Round towards zero, discarding the fractional part
+    TowardZero,
+}
+
+impl RoundingMode {
+    /// Rounds a number to an integer
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxvg_path::convert::RoundingMode;
+    ///
+    /// assert_eq!(RoundingMode::HalfUp.round(-2.5), -2.0);
+    /// assert_eq!(RoundingMode::HalfEven.round(-2.5), -2.0);
+    /// assert_eq!(RoundingMode::TowardZero.round(-2.7), -2.0);
+    /// ```
+    pub fn round(self, data: f64) -> f64 {
+        match self {
+            Self::HalfUp => {
+                let floor = data.floor();
+                if data - floor >= 0.5 {
+                    floor + 1.0
+                } else {
+                    floor
+                }
+            }
+            Self::HalfEven => data.round_ties_even(),
+            Self::TowardZero => data.trunc(),
+        }
+    }
+
+    /// Rounds a number to a specified number of decimal points
+    pub fn to_fixed(self, data: f64, precision: i32) -> f64 {
+        let pow = 10.0_f64.powi(precision);
+        self.round(data * pow) / pow
+    }
 }
 
 /// Calculate the hypotenuse of two numbers
@@ -26,3 +67,23 @@ pub fn saggita(args: &[f64; 7], error: f64) -> Option<f64> {
     }
     Some(rx - f64::sqrt((rx * rx) - 0.25 * (chord * chord)))
 }
+
+#[test]
+fn rounding_mode() {
+    let cases = [
+        (RoundingMode::HalfUp, [3.0, -2.0, 4.0, -3.0, 3.0, -3.0]),
+        (RoundingMode::HalfEven, [2.0, -2.0, 4.0, -4.0, 3.0, -3.0]),
+        (RoundingMode::TowardZero, [2.0, -2.0, 3.0, -3.0, 2.0, -2.0]),
+    ];
+    for (mode, expected) in cases {
+        let rounded = [2.5, -2.5, 3.5, -3.5, 2.7, -2.7].map(|data| mode.round(data));
+        assert_eq!(rounded, expected, "{mode:?}");
+    }
+
+    assert_eq!(RoundingMode::HalfUp.to_fixed(0.25, 1), 0.3);
+    assert_eq!(RoundingMode::HalfUp.to_fixed(-0.25, 1), -0.2);
+    assert_eq!(RoundingMode::HalfEven.to_fixed(0.25, 1), 0.2);
+    assert_eq!(RoundingMode::TowardZero.to_fixed(-1.239, 2), -1.23);
+    // The largest number below a half isn't rounded up
+    assert_eq!(RoundingMode::HalfUp.round(0.499_999_999_999_999_94), 0.0);
+}
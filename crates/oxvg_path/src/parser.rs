@@ -199,6 +199,23 @@ impl Parser {
         }
         Ok(self.done())
     }
+
+    /// Parses a path definition into `path`, reusing the allocation of its commands
+    pub fn parse_into(&mut self, definition: &str, path: &mut Path) -> Result<(), Error> {
+        path.0.clear();
+        self.path_data = std::mem::take(&mut path.0);
+        match self.parse(definition) {
+            Ok(result) => {
+                *path = result;
+                Ok(())
+            }
+            Err(e) => {
+                *path = self.done();
+                path.0.clear();
+                Err(e)
+            }
+        }
+    }
 }
 
 impl std::fmt::Display for Error {
@@ -1,4 +1,5 @@
 use core::f64;
+use std::cell::OnceCell;
 
 use crate::{
     command::{Data, ID},
@@ -13,6 +14,8 @@ pub struct Points {
     pub min_y: f64,
     pub max_x: f64,
     pub max_y: f64,
+    /// The convex hull of each subpath, found when first checking for intersections
+    hulls: OnceCell<Vec<Point>>,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -222,6 +225,99 @@ impl Points {
 
         points
     }
+
+    /// Returns the convex hull of each subpath, which is only formed once for each set of points
+    pub fn hulls(&self) -> &[Point] {
+        self.hulls
+            .get_or_init(|| self.list.iter().map(Point::convex_hull).collect())
+    }
+
+    /// Adds the subpaths of another set of points, keeping any hulls already formed by either
+    pub fn extend(&mut self, other: Self) {
+        let Self {
+            list,
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+            hulls,
+        } = other;
+        if let Some(self_hulls) = self.hulls.get_mut() {
+            match hulls.into_inner() {
+                Some(hulls) => self_hulls.extend(hulls),
+                None => self_hulls.extend(list.iter().map(Point::convex_hull)),
+            }
+        }
+        self.list.extend(list);
+        self.min_x = f64::min(self.min_x, min_x);
+        self.min_y = f64::min(self.min_y, min_y);
+        self.max_x = f64::max(self.max_x, max_x);
+        self.max_y = f64::max(self.max_y, max_y);
+    }
+
+    /// Removes the last subpath, along with its hull
+    pub fn pop(&mut self) -> Option<Point> {
+        if let Some(hulls) = self.hulls.get_mut() {
+            hulls.pop();
+        }
+        self.list.pop()
+    }
+
+    /// Checks if two sets of points have an intersection by checking convex hulls collision
+    /// using Gilbert-Johnson-Keerthi distance algorithm.
+    ///
+    /// # Panics
+    /// If internal assertions fail
+    pub fn intersects(&self, other: &Self) -> bool {
+        if self.max_x <= other.min_x
+            || other.max_x <= self.min_x
+            || self.max_y <= other.min_y
+            || other.max_y <= self.min_y
+            || self.list.iter().all(|set_1| {
+                other.list.iter().all(|set_2| {
+                    set_1.list[set_1.max_x].0[0] <= set_2.list[set_2.min_x].0[0]
+                        || set_2.list[set_2.max_x].0[0] <= set_1.list[set_1.min_x].0[0]
+                        || set_1.list[set_1.max_y].0[1] <= set_2.list[set_2.min_y].0[1]
+                        || set_2.list[set_2.max_y].0[1] <= set_1.list[set_1.min_y].0[1]
+                })
+            })
+        {
+            log::debug!("no intersection, bounds check failed");
+            return false;
+        }
+
+        let hull_nest_2 = other.hulls();
+        self.hulls().iter().any(|hull_1| {
+            if hull_1.list.len() < 3 {
+                return false;
+            }
+
+            hull_nest_2.iter().any(|hull_2| {
+                if hull_2.list.len() < 3 {
+                    return false;
+                }
+
+                let mut simplex = vec![hull_1.get_support(hull_2, geometry::Point([1.0, 0.0]))];
+                let mut direction = simplex[0].minus();
+                let mut iterations = 10_000;
+
+                loop {
+                    iterations -= 1;
+                    if iterations == 0 {
+                        log::error!("Infinite loop while finding path intersections");
+                        return true;
+                    }
+                    simplex.push(hull_1.get_support(hull_2, direction));
+                    if direction.dot(simplex.last().unwrap()) <= 0.0 {
+                        return false;
+                    }
+                    if geometry::Point::process_simplex(&mut simplex, &mut direction) {
+                        return true;
+                    }
+                }
+            })
+        })
+    }
 }
 
 impl Point {
@@ -353,6 +449,7 @@ fn from_positioned() {
                 min_y: 0.0,
                 max_x: 20.0,
                 max_y: 20.0,
+                ..Points::default()
             }
         )
     );
@@ -375,6 +472,7 @@ fn from_positioned() {
                 min_y: 0.0,
                 max_x: 20.0,
                 max_y: 20.0,
+                ..Points::default()
             }
         )
     );
@@ -397,6 +495,7 @@ fn from_positioned() {
                 min_y: 0.0,
                 max_x: 20.0,
                 max_y: 10.0,
+                ..Points::default()
             }
         )
     );
@@ -419,6 +518,7 @@ fn from_positioned() {
                 min_y: 0.0,
                 max_x: 10.0,
                 max_y: 20.0,
+                ..Points::default()
             }
         )
     );
@@ -447,6 +547,7 @@ fn from_positioned() {
                 min_y: -70.0,
                 max_x: 50.0,
                 max_y: 10.0,
+                ..Points::default()
             }
         )
     );
@@ -474,6 +575,7 @@ fn from_positioned() {
                 min_y: 0.0,
                 max_x: 50.0,
                 max_y: 90.0,
+                ..Points::default()
             }
         )
     );
@@ -502,6 +604,7 @@ fn from_positioned() {
                 min_y: -70.0,
                 max_x: 50.0,
                 max_y: 10.0,
+                ..Points::default()
             }
         )
     );
@@ -534,6 +637,7 @@ fn from_positioned() {
                 min_y: -70.0,
                 max_x: 90.0,
                 max_y: 10.0,
+                ..Points::default()
             }
         )
     );
@@ -560,6 +664,7 @@ fn from_positioned() {
                 min_y: 0.0,
                 max_x: 50.0,
                 max_y: 60.0,
+                ..Points::default()
             }
         )
     );
@@ -597,6 +702,7 @@ fn from_positioned() {
                 min_y: 0.0,
                 max_x: 200.0,
                 max_y: 85.0,
+                ..Points::default()
             }
         )
     );
@@ -629,6 +735,7 @@ fn from_positioned() {
                 min_y: 0.0,
                 max_x: 24.0,
                 max_y: 21.450_566_227_695_43,
+                ..Points::default()
             }
         )
     );
@@ -684,7 +791,20 @@ fn from_positioned() {
                 min_y: -50.0,
                 max_x: 314.0,
                 max_y: 105.0,
+                ..Points::default()
             }
         )
     );
 }
+
+#[test]
+fn intersects() {
+    let square = Points::from_path(&Path::parse("M0 0h10v10H0z").unwrap());
+    let overlapping = Points::from_path(&Path::parse("M5 5h10v10H5z").unwrap());
+    let apart = Points::from_path(&Path::parse("M20 20h10v10H20z").unwrap());
+
+    assert!(square.intersects(&overlapping));
+    assert!(overlapping.intersects(&square));
+    assert!(!square.intersects(&apart));
+    assert!(!apart.intersects(&square));
+}
@@ -70,6 +70,17 @@ impl Path {
     }
 }
 
+#[cfg(feature = "format")]
+impl Path {
+    /// Appends the formatted path to the buffer, avoiding the allocation of a new string for
+    /// each path when formatting many paths.
+    pub fn write_to(&self, buf: &mut String) {
+        use std::fmt::Write;
+
+        write!(buf, "{self}").expect("writing to a string is infallible");
+    }
+}
+
 #[cfg(feature = "format")]
 impl std::fmt::Display for Path {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {